@@ -0,0 +1,543 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    os::unix::{
+        fs::{FileTypeExt, PermissionsExt},
+        net::{UnixListener, UnixStream},
+    },
+    path::PathBuf,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use sysinfo::System;
+
+use crate::{
+    config::Config,
+    system::SystemInfo,
+    vpn::{self, VpnProvider, VpnStatus},
+};
+
+/// Bumped whenever `Request`, `Message` or `HostSnapshot` change shape.
+pub const PROTOCOL_VERSION: u32 = 2;
+/// How often the agent takes a snapshot, and clients ask for one.
+pub const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(1);
+const VPN_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// Clients have this long to introduce themselves, and to ask for the
+/// next snapshot after that.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Slows down guessing the token.
+const DENIED_DELAY: Duration = Duration::from_secs(1);
+/// Connections served at once, authenticated or not. Any more are closed
+/// straight away, so a flood of them can't tie up a thread each.
+const MAX_CONNECTIONS: usize = 16;
+/// No line of the protocol comes anywhere near this.
+const MAX_LINE: u64 = 1024 * 1024;
+const TOP_PROCESSES: usize = 5;
+
+/// Where an agent listens, or a client connects to: `host:port` for TCP,
+/// or a path (optionally prefixed with `unix:`) for a Unix socket.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Address {
+    Tcp(String),
+    Unix(PathBuf),
+}
+
+impl Address {
+    pub fn parse(address: &str) -> Self {
+        if let Some(path) = address.strip_prefix("unix:") {
+            Address::Unix(PathBuf::from(path))
+        } else if address.starts_with('/') || address.starts_with('.') {
+            Address::Unix(PathBuf::from(address))
+        } else {
+            Address::Tcp(address.strip_prefix("tcp:").unwrap_or(address).to_string())
+        }
+    }
+}
+
+/// The first line a client sends.
+#[derive(Debug, Serialize, Deserialize)]
+struct Hello {
+    version: u32,
+    token: String,
+}
+
+/// Every line a client sends after its hello.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Request {
+    /// Asks for the latest snapshot. Clients time the answer, which is how
+    /// they keep their latency figure current.
+    Snapshot,
+}
+
+/// Every line the agent sends: a welcome or a refusal in answer to the
+/// hello, then a snapshot per request.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Message {
+    Welcome { hostname: String },
+    Denied { reason: String },
+    Snapshot(Box<HostSnapshot>),
+}
+
+/// What one host reports to the Fleet tab.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HostSnapshot {
+    pub hostname: String,
+    pub os: String,
+    /// Seconds since boot
+    pub uptime: u64,
+    pub cpu_usage: f32,
+    pub cpu_count: usize,
+    pub load_average: [f64; 3],
+    pub memory_used: u64,
+    pub memory_total: u64,
+    pub swap_used: u64,
+    pub swap_total: u64,
+    pub process_count: usize,
+    /// The busiest processes by CPU
+    pub top_processes: Vec<RemoteProcess>,
+    pub vpn: VpnSummary,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RemoteProcess {
+    pub name: String,
+    pub cpu_usage: f32,
+    pub memory: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VpnSummary {
+    pub connected: bool,
+    /// None when no provider was recognised
+    pub provider: Option<String>,
+    pub server: Option<String>,
+    pub country: Option<String>,
+    pub ip: Option<String>,
+}
+
+impl HostSnapshot {
+    pub fn collect(system_info: &SystemInfo, vpn_status: &VpnStatus) -> Self {
+        let system = &system_info.system;
+        let load = System::load_average();
+
+        let mut processes = system_info.process_usage();
+        let process_count = processes.len();
+        processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+        let top_processes = processes
+            .into_iter()
+            .take(TOP_PROCESSES)
+            .map(|process| RemoteProcess {
+                name: process.name,
+                cpu_usage: process.cpu_usage,
+                memory: process.memory,
+            })
+            .collect();
+
+        Self {
+            hostname: System::host_name().unwrap_or_else(|| "unknown".to_string()),
+            os: System::long_os_version().unwrap_or_default(),
+            uptime: System::uptime(),
+            cpu_usage: system.global_cpu_usage(),
+            cpu_count: system.cpus().len(),
+            load_average: [load.one, load.five, load.fifteen],
+            memory_used: system.used_memory(),
+            memory_total: system.total_memory(),
+            swap_used: system.used_swap(),
+            swap_total: system.total_swap(),
+            process_count,
+            top_processes,
+            vpn: VpnSummary {
+                connected: vpn_status.connected,
+                provider: (vpn_status.provider != VpnProvider::Unknown)
+                    .then(|| vpn_status.provider.name().to_string()),
+                server: vpn_status.server.clone(),
+                country: vpn_status.country.clone(),
+                ip: vpn_status.ip.clone(),
+            },
+        }
+    }
+
+    pub fn memory_percent(&self) -> f64 {
+        if self.memory_total > 0 {
+            self.memory_used as f64 / self.memory_total as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Runs the agent until it's killed: samples this machine every
+/// `SNAPSHOT_INTERVAL` and streams the snapshots to every client that
+/// presents `token`.
+pub fn run(listen: &str, token: String, config: &Config) -> Result<()> {
+    let latest: Arc<Mutex<Option<HostSnapshot>>> = Arc::new(Mutex::new(None));
+    let bound = serve_on(listen, token, Arc::clone(&latest))?;
+    eprintln!("agent listening on {}", bound);
+
+    let mut system_info = SystemInfo::new(config);
+    let mut vpn_status = vpn::get_vpn_status();
+    let mut last_vpn_check = Instant::now();
+    loop {
+        thread::sleep(SNAPSHOT_INTERVAL);
+        system_info.refresh_snapshot();
+        if last_vpn_check.elapsed() >= VPN_CHECK_INTERVAL {
+            vpn_status = vpn::get_vpn_status();
+            last_vpn_check = Instant::now();
+        }
+        let snapshot = HostSnapshot::collect(&system_info, &vpn_status);
+        *latest.lock().unwrap_or_else(|err| err.into_inner()) = Some(snapshot);
+    }
+}
+
+/// Listens on `listen` and serves `latest` to clients from a background
+/// thread. Returns the address actually bound, which has the port picked
+/// for `host:0`.
+fn serve_on(
+    listen: &str,
+    token: String,
+    latest: Arc<Mutex<Option<HostSnapshot>>>,
+) -> Result<String> {
+    if token.is_empty() {
+        bail!("the agent's token can't be empty");
+    }
+    let token = Arc::new(token);
+    match Address::parse(listen) {
+        Address::Tcp(address) => {
+            let listener = TcpListener::bind(&address)
+                .with_context(|| format!("cannot listen on {}", address))?;
+            let bound = listener.local_addr()?.to_string();
+            thread::spawn(move || accept(listener.incoming(), token, latest));
+            Ok(bound)
+        }
+        Address::Unix(path) => {
+            // A socket left behind by an agent that didn't shut down cleanly
+            if fs::symlink_metadata(&path).is_ok_and(|meta| meta.file_type().is_socket()) {
+                fs::remove_file(&path)?;
+            }
+            let listener = UnixListener::bind(&path)
+                .with_context(|| format!("cannot listen on {}", path.display()))?;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+            let bound = path.display().to_string();
+            thread::spawn(move || accept(listener.incoming(), token, latest));
+            Ok(bound)
+        }
+    }
+}
+
+/// The parts of `TcpStream` and `UnixStream` both ends need.
+trait Stream: Read + Write + Send + Sized + 'static {
+    /// Applies to reads and writes alike, so a peer that stops reading
+    /// can't block a write forever either.
+    fn set_timeout(&self, timeout: Duration) -> io::Result<()>;
+    fn duplicate(&self) -> io::Result<Self>;
+
+    /// A buffered reader and a writer over the same connection.
+    fn split(self, timeout: Duration) -> io::Result<(BufReader<Self>, Self)> {
+        self.set_timeout(timeout)?;
+        Ok((BufReader::new(self.duplicate()?), self))
+    }
+}
+
+impl Stream for TcpStream {
+    fn set_timeout(&self, timeout: Duration) -> io::Result<()> {
+        self.set_read_timeout(Some(timeout))?;
+        self.set_write_timeout(Some(timeout))
+    }
+
+    fn duplicate(&self) -> io::Result<Self> {
+        self.try_clone()
+    }
+}
+
+impl Stream for UnixStream {
+    fn set_timeout(&self, timeout: Duration) -> io::Result<()> {
+        self.set_read_timeout(Some(timeout))?;
+        self.set_write_timeout(Some(timeout))
+    }
+
+    fn duplicate(&self) -> io::Result<Self> {
+        self.try_clone()
+    }
+}
+
+/// Serves every incoming connection on its own thread, up to
+/// `MAX_CONNECTIONS` at a time.
+fn accept<S: Stream>(
+    incoming: impl Iterator<Item = io::Result<S>>,
+    token: Arc<String>,
+    latest: Arc<Mutex<Option<HostSnapshot>>>,
+) {
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in incoming.flatten() {
+        if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            active.fetch_sub(1, Ordering::SeqCst);
+            continue;
+        }
+        let (token, latest, active) =
+            (Arc::clone(&token), Arc::clone(&latest), Arc::clone(&active));
+        thread::spawn(move || {
+            // Writes time out too: a client that stops reading is dropped
+            // once the socket buffer fills up
+            if let Ok((reader, writer)) = stream.split(CLIENT_TIMEOUT) {
+                serve(reader, writer, &token, &latest);
+            }
+            active.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+/// Talks to one client: checks its hello, then answers each request with
+/// the latest snapshot until the client goes away or goes quiet.
+fn serve(
+    mut reader: impl BufRead,
+    mut writer: impl Write,
+    token: &str,
+    latest: &Mutex<Option<HostSnapshot>>,
+) {
+    let hello = read_line(&mut reader)
+        .ok()
+        .and_then(|line| serde_json::from_str::<Hello>(&line).ok());
+    let denied = match hello {
+        None => Some("expected a hello".to_string()),
+        Some(hello) if hello.version != PROTOCOL_VERSION => Some(format!(
+            "protocol version {} not supported, agent speaks {}",
+            hello.version, PROTOCOL_VERSION
+        )),
+        Some(hello) if !tokens_match(&hello.token, token) => Some("wrong token".to_string()),
+        Some(_) => None,
+    };
+    if let Some(reason) = denied {
+        thread::sleep(DENIED_DELAY);
+        let _ = send(&mut writer, &Message::Denied { reason });
+        return;
+    }
+
+    let hostname = System::host_name().unwrap_or_else(|| "unknown".to_string());
+    if send(&mut writer, &Message::Welcome { hostname }).is_err() {
+        return;
+    }
+    while let Ok(line) = read_line(&mut reader) {
+        let Ok(Request::Snapshot) = serde_json::from_str(&line) else {
+            return;
+        };
+        // The first snapshot is taken a moment after the agent starts
+        let snapshot = loop {
+            if let Some(snapshot) = latest.lock().unwrap_or_else(|err| err.into_inner()).clone() {
+                break snapshot;
+            }
+            thread::sleep(SNAPSHOT_INTERVAL / 10);
+        };
+        if send(&mut writer, &Message::Snapshot(Box::new(snapshot))).is_err() {
+            return;
+        }
+    }
+}
+
+/// Compares every byte whatever the outcome, so the time taken doesn't
+/// give away how much of the token was right.
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn send(writer: &mut impl Write, message: &impl Serialize) -> Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// One line, refusing to buffer more than any message could need.
+pub fn read_line(reader: &mut impl BufRead) -> Result<String> {
+    let mut line = String::new();
+    if reader.take(MAX_LINE).read_line(&mut line)? == 0 {
+        bail!("connection closed");
+    }
+    Ok(line)
+}
+
+/// An open, authenticated connection to an agent.
+pub struct AgentConnection {
+    reader: Box<dyn BufRead + Send>,
+    writer: Box<dyn Write + Send>,
+    pub hostname: String,
+    /// How long the last request took to come back
+    pub latency: Duration,
+    /// When the last snapshot was asked for
+    polled: Option<Instant>,
+}
+
+impl AgentConnection {
+    /// Connects and introduces itself. Reads time out after `timeout`, so a
+    /// silent agent shows up as an error instead of hanging.
+    pub fn open(address: &str, token: &str, timeout: Duration) -> Result<Self> {
+        let (reader, mut writer): (Box<dyn BufRead + Send>, Box<dyn Write + Send>) =
+            match Address::parse(address) {
+                Address::Tcp(address) => {
+                    let socket = address
+                        .to_socket_addrs()?
+                        .next()
+                        .with_context(|| format!("cannot resolve {}", address))?;
+                    let (reader, writer) =
+                        TcpStream::connect_timeout(&socket, timeout)?.split(timeout)?;
+                    (Box::new(reader), Box::new(writer))
+                }
+                Address::Unix(path) => {
+                    let (reader, writer) = UnixStream::connect(&path)?.split(timeout)?;
+                    (Box::new(reader), Box::new(writer))
+                }
+            };
+
+        let mut hello = serde_json::to_string(&Hello {
+            version: PROTOCOL_VERSION,
+            token: token.to_string(),
+        })?;
+        hello.push('\n');
+        let sent = Instant::now();
+        writer.write_all(hello.as_bytes())?;
+        writer.flush()?;
+
+        let mut connection = Self {
+            reader,
+            writer,
+            hostname: String::new(),
+            latency: Duration::ZERO,
+            polled: None,
+        };
+        match connection.next_message()? {
+            Message::Welcome { hostname } => {
+                connection.hostname = hostname;
+                connection.latency = sent.elapsed();
+            }
+            Message::Denied { reason } => bail!("refused: {}", reason),
+            Message::Snapshot(_) => bail!("agent skipped the welcome"),
+        }
+        Ok(connection)
+    }
+
+    /// Waits out the rest of the interval, then asks for a snapshot and
+    /// times how long it takes to arrive.
+    pub fn next_snapshot(&mut self) -> Result<HostSnapshot> {
+        if let Some(polled) = self.polled {
+            thread::sleep(SNAPSHOT_INTERVAL.saturating_sub(polled.elapsed()));
+        }
+        let polled = Instant::now();
+        self.polled = Some(polled);
+        send(&mut self.writer, &Request::Snapshot)?;
+        let message = self.next_message()?;
+        self.latency = polled.elapsed();
+        match message {
+            Message::Snapshot(snapshot) => Ok(*snapshot),
+            Message::Denied { reason } => bail!("refused: {}", reason),
+            Message::Welcome { .. } => bail!("unexpected welcome"),
+        }
+    }
+
+    fn next_message(&mut self) -> Result<Message> {
+        let line = read_line(&mut self.reader)?;
+        Ok(serde_json::from_str(&line)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "secret";
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// An agent serving a made-up snapshot on `listen`, without sampling
+    /// this machine. Returns the address to connect to.
+    fn start_agent(listen: &str) -> String {
+        let snapshot = HostSnapshot {
+            hostname: "test-host".to_string(),
+            cpu_count: 4,
+            memory_total: 1024,
+            ..HostSnapshot::default()
+        };
+        serve_on(
+            listen,
+            TOKEN.to_string(),
+            Arc::new(Mutex::new(Some(snapshot))),
+        )
+        .unwrap()
+    }
+
+    fn check_agent(address: &str) {
+        let mut connection = AgentConnection::open(address, TOKEN, TIMEOUT).unwrap();
+        assert!(!connection.hostname.is_empty());
+        let snapshot = connection.next_snapshot().unwrap();
+        assert_eq!(snapshot.hostname, "test-host");
+        assert_eq!(snapshot.cpu_count, 4);
+        assert_eq!(snapshot.memory_total, 1024);
+        // Measured again on every request
+        connection.latency = Duration::MAX;
+        connection.next_snapshot().unwrap();
+        assert!(connection.latency < TIMEOUT);
+
+        let err = AgentConnection::open(address, "guess", TIMEOUT)
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "refused: wrong token");
+    }
+
+    #[test]
+    fn serves_snapshots_over_tcp() {
+        let address = start_agent("127.0.0.1:0");
+        check_agent(&address);
+
+        // A client from a newer release
+        let stream = TcpStream::connect(&address).unwrap();
+        let (mut reader, mut writer) = stream.split(TIMEOUT).unwrap();
+        writer
+            .write_all(b"{\"version\":999,\"token\":\"secret\"}\n")
+            .unwrap();
+        let reply = read_line(&mut reader).unwrap();
+        match serde_json::from_str(&reply).unwrap() {
+            Message::Denied { reason } => assert!(reason.contains("version 999")),
+            other => panic!("expected a refusal, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn serves_snapshots_over_a_unix_socket() {
+        let path =
+            std::env::temp_dir().join(format!("myworkspace-agent-{}.sock", std::process::id()));
+        let address = start_agent(&path.display().to_string());
+        check_agent(&address);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn closes_connections_over_the_limit() {
+        let address = start_agent("127.0.0.1:0");
+        // Connected but silent, each holding a slot until its hello times out
+        let _idle: Vec<TcpStream> = (0..MAX_CONNECTIONS)
+            .map(|_| TcpStream::connect(&address).unwrap())
+            .collect();
+        // Closed without an answer, before the hello could even be read
+        let started = Instant::now();
+        assert!(AgentConnection::open(&address, TOKEN, TIMEOUT).is_err());
+        assert!(started.elapsed() < CLIENT_TIMEOUT);
+    }
+
+    #[test]
+    fn refuses_an_empty_token() {
+        let latest = Arc::new(Mutex::new(None));
+        assert!(serve_on("127.0.0.1:0", String::new(), latest).is_err());
+    }
+}
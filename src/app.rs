@@ -1,4 +1,16 @@
-use std::collections::VecDeque;
+use ratatui::layout::Rect;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, path::PathBuf, time::Instant};
+
+use crate::{
+    config::{ColumnConfig, Config},
+    explorer::Explorer,
+    fleet::Fleet,
+    history::{self, TimeSeries},
+    services::{ServiceAction, ServiceRequest},
+    system::ProcessCategory,
+    theme,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Tab {
@@ -6,79 +18,416 @@ pub enum Tab {
     Processes,
     SystemInfo,
     Vpn,
+    Containers,
+    Services,
+    Sensors,
+    Memory,
+    Connections,
+    Fleet,
 }
 
 impl Tab {
+    pub const ALL: [Tab; 10] = [
+        Tab::Overview,
+        Tab::Processes,
+        Tab::SystemInfo,
+        Tab::Vpn,
+        Tab::Containers,
+        Tab::Services,
+        Tab::Sensors,
+        Tab::Memory,
+        Tab::Connections,
+        Tab::Fleet,
+    ];
+
     pub fn next(&self) -> Self {
-        match self {
-            Tab::Overview => Tab::Processes,
-            Tab::Processes => Tab::SystemInfo,
-            Tab::SystemInfo => Tab::Vpn,
-            Tab::Vpn => Tab::Overview,
-        }
+        Tab::ALL[(self.index() + 1) % Tab::ALL.len()]
     }
 
     pub fn previous(&self) -> Self {
+        Tab::ALL[(self.index() + Tab::ALL.len() - 1) % Tab::ALL.len()]
+    }
+
+    pub fn title(&self) -> &str {
         match self {
-            Tab::Overview => Tab::Vpn,
-            Tab::Processes => Tab::Overview,
-            Tab::SystemInfo => Tab::Processes,
-            Tab::Vpn => Tab::SystemInfo,
+            Tab::Overview => "Overview [1]",
+            Tab::Processes => "Processes [2]",
+            Tab::SystemInfo => "System Info [3]",
+            Tab::Vpn => "VPN [4]",
+            Tab::Containers => "Containers [5]",
+            Tab::Services => "Services [6]",
+            Tab::Sensors => "Sensors [7]",
+            Tab::Memory => "Memory [8]",
+            Tab::Connections => "Connections [9]",
+            Tab::Fleet => "Fleet [0]",
         }
     }
 
+    pub fn index(&self) -> usize {
+        Tab::ALL.iter().position(|tab| tab == self).unwrap_or(0)
+    }
+
+    /// The number key that opens the tab, '1' to '9' and then '0'.
+    pub fn key(&self) -> char {
+        char::from_digit((self.index() as u32 + 1) % 10, 10).unwrap_or('?')
+    }
+
+    pub fn from_key(key: char) -> Option<Self> {
+        Tab::ALL.into_iter().find(|tab| tab.key() == key)
+    }
+}
+
+/// Which list on the System Info tab the selection keys move through.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiskPane {
+    Mounts,
+    Devices,
 }
 
+/// Which sockets the Connections tab lists.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub enum ProcessSort {
+pub enum ConnectionFilter {
+    All,
+    Listening,
+    /// External connections that don't go through the VPN interface
+    OutsideVpn,
+}
+
+impl ConnectionFilter {
+    pub fn name(&self) -> &str {
+        match self {
+            ConnectionFilter::All => "all",
+            ConnectionFilter::Listening => "listening only",
+            ConnectionFilter::OutsideVpn => "not via VPN",
+        }
+    }
+
+    fn next(&self) -> Self {
+        match self {
+            ConnectionFilter::All => ConnectionFilter::Listening,
+            ConnectionFilter::Listening => ConnectionFilter::OutsideVpn,
+            ConnectionFilter::OutsideVpn => ConnectionFilter::All,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessColumn {
+    Pid,
+    Name,
     Cpu,
     Memory,
-    Name,
-    Pid,
+    User,
+    State,
+    Threads,
+    Command,
+    StartTime,
+    RunTime,
+    VirtualMemory,
+    DiskRead,
+    DiskWrite,
+    DiskReadRate,
+    DiskWriteRate,
+    Sockets,
+    NetReceived,
+    NetSent,
+    NetRate,
+    Nice,
+    Priority,
+}
+
+impl ProcessColumn {
+    pub const ALL: [ProcessColumn; 21] = [
+        ProcessColumn::Pid,
+        ProcessColumn::Name,
+        ProcessColumn::Cpu,
+        ProcessColumn::Memory,
+        ProcessColumn::User,
+        ProcessColumn::State,
+        ProcessColumn::Threads,
+        ProcessColumn::Command,
+        ProcessColumn::StartTime,
+        ProcessColumn::RunTime,
+        ProcessColumn::VirtualMemory,
+        ProcessColumn::DiskRead,
+        ProcessColumn::DiskWrite,
+        ProcessColumn::DiskReadRate,
+        ProcessColumn::DiskWriteRate,
+        ProcessColumn::Sockets,
+        ProcessColumn::NetReceived,
+        ProcessColumn::NetSent,
+        ProcessColumn::NetRate,
+        ProcessColumn::Nice,
+        ProcessColumn::Priority,
+    ];
+
+    pub fn title(&self) -> &str {
+        match self {
+            ProcessColumn::Pid => "PID",
+            ProcessColumn::Name => "Name",
+            ProcessColumn::Cpu => "CPU",
+            ProcessColumn::Memory => "Memory",
+            ProcessColumn::User => "User",
+            ProcessColumn::State => "State",
+            ProcessColumn::Threads => "Threads",
+            ProcessColumn::Command => "Command",
+            ProcessColumn::StartTime => "Started",
+            ProcessColumn::RunTime => "Run Time",
+            ProcessColumn::VirtualMemory => "Virtual",
+            ProcessColumn::DiskRead => "Disk Read",
+            ProcessColumn::DiskWrite => "Disk Write",
+            ProcessColumn::DiskReadRate => "Read/s",
+            ProcessColumn::DiskWriteRate => "Write/s",
+            ProcessColumn::Sockets => "Sockets",
+            ProcessColumn::NetReceived => "Net In",
+            ProcessColumn::NetSent => "Net Out",
+            ProcessColumn::NetRate => "Net/s",
+            ProcessColumn::Nice => "Nice",
+            ProcessColumn::Priority => "Prio",
+        }
+    }
+
+    pub fn default_width(&self) -> u16 {
+        match self {
+            ProcessColumn::Pid => 8,
+            ProcessColumn::Name => 30,
+            ProcessColumn::Cpu => 8,
+            ProcessColumn::Memory => 12,
+            ProcessColumn::User => 12,
+            ProcessColumn::State => 10,
+            ProcessColumn::Threads => 8,
+            ProcessColumn::Command => 50,
+            ProcessColumn::StartTime => 12,
+            ProcessColumn::RunTime => 12,
+            ProcessColumn::VirtualMemory => 12,
+            ProcessColumn::DiskRead => 12,
+            ProcessColumn::DiskWrite => 12,
+            ProcessColumn::DiskReadRate => 12,
+            ProcessColumn::DiskWriteRate => 12,
+            ProcessColumn::Sockets => 8,
+            ProcessColumn::NetReceived => 12,
+            ProcessColumn::NetSent => 12,
+            ProcessColumn::NetRate => 12,
+            ProcessColumn::Nice => 5,
+            ProcessColumn::Priority => 5,
+        }
+    }
+
+    /// Columns that need the (comparatively slow) socket scan.
+    pub fn needs_network(&self) -> bool {
+        matches!(
+            self,
+            ProcessColumn::Sockets
+                | ProcessColumn::NetReceived
+                | ProcessColumn::NetSent
+                | ProcessColumn::NetRate
+        )
+    }
+}
+
+/// Screen regions recorded during the last render, so mouse events can be
+/// mapped back to whatever was drawn under the cursor.
+#[derive(Debug, Clone, Default)]
+pub struct HitAreas {
+    pub tabs: Vec<(Rect, Tab)>,
+    pub categories: Vec<Rect>,
+    /// How many category boxes sit side by side, for the arrow keys
+    pub category_columns: usize,
+    pub process_list: Option<Rect>,
+    /// PIDs of the process table rows in display order
+    pub process_rows: Vec<u32>,
+    /// Index of the first visible row in the process table
+    pub process_offset: usize,
+    pub sort_headers: Vec<(Rect, ProcessColumn)>,
+    /// Selectable list on tabs other than Processes, and its visible rows
+    pub list_area: Option<Rect>,
+    pub list_offset: usize,
+    pub list_rows: usize,
+    /// Unit names of the services table rows in display order
+    pub service_rows: Vec<String>,
+    /// Mount points of the disk usage list in display order
+    pub mount_rows: Vec<String>,
+    pub cpu_chart: Option<Rect>,
+    pub memory_chart: Option<Rect>,
+}
+
+/// How much history the Overview charts show at once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartWindow {
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+    Day,
+}
+
+impl ChartWindow {
+    const ALL: [ChartWindow; 4] = [
+        ChartWindow::OneMinute,
+        ChartWindow::FiveMinutes,
+        ChartWindow::OneHour,
+        ChartWindow::Day,
+    ];
+
+    pub fn seconds(&self) -> f64 {
+        match self {
+            ChartWindow::OneMinute => 60.0,
+            ChartWindow::FiveMinutes => 5.0 * 60.0,
+            ChartWindow::OneHour => 60.0 * 60.0,
+            ChartWindow::Day => 24.0 * 60.0 * 60.0,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            ChartWindow::OneMinute => "1m",
+            ChartWindow::FiveMinutes => "5m",
+            ChartWindow::OneHour => "1h",
+            ChartWindow::Day => "24h",
+        }
+    }
+
+    fn zoomed(&self, steps: isize) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|window| window == self)
+            .unwrap_or(0);
+        let index = index.saturating_add_signed(steps).min(Self::ALL.len() - 1);
+        Self::ALL[index]
+    }
 }
 
 pub struct App {
     pub should_quit: bool,
     pub show_help: bool,
     pub current_tab: Tab,
-    pub process_sort: ProcessSort,
+    pub process_sort: ProcessColumn,
     pub sort_ascending: bool,
     pub process_scroll: usize,
-    pub cpu_history: VecDeque<f32>,
-    pub memory_history: VecDeque<f64>,
+    pub cpu_history: TimeSeries,
+    pub memory_history: TimeSeries,
+    pub chart_window: ChartWindow,
+    /// Right edge of the charts as Unix seconds, None follows the present
+    pub chart_end: Option<f64>,
     pub history_size: usize,
     pub selected_category: usize,
     pub category_expanded: bool,
     pub command_mode: bool,
     pub command_buffer: String,
     pub show_all_processes: bool,
+    pub hit_areas: HitAreas,
+    pub mouse_position: Option<(u16, u16)>,
+    pub config: Config,
+    /// Why the config file couldn't be loaded. It is never saved over
+    /// while this is set.
+    pub config_error: Option<String>,
+    pub column_picker: bool,
+    pub column_picker_index: usize,
+    pub process_detail: Option<u32>,
+    pub detail_scroll: usize,
+    pub detail_cpu_history: VecDeque<u64>,
+    pub detail_memory_history: VecDeque<u64>,
+    pub container_selected: usize,
+    pub containers_only: bool,
+    /// Block device highlighted in the System Info I/O table
+    pub disk_selected: usize,
+    pub mount_selected: usize,
+    pub disk_pane: DiskPane,
+    /// Directory size explorer opened from a mount on the System Info tab
+    pub explorer: Option<Explorer>,
+    pub service_selected: usize,
+    pub services_failed_only: bool,
+    /// Action waiting for a y/n confirmation
+    pub service_confirm: Option<ServiceAction>,
+    /// Work for the main loop, which owns the service monitor
+    pub service_request: Option<ServiceRequest>,
+    pub service_message: Option<String>,
+    /// An action is still waiting on systemctl
+    pub service_running: bool,
+    /// Unit and recent journal lines shown under the services table
+    pub service_journal: Option<(String, Vec<String>)>,
+    /// Sensor whose history is charted on the Sensors tab
+    pub sensor_selected: usize,
+    pub connection_selected: usize,
+    pub connection_filter: ConnectionFilter,
+    /// Unix sockets are many and mostly local plumbing, so they're opt-in
+    pub show_unix_sockets: bool,
+    /// Agents watched on the Fleet tab
+    pub fleet: Fleet,
+    /// Host shown in detail on the Fleet tab, 0 is this machine and the
+    /// agents follow
+    pub fleet_selected: usize,
+    pub host_picker: bool,
+    pub host_picker_index: usize,
+    /// Work for the main loop, which has the data to export
+    pub export_request: bool,
+    /// Save the next frame drawn to a file
+    pub screenshot_request: bool,
+    /// Where the last export or screenshot went, or why it failed, and when
+    pub export_status: Option<(Instant, Result<PathBuf, String>)>,
 }
 
 impl Default for App {
     fn default() -> Self {
+        Self::with_config(Config::default())
+    }
+}
+
+impl App {
+    pub fn with_config(config: Config) -> Self {
         Self {
             should_quit: false,
             show_help: false,
             current_tab: Tab::Overview,
-            process_sort: ProcessSort::Cpu,
-            sort_ascending: false,
+            process_sort: config.processes.sort,
+            sort_ascending: config.processes.sort_ascending,
             process_scroll: 0,
-            cpu_history: VecDeque::with_capacity(100),
-            memory_history: VecDeque::with_capacity(100),
+            cpu_history: TimeSeries::new(),
+            memory_history: TimeSeries::new(),
+            chart_window: ChartWindow::FiveMinutes,
+            chart_end: None,
             history_size: 100,
             selected_category: 0,
             category_expanded: false,
             command_mode: false,
             command_buffer: String::new(),
             show_all_processes: false,
+            hit_areas: HitAreas::default(),
+            mouse_position: None,
+            config,
+            config_error: None,
+            column_picker: false,
+            column_picker_index: 0,
+            process_detail: None,
+            detail_scroll: 0,
+            detail_cpu_history: VecDeque::with_capacity(100),
+            detail_memory_history: VecDeque::with_capacity(100),
+            container_selected: 0,
+            containers_only: false,
+            disk_selected: 0,
+            mount_selected: 0,
+            disk_pane: DiskPane::Mounts,
+            explorer: None,
+            service_selected: 0,
+            services_failed_only: false,
+            service_confirm: None,
+            service_request: None,
+            service_message: None,
+            service_running: false,
+            service_journal: None,
+            sensor_selected: 0,
+            connection_selected: 0,
+            connection_filter: ConnectionFilter::All,
+            show_unix_sockets: false,
+            fleet: Fleet::default(),
+            fleet_selected: 0,
+            host_picker: false,
+            host_picker_index: 0,
+            export_request: false,
+            screenshot_request: false,
+            export_status: None,
         }
     }
-}
-
-impl App {
-    pub fn new() -> Self {
-        Self::default()
-    }
 
     pub fn quit(&mut self) {
         self.should_quit = true;
@@ -88,16 +437,19 @@ impl App {
         self.show_help = !self.show_help;
     }
 
+    pub fn cycle_theme(&mut self) {
+        let appearance = &mut self.config.appearance;
+        appearance.theme = appearance.theme.next();
+        theme::apply(appearance);
+        self.save_config();
+    }
+
     pub fn next_tab(&mut self) {
-        self.current_tab = self.current_tab.next();
-        self.process_scroll = 0;
-        self.category_expanded = false;
+        self.select_tab(self.current_tab.next());
     }
 
     pub fn previous_tab(&mut self) {
-        self.current_tab = self.current_tab.previous();
-        self.process_scroll = 0;
-        self.category_expanded = false;
+        self.select_tab(self.current_tab.previous());
     }
 
     pub fn toggle_category_expanded(&mut self) {
@@ -110,17 +462,278 @@ impl App {
         self.process_scroll = 0;
     }
 
+    /// Cycles the sort key through the currently visible columns.
     pub fn cycle_process_sort(&mut self) {
-        self.process_sort = match self.process_sort {
-            ProcessSort::Cpu => ProcessSort::Memory,
-            ProcessSort::Memory => ProcessSort::Name,
-            ProcessSort::Name => ProcessSort::Pid,
-            ProcessSort::Pid => ProcessSort::Cpu,
-        };
+        let columns = &self.config.processes.columns;
+        let next = columns
+            .iter()
+            .position(|c| c.column == self.process_sort)
+            .map(|i| (i + 1) % columns.len())
+            .unwrap_or(0);
+        if let Some(column) = columns.get(next) {
+            self.process_sort = column.column;
+        }
+        self.save_config();
     }
 
     pub fn toggle_sort_order(&mut self) {
         self.sort_ascending = !self.sort_ascending;
+        self.save_config();
+    }
+
+    pub fn sort_by(&mut self, column: ProcessColumn) {
+        if self.process_sort == column {
+            self.toggle_sort_order();
+        } else {
+            self.process_sort = column;
+            self.save_config();
+        }
+    }
+
+    /// Widens or narrows the column currently used for sorting.
+    pub fn resize_sort_column(&mut self, delta: i16) {
+        let sort = self.process_sort;
+        if let Some(column) = self
+            .config
+            .processes
+            .columns
+            .iter_mut()
+            .find(|c| c.column == sort)
+        {
+            column.width = column
+                .width
+                .saturating_add_signed(delta)
+                .clamp(ColumnConfig::MIN_WIDTH, ColumnConfig::MAX_WIDTH);
+            self.save_config();
+        }
+    }
+
+    pub fn toggle_column_picker(&mut self) {
+        self.column_picker = !self.column_picker;
+        self.column_picker_index = 0;
+    }
+
+    pub fn column_picker_up(&mut self) {
+        self.column_picker_index = self.column_picker_index.saturating_sub(1);
+    }
+
+    pub fn column_picker_down(&mut self) {
+        if self.column_picker_index + 1 < ProcessColumn::ALL.len() {
+            self.column_picker_index += 1;
+        }
+    }
+
+    /// Asks the main loop to export what's on screen.
+    pub fn request_export(&mut self) {
+        self.export_request = true;
+    }
+
+    pub fn request_screenshot(&mut self) {
+        self.screenshot_request = true;
+    }
+
+    /// Opens the host switcher on the host currently shown.
+    pub fn toggle_host_picker(&mut self) {
+        self.host_picker = !self.host_picker;
+        self.host_picker_index = self.fleet_selected;
+    }
+
+    pub fn host_picker_up(&mut self) {
+        self.host_picker_index = self.host_picker_index.saturating_sub(1);
+    }
+
+    pub fn host_picker_down(&mut self) {
+        // This machine comes first, then the agents
+        if self.host_picker_index < self.fleet.len() {
+            self.host_picker_index += 1;
+        }
+    }
+
+    pub fn pick_host(&mut self) {
+        self.fleet_selected = self.host_picker_index;
+        self.host_picker = false;
+        self.select_tab(Tab::Fleet);
+    }
+
+    /// Shows or hides the column under the picker cursor. The last visible
+    /// column can't be hidden.
+    pub fn toggle_picked_column(&mut self) {
+        let column = ProcessColumn::ALL[self.column_picker_index];
+        let columns = &mut self.config.processes.columns;
+
+        if let Some(index) = columns.iter().position(|c| c.column == column) {
+            if columns.len() > 1 {
+                columns.remove(index);
+            }
+        } else {
+            columns.push(ColumnConfig::new(column));
+        }
+
+        if !columns.iter().any(|c| c.column == self.process_sort) {
+            self.process_sort = columns[0].column;
+        }
+        self.save_config();
+    }
+
+    /// Selected row of the list on the current (non-Processes) tab.
+    fn list_selection(&mut self) -> Option<&mut usize> {
+        match self.current_tab {
+            Tab::SystemInfo => match (self.explorer.as_mut(), self.disk_pane) {
+                (Some(explorer), _) => Some(&mut explorer.selected),
+                (None, DiskPane::Mounts) => Some(&mut self.mount_selected),
+                (None, DiskPane::Devices) => Some(&mut self.disk_selected),
+            },
+            Tab::Containers => Some(&mut self.container_selected),
+            Tab::Services => Some(&mut self.service_selected),
+            Tab::Sensors => Some(&mut self.sensor_selected),
+            Tab::Connections => Some(&mut self.connection_selected),
+            Tab::Fleet => Some(&mut self.fleet_selected),
+            _ => None,
+        }
+    }
+
+    pub fn list_up(&mut self) {
+        if let Some(selected) = self.list_selection() {
+            *selected = selected.saturating_sub(1);
+        }
+    }
+
+    pub fn list_down(&mut self) {
+        let rows = self.hit_areas.list_rows;
+        if let Some(selected) = self.list_selection().filter(|s| **s + 1 < rows) {
+            *selected += 1;
+        }
+    }
+
+    pub fn select_list_row(&mut self, row: usize) {
+        let rows = self.hit_areas.list_rows;
+        if let Some(selected) = self.list_selection().filter(|_| row < rows) {
+            *selected = row;
+        }
+    }
+
+    pub fn toggle_containers_only(&mut self) {
+        self.containers_only = !self.containers_only;
+        self.container_selected = 0;
+    }
+
+    pub fn toggle_pseudo_mounts(&mut self) {
+        self.config.disks.show_pseudo = !self.config.disks.show_pseudo;
+        self.mount_selected = 0;
+        self.save_config();
+    }
+
+    pub fn toggle_disk_pane(&mut self) {
+        self.disk_pane = match self.disk_pane {
+            DiskPane::Mounts => DiskPane::Devices,
+            DiskPane::Devices => DiskPane::Mounts,
+        };
+    }
+
+    /// Starts scanning the selected mount in the directory explorer.
+    pub fn open_explorer(&mut self) {
+        if let Some(mount) = self.hit_areas.mount_rows.get(self.mount_selected) {
+            self.explorer = Some(Explorer::scan(PathBuf::from(mount)));
+        }
+    }
+
+    pub fn close_explorer(&mut self) {
+        self.explorer = None;
+    }
+
+    pub fn toggle_services_failed_only(&mut self) {
+        self.services_failed_only = !self.services_failed_only;
+        self.service_selected = 0;
+    }
+
+    pub fn cycle_connection_filter(&mut self) {
+        self.connection_filter = self.connection_filter.next();
+        self.connection_selected = 0;
+    }
+
+    pub fn toggle_unix_sockets(&mut self) {
+        self.show_unix_sockets = !self.show_unix_sockets;
+        self.connection_selected = 0;
+    }
+
+    /// Unit name of the highlighted row in the services table.
+    pub fn selected_service(&self) -> Option<&String> {
+        self.hit_areas.service_rows.get(self.service_selected)
+    }
+
+    /// Asks for confirmation before touching the selected unit. One action
+    /// runs at a time, so the message always matches what's running.
+    pub fn request_service_action(&mut self, action: ServiceAction) {
+        if self.selected_service().is_some() && !self.service_running {
+            self.service_confirm = Some(action);
+        }
+    }
+
+    pub fn confirm_service_action(&mut self) {
+        if let Some(action) = self.service_confirm.take()
+            && let Some(unit) = self.selected_service().cloned()
+        {
+            self.service_running = true;
+            self.service_message = Some(format!("Running systemctl {} {}...", action.name(), unit));
+            self.service_request = Some(ServiceRequest::Action(action, unit));
+        }
+    }
+
+    pub fn cancel_service_action(&mut self) {
+        self.service_confirm = None;
+    }
+
+    pub fn toggle_service_journal(&mut self) {
+        if self.service_journal.is_some() {
+            self.service_journal = None;
+        } else if let Some(unit) = self.selected_service().cloned() {
+            // Filled in when journalctl comes back
+            self.service_journal = Some((unit.clone(), vec!["Loading...".to_string()]));
+            self.service_request = Some(ServiceRequest::Journal(unit));
+        }
+    }
+
+    /// Whether anything on screen needs per-process socket usage.
+    pub fn wants_process_network(&self) -> bool {
+        self.process_detail.is_some()
+            || self
+                .config
+                .processes
+                .columns
+                .iter()
+                .any(|c| c.column.needs_network())
+    }
+
+    pub fn save_config(&mut self) {
+        self.config.processes.sort = self.process_sort;
+        self.config.processes.sort_ascending = self.sort_ascending;
+        // The file on disk has the user's settings, only with a mistake in
+        // them; writing the defaults over it would lose them
+        if self.config_error.is_some() {
+            return;
+        }
+        // Nowhere to show the error inside the TUI, and a read-only config
+        // directory shouldn't stop the monitor from working.
+        let _ = self.config.save();
+    }
+
+    pub fn select_tab(&mut self, tab: Tab) {
+        if self.current_tab != tab {
+            self.current_tab = tab;
+            self.process_scroll = 0;
+            self.category_expanded = false;
+            self.process_detail = None;
+            self.service_confirm = None;
+        }
+    }
+
+    pub fn select_category(&mut self, index: usize) {
+        if self.selected_category == index {
+            self.toggle_category_expanded();
+        } else {
+            self.selected_category = index;
+            self.process_scroll = 0;
+        }
     }
 
     pub fn scroll_up(&mut self) {
@@ -128,47 +741,127 @@ impl App {
     }
 
     pub fn scroll_down(&mut self) {
-        self.process_scroll = self.process_scroll.saturating_add(1);
+        let rows = self.hit_areas.process_rows.len();
+        if rows == 0 || self.process_scroll + 1 < rows {
+            self.process_scroll = self.process_scroll.saturating_add(1);
+        }
     }
 
-    pub fn add_cpu_data(&mut self, value: f32) {
-        if self.cpu_history.len() >= self.history_size {
-            self.cpu_history.pop_front();
+    /// PID of the highlighted row in the process table, if one is shown.
+    pub fn selected_process(&self) -> Option<u32> {
+        self.hit_areas
+            .process_rows
+            .get(self.process_scroll)
+            .copied()
+    }
+
+    pub fn open_process_detail(&mut self, pid: u32) {
+        self.process_detail = Some(pid);
+        self.detail_scroll = 0;
+        self.detail_cpu_history.clear();
+        self.detail_memory_history.clear();
+    }
+
+    pub fn close_process_detail(&mut self) {
+        self.process_detail = None;
+    }
+
+    pub fn detail_scroll_up(&mut self) {
+        self.detail_scroll = self.detail_scroll.saturating_sub(1);
+    }
+
+    pub fn detail_scroll_down(&mut self) {
+        self.detail_scroll = self.detail_scroll.saturating_add(1);
+    }
+
+    /// Records one sample for the detail sparklines. CPU is stored in tenths
+    /// of a percent since sparklines only take integers.
+    pub fn add_detail_sample(&mut self, cpu_usage: f32, memory: u64) {
+        if self.detail_cpu_history.len() >= self.history_size {
+            self.detail_cpu_history.pop_front();
+        }
+        self.detail_cpu_history
+            .push_back((cpu_usage * 10.0).max(0.0) as u64);
+
+        if self.detail_memory_history.len() >= self.history_size {
+            self.detail_memory_history.pop_front();
         }
-        self.cpu_history.push_back(value);
+        self.detail_memory_history.push_back(memory);
+    }
+
+    pub fn add_cpu_data(&mut self, value: f32) {
+        self.cpu_history.push(value as f64);
     }
 
     pub fn add_memory_data(&mut self, value: f64) {
-        if self.memory_history.len() >= self.history_size {
-            self.memory_history.pop_front();
+        self.memory_history.push(value);
+    }
+
+    /// Time span shown by the Overview charts, as Unix seconds.
+    pub fn chart_range(&self) -> (f64, f64) {
+        let end = self.chart_end.unwrap_or_else(history::now);
+        (end - self.chart_window.seconds(), end)
+    }
+
+    pub fn zoom_chart(&mut self, steps: isize) {
+        self.chart_window = self.chart_window.zoomed(steps);
+        self.pan_chart(0.0);
+    }
+
+    /// Moves the charts by a quarter of their window per step, negative
+    /// steps going back in time. Panning up to the present follows it again.
+    pub fn pan_chart(&mut self, steps: f64) {
+        let now = history::now();
+        let window = self.chart_window.seconds();
+        let oldest = now - history::RETENTION.as_secs_f64() + window;
+        let end = (self.chart_end.unwrap_or(now) + steps * window / 4.0).max(oldest);
+        self.chart_end = (end < now).then_some(end);
+    }
+
+    pub fn follow_chart(&mut self) {
+        self.chart_end = None;
+    }
+
+    /// Categories per row as last drawn, the grid has two.
+    fn category_columns(&self) -> usize {
+        match self.hit_areas.category_columns {
+            0 => 2,
+            columns => columns,
         }
-        self.memory_history.push_back(value);
     }
 
     pub fn move_category_left(&mut self) {
-        if self.selected_category % 2 == 1 {
+        if !self
+            .selected_category
+            .is_multiple_of(self.category_columns())
+        {
             self.selected_category -= 1;
         }
         self.process_scroll = 0;
     }
 
     pub fn move_category_right(&mut self) {
-        if self.selected_category % 2 == 0 {
+        let columns = self.category_columns();
+        if self.selected_category % columns + 1 < columns
+            && self.selected_category + 1 < ProcessCategory::ALL.len()
+        {
             self.selected_category += 1;
         }
         self.process_scroll = 0;
     }
 
     pub fn move_category_up(&mut self) {
-        if self.selected_category >= 2 {
-            self.selected_category -= 2;
+        let columns = self.category_columns();
+        if self.selected_category >= columns {
+            self.selected_category -= columns;
         }
         self.process_scroll = 0;
     }
 
     pub fn move_category_down(&mut self) {
-        if self.selected_category < 6 {
-            self.selected_category += 2;
+        let columns = self.category_columns();
+        if self.selected_category + columns < ProcessCategory::ALL.len() {
+            self.selected_category += columns;
         }
         self.process_scroll = 0;
     }
@@ -0,0 +1,110 @@
+use ratatui::{Terminal, backend::TestBackend};
+use std::time::{Duration, Instant};
+
+use crate::{
+    app::{App, Tab},
+    config::Config,
+    system::{ProcessInfo, SystemInfo, categorize_process},
+    ui,
+    vpn::VpnStatus,
+};
+
+pub const DEFAULT_PROCESSES: usize = 2500;
+const FRAMES: u32 = 200;
+const WIDTH: u16 = 200;
+const HEIGHT: u16 = 50;
+
+/// Names spread over every category, so each category box has work to do.
+const NAMES: [&str; 8] = [
+    "systemd-journald",
+    "firefox",
+    "cargo",
+    "alacritty",
+    "nvim",
+    "spotify",
+    "pipewire",
+    "myapp",
+];
+
+/// Times the Processes tab against `count` made-up processes: building and
+/// sorting a snapshot (what happens once per refresh), and drawing frames of
+/// each process view in between refreshes.
+pub fn run(count: usize) -> anyhow::Result<()> {
+    let config = Config::default();
+    let mut system_info = SystemInfo::new(&config);
+    let mut app = App::with_config(config);
+    app.current_tab = Tab::Processes;
+    let vpn_status = VpnStatus::new();
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT))?;
+
+    println!("{count} processes, {WIDTH}x{HEIGHT} terminal, {FRAMES} frames per view");
+
+    let start = Instant::now();
+    for round in 0..FRAMES {
+        let mut processes = system_info.process_view.take_buffer();
+        processes.extend((0..count).map(|index| fake_process(index, round)));
+        system_info.process_view.set_processes(processes);
+        system_info
+            .process_view
+            .sort(app.process_sort, app.sort_ascending);
+    }
+    report("snapshot + sort", start.elapsed());
+
+    let views: [(&str, bool, bool); 3] = [
+        ("category grid", false, false),
+        ("expanded category", true, false),
+        ("all processes", false, true),
+    ];
+    for (name, expanded, all) in views {
+        app.category_expanded = expanded;
+        app.show_all_processes = all;
+        // Scroll halfway down so the visible window isn't just the top rows
+        app.process_scroll = if all { count / 2 } else { 0 };
+
+        let start = Instant::now();
+        for _ in 0..FRAMES {
+            system_info
+                .process_view
+                .sort(app.process_sort, app.sort_ascending);
+            terminal.draw(|frame| ui::render(frame, &mut app, &system_info, &vpn_status))?;
+        }
+        report(name, start.elapsed());
+    }
+
+    Ok(())
+}
+
+fn report(name: &str, elapsed: Duration) {
+    println!(
+        "{:20} {:>8.3} ms",
+        name,
+        elapsed.as_secs_f64() * 1000.0 / FRAMES as f64
+    );
+}
+
+fn fake_process(index: usize, round: u32) -> ProcessInfo {
+    let name = format!("{}-{}", NAMES[index % NAMES.len()], index);
+    // Vary the numbers between rounds like a live system would
+    let load = ((index as u32).wrapping_mul(2_654_435_761) ^ round) % 1000;
+    ProcessInfo {
+        pid: index as u32 + 1,
+        category: categorize_process(&name),
+        command: format!("/usr/bin/{} --worker {}", name, index),
+        name,
+        cpu_usage: load as f32 / 10.0,
+        memory: load as u64 * 1024 * 1024,
+        virtual_memory: load as u64 * 4 * 1024 * 1024,
+        user: "user".to_string(),
+        state: "Sleeping".to_string(),
+        threads: 1 + load % 32,
+        start_time: 1_700_000_000 + index as u64,
+        run_time: load as u64 * 60,
+        disk_read: load as u64 * 4096,
+        disk_written: load as u64 * 2048,
+        disk_read_rate: 0,
+        disk_write_rate: 0,
+        network: None,
+        nice: 0,
+        priority: 20,
+    }
+}
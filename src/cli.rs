@@ -0,0 +1,134 @@
+use anyhow::{Context, Result, bail};
+use std::path::PathBuf;
+
+use crate::{config::HostConfig, export::ExportFormat};
+
+pub const USAGE: &str = "\
+Usage:
+  myWorkspace [--connect ADDRESS]... [--token TOKEN]
+  myWorkspace --agent --listen ADDRESS [--token TOKEN]
+  myWorkspace --export FORMAT [--output DIRECTORY]
+  myWorkspace --bench [PROCESSES]
+
+Options:
+  --connect ADDRESS  Watch the agent at ADDRESS on the Fleet tab (repeatable),
+                     or ssh:[user@]host to read a host over ssh instead
+  --agent            Serve snapshots of this machine instead of opening the TUI
+  --listen ADDRESS   Where the agent listens: host:port, or a Unix socket path
+  --token TOKEN      Shared secret between agent and clients
+                     (default: $MYWORKSPACE_TOKEN, then [fleet] token)
+  --export FORMAT    Write history, processes, disks and VPN state as csv,
+                     json or parquet, then exit
+  --output DIRECTORY Where --export writes (default: [export] directory)
+  --bench            Time the Processes tab against made-up processes
+  -h, --help         Show this help";
+
+/// Environment variable read when `--token` isn't given, which keeps the
+/// token out of `ps`.
+const TOKEN_VARIABLE: &str = "MYWORKSPACE_TOKEN";
+
+#[derive(Debug)]
+pub enum Mode {
+    Monitor {
+        connect: Vec<HostConfig>,
+    },
+    Agent {
+        listen: String,
+    },
+    Export {
+        format: ExportFormat,
+        output: Option<PathBuf>,
+    },
+    Bench {
+        processes: usize,
+    },
+    Help,
+}
+
+#[derive(Debug)]
+pub struct Args {
+    pub mode: Mode,
+    pub token: Option<String>,
+}
+
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Args> {
+    let mut args = args.into_iter().peekable();
+    let mut connect = Vec::new();
+    let mut agent = false;
+    let mut listen = None;
+    let mut bench = None;
+    let mut export = None;
+    let mut output = None;
+    let mut token = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--connect" => connect.push(HostConfig {
+                address: args.next().context("--connect needs an address")?,
+                name: None,
+                token: None,
+            }),
+            "--agent" => agent = true,
+            "--listen" => listen = Some(args.next().context("--listen needs an address")?),
+            "--token" => token = Some(args.next().context("--token needs a value")?),
+            "--export" => {
+                let format = args.next().context("--export needs a format")?;
+                export = Some(
+                    ExportFormat::parse(&format)
+                        .with_context(|| format!("unknown export format: {}", format))?,
+                );
+            }
+            "--output" => {
+                output = Some(PathBuf::from(
+                    args.next().context("--output needs a directory")?,
+                ))
+            }
+            "--bench" => {
+                let processes = args
+                    .next_if(|next| !next.starts_with('-'))
+                    .map(|count| count.parse().context("--bench takes a process count"))
+                    .transpose()?;
+                bench = Some(processes.unwrap_or(crate::bench::DEFAULT_PROCESSES));
+            }
+            "-h" | "--help" => {
+                return Ok(Args {
+                    mode: Mode::Help,
+                    token,
+                });
+            }
+            other => bail!("unknown argument: {}", other),
+        }
+    }
+
+    // An empty token would let in anyone who sends an empty one
+    if token.as_deref() == Some("") {
+        bail!("--token can't be empty");
+    }
+    let token = token.or_else(|| {
+        std::env::var(TOKEN_VARIABLE)
+            .ok()
+            .filter(|token| !token.is_empty())
+    });
+    if let Some(format) = export {
+        if agent || listen.is_some() || bench.is_some() || !connect.is_empty() {
+            bail!("--export can't be combined with --agent, --connect or --bench");
+        }
+        return Ok(Args {
+            mode: Mode::Export { format, output },
+            token,
+        });
+    }
+    if output.is_some() {
+        bail!("--output only applies to --export");
+    }
+    let mode = match (agent, listen, bench) {
+        (true, Some(listen), None) if connect.is_empty() => Mode::Agent { listen },
+        (true, None, _) => bail!("--agent needs --listen"),
+        (true, ..) => bail!("--agent can't be combined with --connect or --bench"),
+        (false, Some(_), _) => bail!("--listen only applies to --agent"),
+        (false, None, Some(processes)) => Mode::Bench { processes },
+        (false, None, None) => Mode::Monitor { connect },
+    };
+
+    Ok(Args { mode, token })
+}
@@ -0,0 +1,369 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use crate::{
+    app::ProcessColumn, export::ExportFormat, screenshot::ScreenshotFormat, theme::ThemeName,
+};
+
+/// User settings, stored as TOML in `$XDG_CONFIG_HOME/myWorkspace/config.toml`
+/// (or `~/.config/myWorkspace/config.toml`). Anything missing from the file
+/// falls back to the defaults below.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub processes: ProcessTableConfig,
+    pub containers: ContainersConfig,
+    pub disks: DisksConfig,
+    pub power: PowerConfig,
+    pub sensors: SensorsConfig,
+    pub appearance: AppearanceConfig,
+    pub overview: OverviewConfig,
+    pub fleet: FleetConfig,
+    pub export: ExportConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessTableConfig {
+    pub columns: Vec<ColumnConfig>,
+    pub sort: ProcessColumn,
+    pub sort_ascending: bool,
+}
+
+impl Default for ProcessTableConfig {
+    fn default() -> Self {
+        Self {
+            columns: [
+                ProcessColumn::Pid,
+                ProcessColumn::Name,
+                ProcessColumn::Cpu,
+                ProcessColumn::Memory,
+            ]
+            .into_iter()
+            .map(ColumnConfig::new)
+            .collect(),
+            sort: ProcessColumn::Cpu,
+            sort_ascending: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContainersConfig {
+    /// Where the cgroup v2 hierarchy is mounted
+    pub cgroup_root: PathBuf,
+}
+
+impl Default for ContainersConfig {
+    fn default() -> Self {
+        Self {
+            cgroup_root: PathBuf::from("/sys/fs/cgroup"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisksConfig {
+    /// Also list the mounts matched by `hidden_filesystems`
+    pub show_pseudo: bool,
+    /// Filesystem types that aren't real storage (tmpfs, overlays, snaps, ...)
+    pub hidden_filesystems: Vec<String>,
+    /// Inode usage, in percent, above which a mount gets flagged
+    pub inode_warning_percent: f64,
+    /// Warn when a mount is predicted to fill up within this many minutes,
+    /// 0 turns the warning off
+    pub fill_warning_minutes: u64,
+}
+
+impl Default for DisksConfig {
+    fn default() -> Self {
+        Self {
+            show_pseudo: false,
+            hidden_filesystems: [
+                "tmpfs",
+                "devtmpfs",
+                "ramfs",
+                "overlay",
+                "squashfs",
+                "fuse.snapfuse",
+                "efivarfs",
+                "proc",
+                "sysfs",
+                "cgroup",
+                "cgroup2",
+                "devpts",
+                "nsfs",
+            ]
+            .map(String::from)
+            .to_vec(),
+            inode_warning_percent: 90.0,
+            fill_warning_minutes: 360,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerConfig {
+    /// Where batteries and AC adapters are listed
+    pub sysfs_root: PathBuf,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        Self {
+            sysfs_root: PathBuf::from("/sys/class/power_supply"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SensorsConfig {
+    /// Where the hwmon chips are listed
+    pub hwmon_root: PathBuf,
+}
+
+impl Default for SensorsConfig {
+    fn default() -> Self {
+        Self {
+            hwmon_root: PathBuf::from("/sys/class/hwmon"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppearanceConfig {
+    pub theme: ThemeName,
+    /// Per-role overrides on top of the theme, e.g. `accent = "#268bd2"`.
+    /// Roles are the fields of `theme::Theme`, colors are names, indexes or
+    /// hex codes.
+    pub colors: BTreeMap<String, String>,
+}
+
+/// Agents the Fleet tab connects to.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FleetConfig {
+    /// Token `--agent` expects from clients, and the one sent to hosts
+    /// without their own
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    pub hosts: Vec<HostConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostConfig {
+    /// `host:port`, a path for a Unix socket, or `ssh:[user@]host`
+    pub address: String,
+    /// Shown instead of the address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+/// Where `E`, `--export` and `P` write to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportConfig {
+    /// Relative paths start from the working directory
+    pub directory: PathBuf,
+    pub format: ExportFormat,
+    /// What `P` saves the screen as
+    pub screenshot_format: ScreenshotFormat,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("."),
+            format: ExportFormat::Csv,
+            screenshot_format: ScreenshotFormat::Html,
+        }
+    }
+}
+
+/// The panels the Overview tab is built from, as rows stacked top to bottom
+/// with the panels of each row side by side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverviewConfig {
+    pub rows: Vec<DashboardRow>,
+}
+
+impl OverviewConfig {
+    pub fn contains(&self, panel: Panel) -> bool {
+        self.rows
+            .iter()
+            .any(|row| row.panels.iter().any(|entry| entry.panel == panel))
+    }
+}
+
+impl Default for OverviewConfig {
+    fn default() -> Self {
+        let row = |size, panels: &[(Panel, PanelSize)]| DashboardRow {
+            size,
+            panels: panels
+                .iter()
+                .map(|&(panel, size)| DashboardPanel { panel, size })
+                .collect(),
+        };
+
+        Self {
+            rows: vec![
+                row(
+                    PanelSize::Length(3),
+                    &[
+                        (Panel::CpuGauge, PanelSize::Ratio(1)),
+                        (Panel::MemoryGauge, PanelSize::Ratio(1)),
+                    ],
+                ),
+                row(
+                    PanelSize::Length(3),
+                    &[(Panel::Scheduler, PanelSize::Ratio(1))],
+                ),
+                row(
+                    PanelSize::Length(10),
+                    &[(Panel::CpuChart, PanelSize::Ratio(1))],
+                ),
+                row(
+                    PanelSize::Length(10),
+                    &[(Panel::MemoryChart, PanelSize::Ratio(1))],
+                ),
+                row(
+                    PanelSize::Ratio(1),
+                    &[
+                        (Panel::Temperatures, PanelSize::Ratio(2)),
+                        (Panel::Battery, PanelSize::Ratio(3)),
+                    ],
+                ),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardRow {
+    #[serde(default)]
+    pub size: PanelSize,
+    pub panels: Vec<DashboardPanel>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DashboardPanel {
+    pub panel: Panel,
+    #[serde(default)]
+    pub size: PanelSize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Panel {
+    CpuGauge,
+    MemoryGauge,
+    Scheduler,
+    CpuChart,
+    MemoryChart,
+    Temperatures,
+    Battery,
+    TopProcesses,
+    /// CPU and memory stacked by process category
+    Categories,
+    Network,
+    Vpn,
+    Disks,
+}
+
+/// How much room a row gets vertically, or a panel within its row
+/// horizontally. Ratios share out whatever the fixed sizes leave over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PanelSize {
+    /// Exact number of lines or columns
+    Length(u16),
+    Percentage(u16),
+    Ratio(u16),
+    /// At least this many lines or columns, growing like `ratio = 1`
+    Min(u16),
+}
+
+impl Default for PanelSize {
+    fn default() -> Self {
+        PanelSize::Ratio(1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ColumnConfig {
+    pub column: ProcessColumn,
+    pub width: u16,
+}
+
+impl ColumnConfig {
+    pub const MIN_WIDTH: u16 = 3;
+    pub const MAX_WIDTH: u16 = 120;
+
+    pub fn new(column: ProcessColumn) -> Self {
+        Self {
+            column,
+            width: column.default_width(),
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+        Some(base.join("myWorkspace").join("config.toml"))
+    }
+
+    /// Loads the config file, using the defaults if there is none. A file
+    /// that can't be read or parsed is an error, so the caller can say so
+    /// instead of quietly overwriting it later.
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err).with_context(|| format!("cannot read {}", path.display()));
+            }
+        };
+        let mut config: Self =
+            toml::from_str(&content).with_context(|| format!("cannot parse {}", path.display()))?;
+        // An empty dashboard isn't useful, fall back to the default one
+        if config.overview.rows.is_empty() {
+            config.overview = OverviewConfig::default();
+        }
+        Ok(config)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, toml::to_string_pretty(self)?)?;
+        // Agent tokens are secrets
+        if self.fleet.token.is_some() || self.fleet.hosts.iter().any(|host| host.token.is_some()) {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+        }
+
+        Ok(())
+    }
+}
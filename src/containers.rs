@@ -0,0 +1,329 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::sysfs::{read_key_values, read_number};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+    Containerd,
+    CriO,
+    Lxc,
+}
+
+impl ContainerRuntime {
+    pub fn name(&self) -> &str {
+        match self {
+            ContainerRuntime::Docker => "Docker",
+            ContainerRuntime::Podman => "Podman",
+            ContainerRuntime::Containerd => "containerd",
+            ContainerRuntime::CriO => "CRI-O",
+            ContainerRuntime::Lxc => "LXC",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Container {
+    pub runtime: ContainerRuntime,
+    pub id: String,
+    pub name: Option<String>,
+    pub image: Option<String>,
+}
+
+impl Container {
+    pub fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(12)]
+    }
+
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| self.short_id().to_string())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CgroupStats {
+    /// Path relative to the cgroup root, e.g. `/system.slice/docker-<id>.scope`
+    pub path: String,
+    pub container: Option<Container>,
+    pub pids: Vec<u32>,
+    pub cpu_percent: f64,
+    pub memory_current: Option<u64>,
+    pub memory_max: Option<u64>,
+    pub pids_current: Option<u64>,
+    pub nr_periods: u64,
+    pub nr_throttled: u64,
+    pub throttled_usec: u64,
+}
+
+impl CgroupStats {
+    pub fn memory_percent(&self) -> Option<f64> {
+        match (self.memory_current, self.memory_max) {
+            (Some(current), Some(max)) if max > 0 => Some(current as f64 / max as f64 * 100.0),
+            _ => None,
+        }
+    }
+
+    pub fn throttled_percent(&self) -> f64 {
+        if self.nr_periods > 0 {
+            self.nr_throttled as f64 / self.nr_periods as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// (name, image) per full container ID.
+type ContainerNames = HashMap<String, (String, String)>;
+
+/// Groups processes by their cgroup v2 path and reads the usage counters the
+/// kernel keeps for each group. Container runtimes name their scopes after
+/// the container ID, which is how groups get matched to containers.
+pub struct ContainerMonitor {
+    root: PathBuf,
+    pub groups: Vec<CgroupStats>,
+    previous_usage: HashMap<String, (u64, Instant)>,
+    names: ContainerNames,
+    last_name_refresh: Option<Instant>,
+    /// Name lookups run on their own thread, a hung daemon would otherwise
+    /// hang the UI with them
+    name_lookup: (Sender<ContainerNames>, Receiver<ContainerNames>),
+    looking_up_names: bool,
+}
+
+/// Asking the runtimes for names spawns processes, so don't do it every tick.
+const NAME_REFRESH_RATE: Duration = Duration::from_secs(10);
+
+impl ContainerMonitor {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            groups: Vec::new(),
+            previous_usage: HashMap::new(),
+            names: HashMap::new(),
+            last_name_refresh: None,
+            name_lookup: mpsc::channel(),
+            looking_up_names: false,
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        if let Ok(names) = self.name_lookup.1.try_recv() {
+            self.names = names;
+            self.looking_up_names = false;
+        }
+        // One lookup at a time, however long the last one is taking
+        if !self.looking_up_names
+            && self
+                .last_name_refresh
+                .is_none_or(|last| last.elapsed() >= NAME_REFRESH_RATE)
+        {
+            let sender = self.name_lookup.0.clone();
+            thread::spawn(move || {
+                let _ = sender.send(container_names());
+            });
+            self.looking_up_names = true;
+            self.last_name_refresh = Some(Instant::now());
+        }
+
+        let now = Instant::now();
+        let mut groups: Vec<CgroupStats> = pids_by_cgroup()
+            .into_iter()
+            .map(|(path, pids)| self.read_group(path, pids, now))
+            .collect();
+
+        groups.sort_by(|a, b| {
+            b.container
+                .is_some()
+                .cmp(&a.container.is_some())
+                .then(
+                    b.cpu_percent
+                        .partial_cmp(&a.cpu_percent)
+                        .unwrap_or(std::cmp::Ordering::Equal),
+                )
+                .then(a.path.cmp(&b.path))
+        });
+
+        self.previous_usage
+            .retain(|path, _| groups.iter().any(|group| &group.path == path));
+        self.groups = groups;
+    }
+
+    fn read_group(&mut self, path: String, mut pids: Vec<u32>, now: Instant) -> CgroupStats {
+        let dir = self.root.join(path.trim_start_matches('/'));
+        let cpu_stat = read_key_values(&dir.join("cpu.stat"));
+        let usage_usec = cpu_stat.get("usage_usec").copied().unwrap_or(0);
+
+        let cpu_percent = match self.previous_usage.get(&path) {
+            Some(&(previous, at)) => {
+                let elapsed = now.duration_since(at).as_micros() as f64;
+                if elapsed > 0.0 {
+                    usage_usec.saturating_sub(previous) as f64 / elapsed * 100.0
+                } else {
+                    0.0
+                }
+            }
+            None => 0.0,
+        };
+        self.previous_usage.insert(path.clone(), (usage_usec, now));
+
+        let mut container = detect_container(&path);
+        if let Some(container) = container.as_mut()
+            && let Some((name, image)) = self.names.get(&container.id)
+        {
+            container.name = Some(name.clone());
+            container.image = Some(image.clone());
+        }
+
+        pids.sort();
+
+        CgroupStats {
+            memory_current: read_number(&dir.join("memory.current")),
+            // "max" means unlimited, which read_number turns into None
+            memory_max: read_number(&dir.join("memory.max")),
+            pids_current: read_number(&dir.join("pids.current")),
+            nr_periods: cpu_stat.get("nr_periods").copied().unwrap_or(0),
+            nr_throttled: cpu_stat.get("nr_throttled").copied().unwrap_or(0),
+            throttled_usec: cpu_stat.get("throttled_usec").copied().unwrap_or(0),
+            path,
+            container,
+            pids,
+            cpu_percent,
+        }
+    }
+}
+
+/// Where the cgroup v2 hierarchy lives below the configured root. On hybrid
+/// setups it is mounted under "unified".
+pub fn unified_root(root: &Path) -> PathBuf {
+    if !root.join("cgroup.controllers").exists() && root.join("unified").is_dir() {
+        root.join("unified")
+    } else {
+        root.to_path_buf()
+    }
+}
+
+/// Reads the unified (`0::`) entry of every `/proc/<pid>/cgroup`.
+fn pids_by_cgroup() -> HashMap<String, Vec<u32>> {
+    let mut groups: HashMap<String, Vec<u32>> = HashMap::new();
+
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return groups;
+    };
+
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|n| n.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let Ok(content) = fs::read_to_string(entry.path().join("cgroup")) else {
+            continue;
+        };
+
+        if let Some(path) = content.lines().find_map(|line| line.strip_prefix("0::")) {
+            groups.entry(path.to_string()).or_default().push(pid);
+        }
+    }
+
+    groups
+}
+
+/// Recognises the scope names the common runtimes create, e.g.
+/// `docker-<id>.scope`, `libpod-<id>.scope`, `cri-containerd-<id>.scope`,
+/// `crio-<id>.scope`, `/docker/<id>` and `lxc.payload.<name>`.
+pub fn detect_container(path: &str) -> Option<Container> {
+    for segment in path.rsplit('/') {
+        let scope = segment.strip_suffix(".scope").unwrap_or(segment);
+
+        let (runtime, id) = if let Some(id) = scope.strip_prefix("docker-") {
+            (ContainerRuntime::Docker, id)
+        } else if let Some(id) = scope.strip_prefix("libpod-conmon-") {
+            (ContainerRuntime::Podman, id)
+        } else if let Some(id) = scope.strip_prefix("libpod-") {
+            (ContainerRuntime::Podman, id)
+        } else if let Some(id) = scope.strip_prefix("cri-containerd-") {
+            (ContainerRuntime::Containerd, id)
+        } else if let Some(id) = scope.strip_prefix("crio-") {
+            (ContainerRuntime::CriO, id)
+        } else if let Some(name) = scope.strip_prefix("lxc.payload.") {
+            return Some(Container {
+                runtime: ContainerRuntime::Lxc,
+                id: name.to_string(),
+                name: Some(name.to_string()),
+                image: None,
+            });
+        } else if is_container_id(scope) && path.contains("/docker/") {
+            (ContainerRuntime::Docker, scope)
+        } else {
+            continue;
+        };
+
+        if is_container_id(id) {
+            return Some(Container {
+                runtime,
+                id: id.to_string(),
+                name: None,
+                image: None,
+            });
+        }
+    }
+
+    None
+}
+
+fn is_container_id(id: &str) -> bool {
+    id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Asks docker and podman for container names, keyed by full container ID.
+/// Whichever CLI isn't installed (or isn't allowed to talk to its daemon) is
+/// just skipped.
+fn container_names() -> ContainerNames {
+    let mut names = HashMap::new();
+
+    for cli in ["docker", "podman"] {
+        let output = Command::new(cli)
+            .args([
+                "ps",
+                "--no-trunc",
+                "--format",
+                "{{.ID}}\t{{.Names}}\t{{.Image}}",
+            ])
+            .output();
+
+        let Ok(output) = output else {
+            continue;
+        };
+
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let mut parts = line.split('\t');
+            if let (Some(id), Some(name), Some(image)) = (parts.next(), parts.next(), parts.next())
+            {
+                names.insert(id.to_string(), (name.to_string(), image.to_string()));
+            }
+        }
+    }
+
+    names
+}
+
+/// Name of a process straight from `/proc/<pid>/comm`, so it also works for
+/// processes sysinfo hasn't picked up yet.
+pub fn process_name(pid: u32) -> String {
+    fs::read_to_string(format!("/proc/{}/comm", pid))
+        .map(|name| name.trim().to_string())
+        .unwrap_or_else(|_| "?".to_string())
+}
@@ -0,0 +1,102 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// How often free space is sampled, whichever tab is open.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+/// Only the recent past says anything about what a build is doing right now.
+const WINDOW: Duration = Duration::from_secs(30 * 60);
+/// Less history than this is too noisy to extrapolate from.
+const MIN_SPAN: Duration = Duration::from_secs(60);
+/// Anything further out than this is not worth mentioning.
+const MAX_PREDICTION: Duration = Duration::from_secs(30 * 24 * 3600);
+
+#[derive(Debug, Clone, Copy)]
+pub struct FillTrend {
+    /// How fast the mount is filling up, in bytes per second
+    pub bytes_per_second: f64,
+    pub time_to_full: Duration,
+}
+
+/// Rolling window of free-space samples per mount, with a least-squares fit
+/// through them to estimate when the mount runs full.
+pub struct DiskTrend {
+    samples: HashMap<String, VecDeque<(Instant, u64)>>,
+    last_sample: Option<Instant>,
+}
+
+impl DiskTrend {
+    pub fn new() -> Self {
+        Self {
+            samples: HashMap::new(),
+            last_sample: None,
+        }
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.last_sample
+            .is_none_or(|last| last.elapsed() >= SAMPLE_INTERVAL)
+    }
+
+    /// Records the available space of each (mount point, available bytes).
+    pub fn record<'a>(&mut self, disks: impl IntoIterator<Item = (&'a str, u64)>) {
+        let now = Instant::now();
+        self.last_sample = Some(now);
+
+        let mut seen = Vec::new();
+        for (mount_point, available) in disks {
+            let samples = self.samples.entry(mount_point.to_string()).or_default();
+            samples.push_back((now, available));
+            while samples
+                .front()
+                .is_some_and(|(at, _)| now.duration_since(*at) > WINDOW)
+            {
+                samples.pop_front();
+            }
+            seen.push(mount_point.to_string());
+        }
+
+        self.samples
+            .retain(|mount_point, _| seen.contains(mount_point));
+    }
+
+    /// Time until the mount is full at the current rate, if it is filling up
+    /// at all.
+    pub fn trend(&self, mount_point: &str) -> Option<FillTrend> {
+        let samples = self.samples.get(mount_point)?;
+        let (first_at, _) = *samples.front()?;
+        let (last_at, available) = *samples.back()?;
+        if last_at.duration_since(first_at) < MIN_SPAN {
+            return None;
+        }
+
+        let points: Vec<(f64, f64)> = samples
+            .iter()
+            .map(|(at, free)| (at.duration_since(first_at).as_secs_f64(), *free as f64))
+            .collect();
+        let count = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+        let covariance: f64 = points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum();
+        let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        if variance == 0.0 {
+            return None;
+        }
+
+        // Free space going down means a negative slope
+        let bytes_per_second = -covariance / variance;
+        if bytes_per_second <= 0.0 {
+            return None;
+        }
+
+        let time_to_full = Duration::from_secs_f64(available as f64 / bytes_per_second);
+        (time_to_full <= MAX_PREDICTION).then_some(FillTrend {
+            bytes_per_second,
+            time_to_full,
+        })
+    }
+}
@@ -0,0 +1,187 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::Path,
+    time::Instant,
+};
+
+/// Sectors in /proc/diskstats are always 512 bytes, whatever the device uses.
+const SECTOR_SIZE: u64 = 512;
+
+/// Cumulative counters from one line of `/proc/diskstats`.
+#[derive(Debug, Clone, Copy, Default)]
+struct RawStats {
+    reads: u64,
+    sectors_read: u64,
+    read_ms: u64,
+    writes: u64,
+    sectors_written: u64,
+    write_ms: u64,
+    in_flight: u64,
+    io_ms: u64,
+    weighted_io_ms: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DeviceStats {
+    pub name: String,
+    pub read_rate: u64,
+    pub write_rate: u64,
+    pub read_iops: f64,
+    pub write_iops: f64,
+    /// Requests currently in flight
+    pub in_flight: u64,
+    /// Average number of requests queued over the last interval
+    pub queue_depth: f64,
+    pub read_latency_ms: f64,
+    pub write_latency_ms: f64,
+    /// Share of the interval the device was busy
+    pub utilization: f64,
+    pub total_read: u64,
+    pub total_written: u64,
+}
+
+/// Samples `/proc/diskstats` and turns the counters into per-device rates.
+pub struct DiskIoMonitor {
+    pub devices: Vec<DeviceStats>,
+    /// (read, write) bytes per second, oldest first
+    pub history: HashMap<String, VecDeque<(u64, u64)>>,
+    pub history_size: usize,
+    previous: HashMap<String, RawStats>,
+    last_sample: Option<Instant>,
+}
+
+impl DiskIoMonitor {
+    pub fn new() -> Self {
+        Self {
+            devices: Vec::new(),
+            history: HashMap::new(),
+            history_size: 100,
+            previous: HashMap::new(),
+            last_sample: None,
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        let now = Instant::now();
+        let elapsed_ms = self
+            .last_sample
+            .map(|last| now.duration_since(last).as_secs_f64() * 1000.0)
+            .unwrap_or(0.0);
+        self.last_sample = Some(now);
+
+        let current = read_diskstats();
+        let mut devices = Vec::new();
+
+        for (name, stats) in &current {
+            // Never-used loop/ram devices are just noise
+            if stats.reads == 0 && stats.writes == 0 {
+                continue;
+            }
+
+            let mut device = DeviceStats {
+                name: name.clone(),
+                in_flight: stats.in_flight,
+                total_read: stats.sectors_read * SECTOR_SIZE,
+                total_written: stats.sectors_written * SECTOR_SIZE,
+                ..Default::default()
+            };
+
+            if let Some(previous) = self.previous.get(name).filter(|_| elapsed_ms > 0.0) {
+                let seconds = elapsed_ms / 1000.0;
+                let reads = stats.reads.saturating_sub(previous.reads);
+                let writes = stats.writes.saturating_sub(previous.writes);
+
+                device.read_rate = ((stats.sectors_read.saturating_sub(previous.sectors_read)
+                    * SECTOR_SIZE) as f64
+                    / seconds) as u64;
+                device.write_rate = ((stats
+                    .sectors_written
+                    .saturating_sub(previous.sectors_written)
+                    * SECTOR_SIZE) as f64
+                    / seconds) as u64;
+                device.read_iops = reads as f64 / seconds;
+                device.write_iops = writes as f64 / seconds;
+                device.read_latency_ms =
+                    average(stats.read_ms.saturating_sub(previous.read_ms), reads);
+                device.write_latency_ms =
+                    average(stats.write_ms.saturating_sub(previous.write_ms), writes);
+                device.queue_depth = stats.weighted_io_ms.saturating_sub(previous.weighted_io_ms)
+                    as f64
+                    / elapsed_ms;
+                device.utilization =
+                    (stats.io_ms.saturating_sub(previous.io_ms) as f64 / elapsed_ms * 100.0)
+                        .min(100.0);
+            }
+
+            let history = self.history.entry(name.clone()).or_default();
+            if history.len() >= self.history_size {
+                history.pop_front();
+            }
+            history.push_back((device.read_rate, device.write_rate));
+
+            devices.push(device);
+        }
+
+        devices.sort_by(|a, b| a.name.cmp(&b.name));
+
+        self.history
+            .retain(|name, _| devices.iter().any(|device| &device.name == name));
+        self.devices = devices;
+        self.previous = current;
+    }
+
+    pub fn device(&self, name: &str) -> Option<&DeviceStats> {
+        self.devices.iter().find(|device| device.name == name)
+    }
+}
+
+fn average(total_ms: u64, count: u64) -> f64 {
+    if count > 0 {
+        total_ms as f64 / count as f64
+    } else {
+        0.0
+    }
+}
+
+fn read_diskstats() -> HashMap<String, RawStats> {
+    fs::read_to_string("/proc/diskstats")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace().skip(2);
+            let name = fields.next()?.to_string();
+            let values: Vec<u64> = fields.filter_map(|value| value.parse().ok()).collect();
+            if values.len() < 11 {
+                return None;
+            }
+
+            Some((
+                name,
+                RawStats {
+                    reads: values[0],
+                    sectors_read: values[2],
+                    read_ms: values[3],
+                    writes: values[4],
+                    sectors_written: values[6],
+                    write_ms: values[7],
+                    in_flight: values[8],
+                    io_ms: values[9],
+                    weighted_io_ms: values[10],
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Kernel name of the block device behind a mount source, e.g. `/dev/sda1`
+/// gives `sda1` and `/dev/mapper/root` gives `dm-0`.
+pub fn block_device_name(source: &str) -> Option<String> {
+    if !source.starts_with("/dev/") {
+        return None;
+    }
+
+    let path = fs::canonicalize(source).unwrap_or_else(|_| Path::new(source).to_path_buf());
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
@@ -1,6 +1,14 @@
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Position, Rect};
 use std::time::Duration;
 
+use crate::{
+    app::{App, Tab},
+    services::ServiceAction,
+};
+
 pub fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
     if app.show_help {
         match key.code {
@@ -12,6 +20,100 @@ pub fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
         return;
     }
 
+    if app.column_picker {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('c') | KeyCode::Char('q') => {
+                app.toggle_column_picker();
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                app.column_picker_up();
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                app.column_picker_down();
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                app.toggle_picked_column();
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    if app.host_picker {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('H') | KeyCode::Char('q') => {
+                app.toggle_host_picker();
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                app.host_picker_up();
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                app.host_picker_down();
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                app.pick_host();
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    if app.service_confirm.is_some() {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                app.confirm_service_action();
+            }
+            _ => {
+                app.cancel_service_action();
+            }
+        }
+        return;
+    }
+
+    if app.current_tab == Tab::SystemInfo
+        && let Some(explorer) = app.explorer.as_mut()
+    {
+        if explorer.confirm_delete {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => explorer.delete_selected(),
+                _ => explorer.cancel_delete(),
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.quit();
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                app.close_explorer();
+            }
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => {
+                explorer.open_selected();
+            }
+            KeyCode::Backspace | KeyCode::Char('h') | KeyCode::Left => {
+                explorer.go_up();
+            }
+            KeyCode::Char('s') => {
+                explorer.toggle_sort();
+            }
+            KeyCode::Char('r') => {
+                explorer.rescan();
+            }
+            KeyCode::Char('D') | KeyCode::Delete => {
+                explorer.request_delete();
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                app.list_up();
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                app.list_down();
+            }
+            _ => {}
+        }
+        return;
+    }
+
     if app.command_mode {
         match key.code {
             KeyCode::Esc => {
@@ -41,23 +143,64 @@ pub fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
         KeyCode::Char('?') => {
             app.toggle_help();
         }
+        KeyCode::Char('t') => {
+            app.cycle_theme();
+        }
         KeyCode::Tab => {
             app.next_tab();
         }
         KeyCode::BackTab => {
             app.previous_tab();
         }
-        KeyCode::Char('1') => {
-            app.current_tab = crate::app::Tab::Overview;
+        KeyCode::Char(key @ '0'..='9') => {
+            if let Some(tab) = crate::app::Tab::from_key(key) {
+                app.select_tab(tab);
+            }
+        }
+        KeyCode::Char('E') => {
+            app.request_export();
+        }
+        KeyCode::Char('P') => {
+            app.request_screenshot();
+        }
+        KeyCode::Char('H') => {
+            app.toggle_host_picker();
+        }
+        KeyCode::Char('f') if app.current_tab == crate::app::Tab::Connections => {
+            app.cycle_connection_filter();
+        }
+        KeyCode::Char('u') if app.current_tab == crate::app::Tab::Connections => {
+            app.toggle_unix_sockets();
+        }
+        KeyCode::Char('a') if app.current_tab == crate::app::Tab::SystemInfo => {
+            app.toggle_pseudo_mounts();
         }
-        KeyCode::Char('2') => {
-            app.current_tab = crate::app::Tab::Processes;
+        KeyCode::Char('d') if app.current_tab == crate::app::Tab::SystemInfo => {
+            app.toggle_disk_pane();
         }
-        KeyCode::Char('3') => {
-            app.current_tab = crate::app::Tab::SystemInfo;
+        KeyCode::Char('e') | KeyCode::Enter if app.current_tab == crate::app::Tab::SystemInfo => {
+            app.open_explorer();
         }
-        KeyCode::Char('4') => {
-            app.current_tab = crate::app::Tab::Vpn;
+        KeyCode::Char('f') if app.current_tab == crate::app::Tab::Containers => {
+            app.toggle_containers_only();
+        }
+        KeyCode::Char('f') if app.current_tab == crate::app::Tab::Services => {
+            app.toggle_services_failed_only();
+        }
+        KeyCode::Char('S') if app.current_tab == crate::app::Tab::Services => {
+            app.request_service_action(ServiceAction::Start);
+        }
+        KeyCode::Char('X') if app.current_tab == crate::app::Tab::Services => {
+            app.request_service_action(ServiceAction::Stop);
+        }
+        KeyCode::Char('R') if app.current_tab == crate::app::Tab::Services => {
+            app.request_service_action(ServiceAction::Restart);
+        }
+        KeyCode::Enter if app.current_tab == crate::app::Tab::Services => {
+            app.toggle_service_journal();
+        }
+        KeyCode::Esc if app.current_tab == crate::app::Tab::Services => {
+            app.service_journal = None;
         }
         KeyCode::Char('s') => {
             app.cycle_process_sort();
@@ -65,23 +208,53 @@ pub fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
         KeyCode::Char('o') => {
             app.toggle_sort_order();
         }
-        KeyCode::Enter => {
-            if app.current_tab == crate::app::Tab::Processes {
+        KeyCode::Enter if app.current_tab == crate::app::Tab::Processes => {
+            if app.category_expanded || app.show_all_processes {
+                if let Some(pid) = app.selected_process() {
+                    app.open_process_detail(pid);
+                }
+            } else {
                 app.toggle_category_expanded();
             }
         }
-        KeyCode::Char('/') => {
-            if app.current_tab == crate::app::Tab::Processes {
-                app.enter_command_mode();
-            }
+        KeyCode::Char('c') if app.current_tab == crate::app::Tab::Processes => {
+            app.toggle_column_picker();
         }
-        KeyCode::Esc => {
-            if app.current_tab == crate::app::Tab::Processes {
-                if app.show_all_processes {
-                    app.exit_command_mode();
-                } else if app.category_expanded {
-                    app.collapse_category();
-                }
+        KeyCode::Char('+') | KeyCode::Char('=') if app.current_tab == crate::app::Tab::Overview => {
+            app.zoom_chart(-1);
+        }
+        KeyCode::Char('-') if app.current_tab == crate::app::Tab::Overview => {
+            app.zoom_chart(1);
+        }
+        KeyCode::Char('[') if app.current_tab == crate::app::Tab::Overview => {
+            app.pan_chart(-1.0);
+        }
+        KeyCode::Char(']') if app.current_tab == crate::app::Tab::Overview => {
+            app.pan_chart(1.0);
+        }
+        KeyCode::End if app.current_tab == crate::app::Tab::Overview => {
+            app.follow_chart();
+        }
+        KeyCode::Char('<') | KeyCode::Char('-')
+            if app.current_tab == crate::app::Tab::Processes =>
+        {
+            app.resize_sort_column(-2);
+        }
+        KeyCode::Char('>') | KeyCode::Char('+')
+            if app.current_tab == crate::app::Tab::Processes =>
+        {
+            app.resize_sort_column(2);
+        }
+        KeyCode::Char('/') if app.current_tab == crate::app::Tab::Processes => {
+            app.enter_command_mode();
+        }
+        KeyCode::Esc if app.current_tab == crate::app::Tab::Processes => {
+            if app.process_detail.is_some() {
+                app.close_process_detail();
+            } else if app.show_all_processes {
+                app.exit_command_mode();
+            } else if app.category_expanded {
+                app.collapse_category();
             }
         }
         KeyCode::Char('h') | KeyCode::Left => {
@@ -104,22 +277,46 @@ pub fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
         }
         KeyCode::Char('k') | KeyCode::Up => {
             if app.current_tab == crate::app::Tab::Processes {
-                if app.category_expanded || app.show_all_processes {
+                if app.process_detail.is_some() {
+                    app.detail_scroll_up();
+                } else if app.category_expanded || app.show_all_processes {
                     app.scroll_up();
                 } else {
                     app.move_category_up();
                 }
+            } else if matches!(
+                app.current_tab,
+                crate::app::Tab::SystemInfo
+                    | crate::app::Tab::Containers
+                    | crate::app::Tab::Services
+                    | crate::app::Tab::Sensors
+                    | crate::app::Tab::Connections
+                    | crate::app::Tab::Fleet
+            ) {
+                app.list_up();
             } else {
                 app.scroll_up();
             }
         }
         KeyCode::Char('j') | KeyCode::Down => {
             if app.current_tab == crate::app::Tab::Processes {
-                if app.category_expanded || app.show_all_processes {
+                if app.process_detail.is_some() {
+                    app.detail_scroll_down();
+                } else if app.category_expanded || app.show_all_processes {
                     app.scroll_down();
                 } else {
                     app.move_category_down();
                 }
+            } else if matches!(
+                app.current_tab,
+                crate::app::Tab::SystemInfo
+                    | crate::app::Tab::Containers
+                    | crate::app::Tab::Services
+                    | crate::app::Tab::Sensors
+                    | crate::app::Tab::Connections
+                    | crate::app::Tab::Fleet
+            ) {
+                app.list_down();
             } else {
                 app.scroll_down();
             }
@@ -128,6 +325,132 @@ pub fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
     }
 }
 
+pub fn handle_mouse_event(mouse: MouseEvent, app: &mut App) {
+    let position = Position::new(mouse.column, mouse.row);
+
+    match mouse.kind {
+        MouseEventKind::Moved | MouseEventKind::Drag(_) => {
+            app.mouse_position = Some((mouse.column, mouse.row));
+            return;
+        }
+        _ => {}
+    }
+
+    if app.show_help {
+        if let MouseEventKind::Down(_) = mouse.kind {
+            app.toggle_help();
+        }
+        return;
+    }
+
+    if app.command_mode
+        || app.column_picker
+        || app.service_confirm.is_some()
+        || app.explorer.as_ref().is_some_and(|e| e.confirm_delete)
+    {
+        return;
+    }
+
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(tab) = find_hit(&app.hit_areas.tabs, position) {
+                app.select_tab(tab);
+                return;
+            }
+
+            if let Some(list) = app.hit_areas.list_area.filter(|l| l.contains(position)) {
+                let row = app.hit_areas.list_offset + (position.y - list.y) as usize;
+                app.select_list_row(row);
+                return;
+            }
+
+            if app.current_tab != Tab::Processes {
+                return;
+            }
+
+            if let Some(sort) = find_hit(&app.hit_areas.sort_headers, position) {
+                app.sort_by(sort);
+                return;
+            }
+
+            // Clicking a row selects it, clicking the selected row opens it
+            if let Some(list) = app
+                .hit_areas
+                .process_list
+                .filter(|list| list.contains(position))
+            {
+                let row = app.hit_areas.process_offset + (position.y - list.y) as usize;
+                if let Some(&pid) = app.hit_areas.process_rows.get(row) {
+                    if row == app.process_scroll {
+                        app.open_process_detail(pid);
+                    } else {
+                        app.process_scroll = row;
+                    }
+                }
+                return;
+            }
+
+            // Category boxes are only recorded while the grid is on screen.
+            if let Some(index) = app
+                .hit_areas
+                .categories
+                .iter()
+                .position(|area| area.contains(position))
+            {
+                app.select_category(index);
+            }
+        }
+        MouseEventKind::ScrollUp if is_over_process_list(app, position) => {
+            app.scroll_up();
+        }
+        MouseEventKind::ScrollDown if is_over_process_list(app, position) => {
+            app.scroll_down();
+        }
+        MouseEventKind::ScrollUp if is_over_chart(app, position) => {
+            app.zoom_chart(-1);
+        }
+        MouseEventKind::ScrollDown if is_over_chart(app, position) => {
+            app.zoom_chart(1);
+        }
+        MouseEventKind::ScrollUp if is_over_list(app, position) => {
+            app.list_up();
+        }
+        MouseEventKind::ScrollDown if is_over_list(app, position) => {
+            app.list_down();
+        }
+        _ => {}
+    }
+}
+
+fn find_hit<T: Copy>(areas: &[(Rect, T)], position: Position) -> Option<T> {
+    areas
+        .iter()
+        .find(|(area, _)| area.contains(position))
+        .map(|(_, value)| *value)
+}
+
+fn is_over_process_list(app: &App, position: Position) -> bool {
+    app.current_tab == Tab::Processes
+        && app
+            .hit_areas
+            .process_list
+            .is_some_and(|area| area.contains(position))
+}
+
+fn is_over_chart(app: &App, position: Position) -> bool {
+    app.current_tab == Tab::Overview
+        && [app.hit_areas.cpu_chart, app.hit_areas.memory_chart]
+            .into_iter()
+            .flatten()
+            .any(|area| area.contains(position))
+}
+
+fn is_over_list(app: &App, position: Position) -> bool {
+    app.hit_areas
+        .list_area
+        .is_some_and(|area| area.contains(position))
+}
+
 pub fn poll_events(timeout: Duration) -> anyhow::Result<Option<Event>> {
     if event::poll(timeout)? {
         Ok(Some(event::read()?))
@@ -0,0 +1,404 @@
+use std::{
+    ffi::OsString,
+    fs, io,
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    thread::{self, JoinHandle},
+};
+
+/// How many directories deep the scan and deletes go. Each level is a
+/// stack frame, so this keeps a pathological tree from overflowing the
+/// thread's stack.
+const MAX_DEPTH: usize = 256;
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    /// As on disk, which needn't be UTF-8; only converted for display
+    pub name: OsString,
+    /// Disk usage in bytes (allocated blocks, like `du`)
+    pub size: u64,
+    /// Number of files and directories below this one, itself included
+    pub items: u64,
+    pub is_dir: bool,
+    /// Set when the directory (or part of it) couldn't be read
+    pub unreadable: bool,
+    pub children: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExplorerSort {
+    Size,
+    Name,
+}
+
+/// Counters the scanning thread updates as it goes.
+#[derive(Debug, Default)]
+pub struct ScanProgress {
+    pub items: AtomicU64,
+    pub bytes: AtomicU64,
+    pub current: Mutex<String>,
+    cancelled: AtomicBool,
+}
+
+/// A delete running on its own thread, and what to take off the tree
+/// once it's done.
+struct Deletion {
+    task: JoinHandle<io::Result<()>>,
+    /// Directories from the root down to the one holding the entry
+    parent: Vec<OsString>,
+    name: OsString,
+    size: u64,
+    items: u64,
+    target: PathBuf,
+}
+
+/// ncdu-style browser for one mount. The whole tree is scanned once on a
+/// background thread, after that drilling down is just walking the tree.
+pub struct Explorer {
+    pub root: PathBuf,
+    pub tree: Option<Entry>,
+    pub progress: Arc<ScanProgress>,
+    scan: Option<JoinHandle<Entry>>,
+    /// Names of the directories from the root down to the open one
+    path: Vec<OsString>,
+    deletion: Option<Deletion>,
+    pub selected: usize,
+    pub sort: ExplorerSort,
+    pub confirm_delete: bool,
+    pub message: Option<String>,
+}
+
+impl Explorer {
+    pub fn scan(root: PathBuf) -> Self {
+        let progress = Arc::new(ScanProgress::default());
+        let scan = {
+            let root = root.clone();
+            let progress = Arc::clone(&progress);
+            thread::spawn(move || {
+                let device = fs::symlink_metadata(&root).map(|m| m.dev()).unwrap_or(0);
+                let mut tree = scan_entry(&root, device, &progress, 0);
+                tree.name = root.clone().into_os_string();
+                tree
+            })
+        };
+
+        Self {
+            root,
+            tree: None,
+            progress,
+            scan: Some(scan),
+            path: Vec::new(),
+            deletion: None,
+            selected: 0,
+            sort: ExplorerSort::Size,
+            confirm_delete: false,
+            message: None,
+        }
+    }
+
+    pub fn rescan(&mut self) {
+        *self = Self::scan(self.root.clone());
+    }
+
+    /// Picks up the tree once the scanning thread is done, and the outcome
+    /// of a delete once that is.
+    pub fn poll(&mut self) {
+        if self
+            .deletion
+            .as_ref()
+            .is_some_and(|deletion| deletion.task.is_finished())
+            && let Some(deletion) = self.deletion.take()
+        {
+            self.finish_delete(deletion);
+        }
+
+        if !self.scan.as_ref().is_some_and(|scan| scan.is_finished()) {
+            return;
+        }
+
+        if let Some(scan) = self.scan.take() {
+            match scan.join() {
+                Ok(tree) => {
+                    self.tree = Some(tree);
+                    self.sort_current();
+                }
+                Err(_) => self.message = Some("Scan failed".to_string()),
+            }
+        }
+    }
+
+    pub fn is_scanning(&self) -> bool {
+        self.scan.is_some()
+    }
+
+    pub fn is_deleting(&self) -> bool {
+        self.deletion.is_some()
+    }
+
+    /// The directory currently open.
+    pub fn current(&self) -> Option<&Entry> {
+        let mut entry = self.tree.as_ref()?;
+        for name in &self.path {
+            entry = entry.children.iter().find(|child| &child.name == name)?;
+        }
+        Some(entry)
+    }
+
+    pub fn current_path(&self) -> PathBuf {
+        self.path
+            .iter()
+            .fold(self.root.clone(), |path, name| path.join(name))
+    }
+
+    pub fn selected_entry(&self) -> Option<&Entry> {
+        self.current()?.children.get(self.selected)
+    }
+
+    pub fn open_selected(&mut self) {
+        if let Some(entry) = self.selected_entry().filter(|entry| entry.is_dir) {
+            self.path.push(entry.name.clone());
+            self.selected = 0;
+            self.sort_current();
+        }
+    }
+
+    /// Goes to the parent directory, keeping the one we came from selected.
+    pub fn go_up(&mut self) {
+        if let Some(name) = self.path.pop() {
+            self.sort_current();
+            self.selected = self
+                .current()
+                .and_then(|entry| entry.children.iter().position(|child| child.name == name))
+                .unwrap_or(0);
+        }
+    }
+
+    pub fn toggle_sort(&mut self) {
+        self.sort = match self.sort {
+            ExplorerSort::Size => ExplorerSort::Name,
+            ExplorerSort::Name => ExplorerSort::Size,
+        };
+        self.sort_current();
+        self.selected = 0;
+    }
+
+    /// Sorts the open directory only. Directories are sorted as they're
+    /// entered, as the whole tree can hold millions of entries.
+    fn sort_current(&mut self) {
+        let sort = self.sort;
+        let Some(mut entry) = self.tree.as_mut() else {
+            return;
+        };
+        for name in &self.path {
+            let Some(child) = entry.children.iter_mut().find(|child| &child.name == name) else {
+                return;
+            };
+            entry = child;
+        }
+        match sort {
+            ExplorerSort::Size => entry
+                .children
+                .sort_by_key(|child| std::cmp::Reverse(child.size)),
+            ExplorerSort::Name => entry.children.sort_by(|a, b| a.name.cmp(&b.name)),
+        }
+    }
+
+    pub fn request_delete(&mut self) {
+        if self.selected_entry().is_some() && !self.is_deleting() {
+            self.confirm_delete = true;
+        }
+    }
+
+    pub fn cancel_delete(&mut self) {
+        self.confirm_delete = false;
+    }
+
+    /// Deletes the selected entry from disk on a background thread. Only
+    /// what lives on the scanned filesystem is removed: a mount point is
+    /// refused outright, and filesystems mounted further down are left in
+    /// place, as the scan didn't count them either.
+    pub fn delete_selected(&mut self) {
+        self.confirm_delete = false;
+        if self.is_deleting() {
+            return;
+        }
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        let (name, size, items) = (entry.name.clone(), entry.size, entry.items);
+        let target = self.current_path().join(&name);
+
+        let task = {
+            let root = self.root.clone();
+            let target = target.clone();
+            thread::spawn(move || {
+                let device = fs::symlink_metadata(&root)?.dev();
+                if fs::symlink_metadata(&target)?.dev() != device {
+                    return Err(io::Error::other("another filesystem is mounted there"));
+                }
+                remove_tree(&target, device, 0)
+            })
+        };
+        self.message = Some(format!("Deleting {}...", target.display()));
+        self.deletion = Some(Deletion {
+            task,
+            parent: self.path.clone(),
+            name,
+            size,
+            items,
+            target,
+        });
+    }
+
+    /// Takes a finished delete's size off every directory above it.
+    fn finish_delete(&mut self, deletion: Deletion) {
+        let target = deletion.target.display();
+        match deletion.task.join() {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                // The walk may have got part of the way, so the sizes shown
+                // can be stale now
+                self.message = Some(format!(
+                    "Failed to delete {}: {} (r to rescan)",
+                    target, err
+                ));
+                return;
+            }
+            Err(_) => {
+                self.message = Some(format!("Failed to delete {} (r to rescan)", target));
+                return;
+            }
+        }
+
+        if let Some(mut dir) = self.tree.as_mut() {
+            dir.size = dir.size.saturating_sub(deletion.size);
+            dir.items = dir.items.saturating_sub(deletion.items);
+            for parent in &deletion.parent {
+                let Some(index) = dir.children.iter().position(|child| &child.name == parent)
+                else {
+                    break;
+                };
+                dir = &mut dir.children[index];
+                dir.size = dir.size.saturating_sub(deletion.size);
+                dir.items = dir.items.saturating_sub(deletion.items);
+            }
+            dir.children.retain(|child| child.name != deletion.name);
+        }
+
+        let remaining = self.current().map_or(0, |entry| entry.children.len());
+        self.selected = self.selected.min(remaining.saturating_sub(1));
+        self.message = Some(format!("Deleted {}", target));
+    }
+}
+
+impl Drop for Explorer {
+    fn drop(&mut self) {
+        // Let an unfinished scan wind down on its own instead of blocking
+        self.progress.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+fn target_name(path: &Path) -> OsString {
+    path.file_name().map(OsString::from).unwrap_or_default()
+}
+
+/// Like `fs::remove_dir_all`, but stays on `device`: directories with
+/// another filesystem mounted on them are skipped, which leaves their
+/// parents in place too and is reported once the rest is gone.
+fn remove_tree(path: &Path, device: u64, depth: usize) -> io::Result<()> {
+    if !fs::symlink_metadata(path)?.is_dir() {
+        return fs::remove_file(path);
+    }
+    if depth >= MAX_DEPTH {
+        return Err(io::Error::other(format!(
+            "{} is nested too deep",
+            path.display()
+        )));
+    }
+
+    let mut mounted = None;
+    for child in fs::read_dir(path)? {
+        let child = child?.path();
+        if fs::symlink_metadata(&child)?.dev() != device {
+            mounted.get_or_insert(child);
+            continue;
+        }
+        remove_tree(&child, device, depth + 1)?;
+    }
+    if let Some(mount) = mounted {
+        return Err(io::Error::other(format!(
+            "left {} alone, another filesystem is mounted there",
+            mount.display()
+        )));
+    }
+    fs::remove_dir(path)
+}
+
+/// Walks `path` without following symlinks or crossing into other
+/// filesystems mounted below it. `depth` is how far below the root it is.
+fn scan_entry(path: &Path, device: u64, progress: &ScanProgress, depth: usize) -> Entry {
+    let name = target_name(path);
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return Entry {
+            name,
+            size: 0,
+            items: 1,
+            is_dir: false,
+            unreadable: true,
+            children: Vec::new(),
+        };
+    };
+
+    let size = metadata.blocks() * 512;
+    progress.items.fetch_add(1, Ordering::Relaxed);
+    progress.bytes.fetch_add(size, Ordering::Relaxed);
+
+    let mut entry = Entry {
+        name,
+        size,
+        items: 1,
+        is_dir: metadata.is_dir(),
+        unreadable: false,
+        children: Vec::new(),
+    };
+
+    if !metadata.is_dir() || metadata.dev() != device {
+        return entry;
+    }
+    // Counted, but not looked into
+    if depth >= MAX_DEPTH {
+        entry.unreadable = true;
+        return entry;
+    }
+
+    if let Ok(mut current) = progress.current.lock() {
+        *current = path.to_string_lossy().to_string();
+    }
+
+    let Ok(read_dir) = fs::read_dir(path) else {
+        entry.unreadable = true;
+        return entry;
+    };
+
+    for child in read_dir {
+        if progress.cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        let Ok(child) = child else {
+            entry.unreadable = true;
+            continue;
+        };
+
+        let child = scan_entry(&child.path(), device, progress, depth + 1);
+        entry.size += child.size;
+        entry.items += child.items;
+        entry.unreadable |= child.unreadable;
+        entry.children.push(child);
+    }
+
+    entry
+}
@@ -0,0 +1,755 @@
+use anyhow::{Context, Result};
+use chrono::{Local, SecondsFormat, TimeZone};
+use parquet::{
+    data_type::{BoolType, ByteArray, ByteArrayType, DataType, DoubleType, Int64Type},
+    file::{
+        properties::WriterProperties,
+        writer::{SerializedColumnWriter, SerializedFileWriter},
+    },
+    schema::parser::parse_message_type,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+};
+use sysinfo::System;
+
+use crate::{
+    app::{App, ProcessColumn},
+    config::Config,
+    history,
+    system::{ProcessCategory, ProcessInfo, ProcessNetwork, SystemInfo},
+    vpn::{self, VpnProvider, VpnStatus},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+    Parquet,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] =
+        [ExportFormat::Csv, ExportFormat::Json, ExportFormat::Parquet];
+
+    pub fn name(&self) -> &str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    /// Unix seconds, written as a date and time
+    Time,
+    Int,
+    Float,
+    Bool,
+    Text,
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Time(f64),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Text(String),
+    Null,
+}
+
+/// Rounded so f32 readings don't come out as 12.300000190734863.
+fn float(value: f64) -> Value {
+    Value::Float((value * 1000.0).round() / 1000.0)
+}
+
+fn text(value: impl Into<String>) -> Value {
+    Value::Text(value.into())
+}
+
+fn optional_text(value: &Option<String>) -> Value {
+    value.as_deref().map_or(Value::Null, text)
+}
+
+/// One file's worth of rows (one section of the JSON export).
+struct Table {
+    name: &'static str,
+    columns: Vec<(&'static str, Kind)>,
+    rows: Vec<Vec<Value>>,
+    /// Written as an object instead of a list in JSON
+    single_row: bool,
+}
+
+/// Exports what the monitor currently shows into `directory`, and returns
+/// the file (JSON) or directory of per-table files (CSV, Parquet) written.
+pub fn write(
+    format: ExportFormat,
+    directory: &Path,
+    app: &App,
+    system_info: &SystemInfo,
+    vpn_status: &VpnStatus,
+) -> Result<PathBuf> {
+    let now = history::now();
+    let hostname = System::host_name().unwrap_or_else(|| "unknown".to_string());
+    let tables = [
+        host_table(system_info, &hostname, now),
+        history_table(app, &hostname),
+        process_table(app, system_info, &hostname, now),
+        disk_table(app, system_info, &hostname, now),
+        vpn_table(vpn_status, &hostname, now),
+    ];
+
+    fs::create_dir_all(directory)
+        .with_context(|| format!("cannot create {}", directory.display()))?;
+    let name = format!(
+        "myworkspace-{}-{}",
+        hostname,
+        Local::now().format("%Y%m%d-%H%M%S")
+    );
+    match format {
+        ExportFormat::Json => {
+            let path = unused_path(directory, &name, Some("json"));
+            write_json(&tables, &path)?;
+            Ok(path)
+        }
+        ExportFormat::Csv | ExportFormat::Parquet => {
+            let path = unused_path(directory, &name, None);
+            fs::create_dir_all(&path)
+                .with_context(|| format!("cannot create {}", path.display()))?;
+            for table in &tables {
+                let file = path.join(format!("{}.{}", table.name, format.name()));
+                if format == ExportFormat::Csv {
+                    write_csv(table, &file)?;
+                } else {
+                    write_parquet(table, &file)?;
+                }
+            }
+            Ok(path)
+        }
+    }
+}
+
+/// `directory/name.extension`, with `-2`, `-3`... added to the name while
+/// that is taken, so a second export within the same second doesn't
+/// overwrite the first.
+pub fn unused_path(directory: &Path, name: &str, extension: Option<&str>) -> PathBuf {
+    // Not `with_extension`, which would cut a dotted hostname short
+    let path = |name: String| match extension {
+        Some(extension) => directory.join(format!("{}.{}", name, extension)),
+        None => directory.join(name),
+    };
+    let mut candidate = path(name.to_string());
+    let mut number = 2;
+    while candidate.exists() {
+        candidate = path(format!("{}-{}", name, number));
+        number += 1;
+    }
+    candidate
+}
+
+/// The `--export` mode: samples this machine for a moment and exports it
+/// without opening the TUI.
+pub fn run(format: ExportFormat, output: Option<PathBuf>, config: Config) -> Result<()> {
+    let directory = output.unwrap_or_else(|| config.export.directory.clone());
+    let mut system_info = SystemInfo::new(&config);
+    // CPU usage needs two readings some time apart
+    thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    system_info.refresh_processes(None);
+
+    let mut app = App::with_config(config);
+    app.add_cpu_data(system_info.get_cpu_usage());
+    app.add_memory_data(system_info.get_memory_percentage());
+    system_info
+        .process_view
+        .sort(app.process_sort, app.sort_ascending);
+
+    let path = write(
+        format,
+        &directory,
+        &app,
+        &system_info,
+        &vpn::get_vpn_status(),
+    )?;
+    println!("{}", path.display());
+    Ok(())
+}
+
+fn host_table(system_info: &SystemInfo, hostname: &str, now: f64) -> Table {
+    let (memory_used, memory_total) = system_info.get_memory_usage();
+    Table {
+        name: "host",
+        columns: vec![
+            ("exported_at", Kind::Time),
+            ("hostname", Kind::Text),
+            ("os", Kind::Text),
+            ("kernel", Kind::Text),
+            ("uptime_seconds", Kind::Int),
+            ("cpu_count", Kind::Int),
+            ("memory_total_bytes", Kind::Int),
+            ("memory_used_bytes", Kind::Int),
+        ],
+        rows: vec![vec![
+            Value::Time(now),
+            text(hostname),
+            optional_text(&System::long_os_version()),
+            optional_text(&System::kernel_version()),
+            Value::Int(System::uptime() as i64),
+            Value::Int(system_info.system.cpus().len() as i64),
+            Value::Int(memory_total as i64),
+            Value::Int(memory_used as i64),
+        ]],
+        single_row: true,
+    }
+}
+
+/// The CPU and memory history behind the Overview charts, one row per
+/// sample. Samples older than an hour are minute averages.
+fn history_table(app: &App, hostname: &str) -> Table {
+    let series = [
+        ("cpu_percent", &app.cpu_history),
+        ("memory_percent", &app.memory_history),
+    ];
+    Table {
+        name: "history",
+        columns: vec![
+            ("timestamp", Kind::Time),
+            ("host", Kind::Text),
+            ("metric", Kind::Text),
+            ("value", Kind::Float),
+        ],
+        rows: series
+            .into_iter()
+            .flat_map(|(metric, series)| {
+                series.all().into_iter().map(move |(time, value)| {
+                    vec![
+                        Value::Time(time),
+                        text(hostname),
+                        text(metric),
+                        float(value),
+                    ]
+                })
+            })
+            .collect(),
+        single_row: false,
+    }
+}
+
+/// The process table as the Processes tab shows it: the picked columns, in
+/// the current sort order, limited to the open category if one is expanded.
+fn process_table(app: &App, system_info: &SystemInfo, hostname: &str, now: f64) -> Table {
+    let view = &system_info.process_view;
+    let processes: Vec<&ProcessInfo> = if app.category_expanded && !app.show_all_processes {
+        view.category(ProcessCategory::ALL[app.selected_category])
+            .collect()
+    } else {
+        view.all().iter().collect()
+    };
+    let columns: Vec<ProcessColumn> = app
+        .config
+        .processes
+        .columns
+        .iter()
+        .map(|column| column.column)
+        .collect();
+
+    let mut header = vec![("timestamp", Kind::Time), ("host", Kind::Text)];
+    header.extend(columns.iter().map(|column| process_field(*column)));
+    header.push(("category", Kind::Text));
+
+    Table {
+        name: "processes",
+        columns: header,
+        rows: processes
+            .into_iter()
+            .map(|process| {
+                let mut row = vec![Value::Time(now), text(hostname)];
+                row.extend(columns.iter().map(|column| process_value(*column, process)));
+                row.push(text(process.category.name()));
+                row
+            })
+            .collect(),
+        single_row: false,
+    }
+}
+
+/// Column name and type of a process column. Sizes are in bytes and rates
+/// in bytes per second, unlike the rounded figures on screen.
+fn process_field(column: ProcessColumn) -> (&'static str, Kind) {
+    match column {
+        ProcessColumn::Pid => ("pid", Kind::Int),
+        ProcessColumn::Name => ("name", Kind::Text),
+        ProcessColumn::Cpu => ("cpu_percent", Kind::Float),
+        ProcessColumn::Memory => ("memory_bytes", Kind::Int),
+        ProcessColumn::User => ("user", Kind::Text),
+        ProcessColumn::State => ("state", Kind::Text),
+        ProcessColumn::Threads => ("threads", Kind::Int),
+        ProcessColumn::Command => ("command", Kind::Text),
+        ProcessColumn::StartTime => ("started", Kind::Time),
+        ProcessColumn::RunTime => ("run_time_seconds", Kind::Int),
+        ProcessColumn::VirtualMemory => ("virtual_memory_bytes", Kind::Int),
+        ProcessColumn::DiskRead => ("disk_read_bytes", Kind::Int),
+        ProcessColumn::DiskWrite => ("disk_written_bytes", Kind::Int),
+        ProcessColumn::DiskReadRate => ("disk_read_rate", Kind::Int),
+        ProcessColumn::DiskWriteRate => ("disk_write_rate", Kind::Int),
+        ProcessColumn::Sockets => ("sockets", Kind::Int),
+        ProcessColumn::NetReceived => ("net_received_bytes", Kind::Int),
+        ProcessColumn::NetSent => ("net_sent_bytes", Kind::Int),
+        ProcessColumn::NetRate => ("net_rate", Kind::Int),
+        ProcessColumn::Nice => ("nice", Kind::Int),
+        ProcessColumn::Priority => ("priority", Kind::Int),
+    }
+}
+
+fn process_value(column: ProcessColumn, process: &ProcessInfo) -> Value {
+    let network = |value: fn(&ProcessNetwork) -> u64| {
+        process
+            .network
+            .as_ref()
+            .map_or(Value::Null, |network| Value::Int(value(network) as i64))
+    };
+    match column {
+        ProcessColumn::Pid => Value::Int(process.pid as i64),
+        ProcessColumn::Name => text(&process.name),
+        ProcessColumn::Cpu => float(process.cpu_usage as f64),
+        ProcessColumn::Memory => Value::Int(process.memory as i64),
+        ProcessColumn::User => text(&process.user),
+        ProcessColumn::State => text(&process.state),
+        ProcessColumn::Threads => Value::Int(process.threads as i64),
+        ProcessColumn::Command => text(&process.command),
+        ProcessColumn::StartTime => Value::Time(process.start_time as f64),
+        ProcessColumn::RunTime => Value::Int(process.run_time as i64),
+        ProcessColumn::VirtualMemory => Value::Int(process.virtual_memory as i64),
+        ProcessColumn::DiskRead => Value::Int(process.disk_read as i64),
+        ProcessColumn::DiskWrite => Value::Int(process.disk_written as i64),
+        ProcessColumn::DiskReadRate => Value::Int(process.disk_read_rate as i64),
+        ProcessColumn::DiskWriteRate => Value::Int(process.disk_write_rate as i64),
+        ProcessColumn::Sockets => network(|network| network.usage.sockets as u64),
+        ProcessColumn::NetReceived => network(|network| network.usage.bytes_received),
+        ProcessColumn::NetSent => network(|network| network.usage.bytes_sent),
+        ProcessColumn::NetRate => network(|network| network.total_rate()),
+        ProcessColumn::Nice => Value::Int(process.nice as i64),
+        ProcessColumn::Priority => Value::Int(process.priority as i64),
+    }
+}
+
+/// The mounts the System Info tab lists, with the same pseudo filesystem
+/// filter.
+fn disk_table(app: &App, system_info: &SystemInfo, hostname: &str, now: f64) -> Table {
+    let settings = &app.config.disks;
+    Table {
+        name: "disks",
+        columns: vec![
+            ("timestamp", Kind::Time),
+            ("host", Kind::Text),
+            ("mount_point", Kind::Text),
+            ("source", Kind::Text),
+            ("file_system", Kind::Text),
+            ("total_bytes", Kind::Int),
+            ("used_bytes", Kind::Int),
+            ("available_bytes", Kind::Int),
+            ("used_percent", Kind::Float),
+            ("inodes_total", Kind::Int),
+            ("inodes_free", Kind::Int),
+            ("read_only", Kind::Bool),
+        ],
+        rows: system_info
+            .get_disk_info()
+            .iter()
+            .filter(|disk| settings.show_pseudo || !disk.is_pseudo(&settings.hidden_filesystems))
+            .map(|disk| {
+                let used = disk.total_space.saturating_sub(disk.available_space);
+                let percent = if disk.total_space > 0 {
+                    used as f64 / disk.total_space as f64 * 100.0
+                } else {
+                    0.0
+                };
+                let optional_int = |value: Option<u64>| {
+                    value.map_or(Value::Null, |value| Value::Int(value as i64))
+                };
+                vec![
+                    Value::Time(now),
+                    text(hostname),
+                    text(&disk.mount_point),
+                    text(&disk.source),
+                    text(&disk.file_system),
+                    Value::Int(disk.total_space as i64),
+                    Value::Int(used as i64),
+                    Value::Int(disk.available_space as i64),
+                    float(percent),
+                    optional_int(disk.inodes_total),
+                    optional_int(disk.inodes_free),
+                    Value::Bool(disk.is_read_only),
+                ]
+            })
+            .collect(),
+        single_row: false,
+    }
+}
+
+fn vpn_table(vpn_status: &VpnStatus, hostname: &str, now: f64) -> Table {
+    Table {
+        name: "vpn",
+        columns: vec![
+            ("timestamp", Kind::Time),
+            ("host", Kind::Text),
+            ("connected", Kind::Bool),
+            ("provider", Kind::Text),
+            ("server", Kind::Text),
+            ("country", Kind::Text),
+            ("city", Kind::Text),
+            ("ip", Kind::Text),
+            ("protocol", Kind::Text),
+            ("interface", Kind::Text),
+        ],
+        rows: vec![vec![
+            Value::Time(now),
+            text(hostname),
+            Value::Bool(vpn_status.connected),
+            if vpn_status.provider == VpnProvider::Unknown {
+                Value::Null
+            } else {
+                text(vpn_status.provider.name())
+            },
+            optional_text(&vpn_status.server),
+            optional_text(&vpn_status.country),
+            optional_text(&vpn_status.city),
+            optional_text(&vpn_status.ip),
+            optional_text(&vpn_status.protocol),
+            optional_text(&vpn_status.interface),
+        ]],
+        single_row: true,
+    }
+}
+
+/// Local time with its offset, so the files read the same anywhere.
+fn format_time(seconds: f64) -> String {
+    Local
+        .timestamp_millis_opt((seconds * 1000.0) as i64)
+        .single()
+        .map_or_else(String::new, |time| {
+            time.to_rfc3339_opts(SecondsFormat::Millis, false)
+        })
+}
+
+fn write_csv(table: &Table, path: &Path) -> Result<()> {
+    let mut writer =
+        csv::Writer::from_path(path).with_context(|| format!("cannot write {}", path.display()))?;
+    writer.write_record(table.columns.iter().map(|(name, _)| name))?;
+    for row in &table.rows {
+        writer.write_record(row.iter().map(|value| match value {
+            Value::Time(seconds) => format_time(*seconds),
+            Value::Int(value) => value.to_string(),
+            Value::Float(value) => value.to_string(),
+            Value::Bool(value) => value.to_string(),
+            Value::Text(value) => value.clone(),
+            Value::Null => String::new(),
+        }))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_json(tables: &[Table], path: &Path) -> Result<()> {
+    let mut document = serde_json::Map::new();
+    for table in tables {
+        let mut rows = table.rows.iter().map(|row| {
+            let object: serde_json::Map<String, serde_json::Value> = table
+                .columns
+                .iter()
+                .zip(row)
+                .map(|((name, _), value)| {
+                    let value = match value {
+                        Value::Time(seconds) => format_time(*seconds).into(),
+                        Value::Int(value) => (*value).into(),
+                        Value::Float(value) => (*value).into(),
+                        Value::Bool(value) => (*value).into(),
+                        Value::Text(value) => value.clone().into(),
+                        Value::Null => serde_json::Value::Null,
+                    };
+                    (name.to_string(), value)
+                })
+                .collect();
+            serde_json::Value::Object(object)
+        });
+        let section = if table.single_row {
+            rows.next().unwrap_or_default()
+        } else {
+            serde_json::Value::Array(rows.collect())
+        };
+        document.insert(table.name.to_string(), section);
+    }
+
+    let json = serde_json::to_string_pretty(&serde_json::Value::Object(document))?;
+    fs::write(path, json).with_context(|| format!("cannot write {}", path.display()))
+}
+
+fn write_parquet(table: &Table, path: &Path) -> Result<()> {
+    let fields: Vec<String> = table
+        .columns
+        .iter()
+        .map(|(name, kind)| match kind {
+            Kind::Time => format!("OPTIONAL INT64 {} (TIMESTAMP(MILLIS,true));", name),
+            Kind::Int => format!("OPTIONAL INT64 {};", name),
+            Kind::Float => format!("OPTIONAL DOUBLE {};", name),
+            Kind::Bool => format!("OPTIONAL BOOLEAN {};", name),
+            Kind::Text => format!("OPTIONAL BYTE_ARRAY {} (STRING);", name),
+        })
+        .collect();
+    let schema = parse_message_type(&format!(
+        "message {} {{ {} }}",
+        table.name,
+        fields.join(" ")
+    ))?;
+
+    let file =
+        fs::File::create(path).with_context(|| format!("cannot write {}", path.display()))?;
+    let mut writer = SerializedFileWriter::new(
+        file,
+        Arc::new(schema),
+        Arc::new(WriterProperties::builder().build()),
+    )?;
+    let mut row_group = writer.next_row_group()?;
+    let mut index = 0;
+    while let Some(mut column) = row_group.next_column()? {
+        let rows = &table.rows;
+        match table.columns[index].1 {
+            Kind::Time => {
+                write_column::<Int64Type>(&mut column, rows, index, |value| match value {
+                    Value::Time(seconds) => Some((seconds * 1000.0) as i64),
+                    _ => None,
+                })?
+            }
+            Kind::Int => {
+                write_column::<Int64Type>(&mut column, rows, index, |value| match value {
+                    Value::Int(value) => Some(*value),
+                    _ => None,
+                })?
+            }
+            Kind::Float => {
+                write_column::<DoubleType>(&mut column, rows, index, |value| match value {
+                    Value::Float(value) => Some(*value),
+                    _ => None,
+                })?
+            }
+            Kind::Bool => {
+                write_column::<BoolType>(&mut column, rows, index, |value| match value {
+                    Value::Bool(value) => Some(*value),
+                    _ => None,
+                })?
+            }
+            Kind::Text => {
+                write_column::<ByteArrayType>(&mut column, rows, index, |value| match value {
+                    Value::Text(value) => Some(ByteArray::from(value.as_str())),
+                    _ => None,
+                })?
+            }
+        }
+        column.close()?;
+        index += 1;
+    }
+    row_group.close()?;
+    writer.close()?;
+    Ok(())
+}
+
+/// Writes column `index` of every row, nulls included, as one batch.
+fn write_column<T: DataType>(
+    column: &mut SerializedColumnWriter<'_>,
+    rows: &[Vec<Value>],
+    index: usize,
+    convert: impl Fn(&Value) -> Option<T::T>,
+) -> Result<()> {
+    let mut values = Vec::with_capacity(rows.len());
+    let mut levels = Vec::with_capacity(rows.len());
+    for row in rows {
+        match convert(&row[index]) {
+            Some(value) => {
+                values.push(value);
+                levels.push(1);
+            }
+            None => levels.push(0),
+        }
+    }
+    column
+        .typed::<T>()
+        .write_batch(&values, Some(&levels), None)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::Field,
+    };
+
+    fn temp_dir(test: &str) -> PathBuf {
+        crate::sysfs::testing::fake_root("export", test)
+    }
+
+    fn sample_table() -> Table {
+        Table {
+            name: "samples",
+            columns: vec![
+                ("time", Kind::Time),
+                ("count", Kind::Int),
+                ("ratio", Kind::Float),
+                ("ok", Kind::Bool),
+                ("name", Kind::Text),
+            ],
+            rows: vec![
+                vec![
+                    Value::Time(1_700_000_000.5),
+                    Value::Int(-3),
+                    Value::Float(1.25),
+                    Value::Bool(true),
+                    text("a, \"b\""),
+                ],
+                vec![
+                    Value::Null,
+                    Value::Null,
+                    Value::Null,
+                    Value::Null,
+                    Value::Null,
+                ],
+                vec![
+                    Value::Time(0.0),
+                    Value::Int(i64::MAX),
+                    Value::Null,
+                    Value::Bool(false),
+                    text("ü"),
+                ],
+            ],
+            single_row: false,
+        }
+    }
+
+    #[test]
+    fn round_trips_every_format() {
+        let dir = temp_dir("round-trip");
+        let table = sample_table();
+
+        let csv_path = dir.join("samples.csv");
+        write_csv(&table, &csv_path).unwrap();
+        let mut reader = csv::Reader::from_path(&csv_path).unwrap();
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec!["time", "count", "ratio", "ok", "name"]
+        );
+        let records: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(
+            records[0],
+            vec![
+                format_time(1_700_000_000.5).as_str(),
+                "-3",
+                "1.25",
+                "true",
+                "a, \"b\""
+            ]
+        );
+        assert_eq!(records[1], vec!["", "", "", "", ""]);
+        assert_eq!(
+            records[2],
+            vec![
+                format_time(0.0).as_str(),
+                &i64::MAX.to_string(),
+                "",
+                "false",
+                "ü"
+            ]
+        );
+
+        let json_path = dir.join("samples.json");
+        write_json(&[table], &json_path).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+        let rows = json["samples"].as_array().unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0]["time"], format_time(1_700_000_000.5));
+        assert_eq!(rows[0]["count"], -3);
+        assert_eq!(rows[0]["ratio"], 1.25);
+        assert_eq!(rows[0]["ok"], true);
+        assert_eq!(rows[0]["name"], "a, \"b\"");
+        assert!(
+            rows[1]
+                .as_object()
+                .unwrap()
+                .values()
+                .all(|value| value.is_null())
+        );
+        assert_eq!(rows[2]["count"], i64::MAX);
+        assert!(rows[2]["ratio"].is_null());
+
+        let parquet_path = dir.join("samples.parquet");
+        write_parquet(&sample_table(), &parquet_path).unwrap();
+        let reader = SerializedFileReader::new(fs::File::open(&parquet_path).unwrap()).unwrap();
+        let rows: Vec<Vec<Field>> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                row.unwrap()
+                    .get_column_iter()
+                    .map(|(_, field)| field.clone())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                vec![
+                    Field::TimestampMillis(1_700_000_000_500),
+                    Field::Long(-3),
+                    Field::Double(1.25),
+                    Field::Bool(true),
+                    Field::Str("a, \"b\"".to_string()),
+                ],
+                vec![Field::Null; 5],
+                vec![
+                    Field::TimestampMillis(0),
+                    Field::Long(i64::MAX),
+                    Field::Null,
+                    Field::Bool(false),
+                    Field::Str("ü".to_string()),
+                ],
+            ]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn never_reuses_a_path() {
+        let dir = temp_dir("unused");
+        let first = unused_path(&dir, "host.example.com-1", Some("json"));
+        assert_eq!(first, dir.join("host.example.com-1.json"));
+        fs::write(&first, "").unwrap();
+        let second = unused_path(&dir, "host.example.com-1", Some("json"));
+        assert_eq!(second, dir.join("host.example.com-1-2.json"));
+        fs::write(&second, "").unwrap();
+        assert_eq!(
+            unused_path(&dir, "host.example.com-1", Some("json")),
+            dir.join("host.example.com-1-3.json")
+        );
+
+        fs::create_dir(dir.join("export")).unwrap();
+        assert_eq!(unused_path(&dir, "export", None), dir.join("export-2"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,176 @@
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+
+use crate::{
+    agent::{AgentConnection, HostSnapshot},
+    config::HostConfig,
+    history::TimeSeries,
+    ssh::{self, SshCollector},
+};
+
+/// An agent that stays quiet this long is treated as gone.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+const RETRY_DELAY: Duration = Duration::from_secs(3);
+/// Snapshots older than this are flagged; both sources send one a second.
+const STALE_AFTER: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostStatus {
+    Connecting,
+    Connected,
+    /// Why the last attempt failed; the connection is retried
+    Failed(String),
+}
+
+/// One agent as last heard from.
+#[derive(Debug, Clone)]
+pub struct RemoteHost {
+    pub name: String,
+    pub address: String,
+    pub status: HostStatus,
+    /// Kept after the connection drops, so the last known state stays visible
+    pub snapshot: Option<HostSnapshot>,
+    pub updated: Option<Instant>,
+    /// Round trip to the host, as last measured
+    pub latency: Option<Duration>,
+    pub cpu_history: TimeSeries,
+    pub memory_history: TimeSeries,
+}
+
+/// The hosts the Fleet tab watches, through an agent or over ssh. Each one
+/// is followed by its own background thread, which reconnects whenever the
+/// connection drops.
+#[derive(Default)]
+pub struct Fleet {
+    hosts: Vec<Arc<Mutex<RemoteHost>>>,
+}
+
+impl Fleet {
+    pub fn connect(hosts: &[HostConfig], token: Option<&str>) -> Self {
+        let hosts = hosts
+            .iter()
+            .map(|config| {
+                let host = Arc::new(Mutex::new(RemoteHost {
+                    name: config
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| config.address.clone()),
+                    address: config.address.clone(),
+                    status: HostStatus::Connecting,
+                    snapshot: None,
+                    updated: None,
+                    latency: None,
+                    cpu_history: TimeSeries::new(),
+                    memory_history: TimeSeries::new(),
+                }));
+                let token = config
+                    .token
+                    .clone()
+                    .or_else(|| token.map(str::to_string))
+                    .unwrap_or_default();
+                let watched = Arc::clone(&host);
+                let address = config.address.clone();
+                thread::spawn(move || watch(&watched, &address, &token));
+                host
+            })
+            .collect();
+
+        Self { hosts }
+    }
+
+    pub fn len(&self) -> usize {
+        self.hosts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hosts.is_empty()
+    }
+
+    /// Each host locked in turn; hold on to them only while drawing.
+    pub fn hosts(&self) -> impl Iterator<Item = MutexGuard<'_, RemoteHost>> {
+        self.hosts.iter().map(|host| lock(host))
+    }
+
+    pub fn host(&self, index: usize) -> Option<MutexGuard<'_, RemoteHost>> {
+        self.hosts.get(index).map(|host| lock(host))
+    }
+}
+
+impl RemoteHost {
+    pub fn over_ssh(&self) -> bool {
+        ssh::destination(&self.address).is_some()
+    }
+
+    /// Whether the newest snapshot is older than it should be.
+    pub fn is_stale(&self) -> bool {
+        self.updated
+            .is_some_and(|updated| updated.elapsed() > STALE_AFTER)
+    }
+}
+
+/// Where a host's snapshots come from.
+enum Source {
+    Agent(AgentConnection),
+    Ssh(Box<SshCollector>),
+}
+
+impl Source {
+    fn open(address: &str, token: &str) -> Result<Self> {
+        Ok(match ssh::destination(address) {
+            Some(destination) => {
+                Source::Ssh(Box::new(SshCollector::open(destination, READ_TIMEOUT)?))
+            }
+            None => Source::Agent(AgentConnection::open(address, token, READ_TIMEOUT)?),
+        })
+    }
+
+    fn next_snapshot(&mut self) -> Result<HostSnapshot> {
+        match self {
+            Source::Agent(connection) => connection.next_snapshot(),
+            Source::Ssh(collector) => collector.next_snapshot(),
+        }
+    }
+
+    fn latency(&self) -> Duration {
+        match self {
+            Source::Agent(connection) => connection.latency,
+            Source::Ssh(collector) => collector.latency,
+        }
+    }
+}
+
+fn lock(host: &Mutex<RemoteHost>) -> MutexGuard<'_, RemoteHost> {
+    host.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn watch(host: &Mutex<RemoteHost>, address: &str, token: &str) {
+    loop {
+        lock(host).status = HostStatus::Connecting;
+        let error = match Source::open(address, token) {
+            Ok(mut source) => {
+                lock(host).status = HostStatus::Connected;
+                loop {
+                    match source.next_snapshot() {
+                        Ok(snapshot) => {
+                            let mut host = lock(host);
+                            host.latency = Some(source.latency());
+                            host.cpu_history.push(snapshot.cpu_usage as f64);
+                            host.memory_history.push(snapshot.memory_percent());
+                            host.snapshot = Some(snapshot);
+                            host.updated = Some(Instant::now());
+                        }
+                        Err(err) => break err,
+                    }
+                }
+            }
+            Err(err) => err,
+        };
+        lock(host).status = HostStatus::Failed(error.to_string());
+        thread::sleep(RETRY_DELAY);
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Every sample is kept this long, older history only survives as
+/// per-minute buckets.
+const RAW_RETENTION: Duration = Duration::from_secs(60 * 60);
+const BUCKET_LENGTH: Duration = Duration::from_secs(60);
+pub const RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Min, average and max of the samples in a time span.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+}
+
+/// One minute of samples folded together.
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    start: f64,
+    min: f64,
+    max: f64,
+    sum: f64,
+    count: u32,
+}
+
+impl Bucket {
+    fn new(start: f64, value: f64) -> Self {
+        Self {
+            start,
+            min: value,
+            max: value,
+            sum: value,
+            count: 1,
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn avg(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+/// Timestamped samples of one metric, at full resolution for the last hour
+/// and minute by minute for the last day. Times are Unix seconds.
+#[derive(Debug, Clone, Default)]
+pub struct TimeSeries {
+    raw: VecDeque<(f64, f64)>,
+    buckets: VecDeque<Bucket>,
+}
+
+impl TimeSeries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.push_at(now(), value);
+    }
+
+    fn push_at(&mut self, time: f64, value: f64) {
+        self.raw.push_back((time, value));
+        while self
+            .raw
+            .front()
+            .is_some_and(|(oldest, _)| time - oldest > RAW_RETENTION.as_secs_f64())
+        {
+            self.raw.pop_front();
+        }
+
+        let start = (time / BUCKET_LENGTH.as_secs_f64()).floor() * BUCKET_LENGTH.as_secs_f64();
+        match self.buckets.back_mut() {
+            Some(bucket) if bucket.start == start => bucket.add(value),
+            _ => self.buckets.push_back(Bucket::new(start, value)),
+        }
+        while self
+            .buckets
+            .front()
+            .is_some_and(|bucket| time - bucket.start > RETENTION.as_secs_f64())
+        {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Whether the full-resolution samples still reach back to `from`.
+    fn raw_covers(&self, from: f64) -> bool {
+        now() - from <= RAW_RETENTION.as_secs_f64()
+    }
+
+    /// The samples between `from` and `to` at the finest resolution still
+    /// kept for that span.
+    pub fn range(&self, from: f64, to: f64) -> Vec<(f64, f64)> {
+        let within = |time: f64| time >= from && time <= to;
+        if self.raw_covers(from) {
+            self.raw
+                .iter()
+                .copied()
+                .filter(|(time, _)| within(*time))
+                .collect()
+        } else {
+            self.buckets
+                .iter()
+                .filter(|bucket| within(bucket.start))
+                .map(|bucket| (bucket.start, bucket.avg()))
+                .collect()
+        }
+    }
+
+    /// Everything still kept: minute averages from before the full-resolution
+    /// samples begin, then the samples themselves.
+    pub fn all(&self) -> Vec<(f64, f64)> {
+        let first_raw = self.raw.front().map_or(f64::INFINITY, |(time, _)| *time);
+        self.buckets
+            .iter()
+            .filter(|bucket| bucket.start + BUCKET_LENGTH.as_secs_f64() <= first_raw)
+            .map(|bucket| (bucket.start, bucket.avg()))
+            .chain(self.raw.iter().copied())
+            .collect()
+    }
+
+    pub fn summary(&self, from: f64, to: f64) -> Option<Summary> {
+        let within = |time: f64| time >= from && time <= to;
+        let (min, max, sum, count) = if self.raw_covers(from) {
+            self.raw.iter().filter(|(time, _)| within(*time)).fold(
+                (f64::INFINITY, f64::NEG_INFINITY, 0.0, 0),
+                |(min, max, sum, count), (_, value)| {
+                    (min.min(*value), max.max(*value), sum + value, count + 1)
+                },
+            )
+        } else {
+            self.buckets
+                .iter()
+                .filter(|bucket| within(bucket.start))
+                .fold(
+                    (f64::INFINITY, f64::NEG_INFINITY, 0.0, 0),
+                    |(min, max, sum, count), bucket| {
+                        (
+                            min.min(bucket.min),
+                            max.max(bucket.max),
+                            sum + bucket.sum,
+                            count + bucket.count,
+                        )
+                    },
+                )
+        };
+
+        (count > 0).then(|| Summary {
+            min,
+            avg: sum / count as f64,
+            max,
+        })
+    }
+}
+
+/// The current time as Unix seconds.
+pub fn now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |elapsed| elapsed.as_secs_f64())
+}
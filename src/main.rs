@@ -1,12 +1,34 @@
+mod agent;
 mod app;
+mod bench;
+mod cli;
+mod config;
+mod containers;
+mod disk_trend;
+mod diskio;
 mod events;
+mod explorer;
+mod export;
+mod fleet;
+mod history;
+mod memory;
+mod power;
+mod process_view;
+mod scheduler;
+mod screenshot;
+mod sensors;
+mod services;
+mod sockets;
+mod ssh;
+mod sysfs;
 mod system;
+mod theme;
 mod ui;
 mod vpn;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::{
-    event::Event,
+    event::{DisableMouseCapture, EnableMouseCapture, Event},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
@@ -17,39 +39,69 @@ use std::{
 };
 
 use app::App;
+use config::Config;
+use services::{ServiceOutcome, ServiceRequest};
 use system::SystemInfo;
 use vpn::VpnStatus;
 
 fn main() -> Result<()> {
+    let args = match cli::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    // A broken config file is reported and left alone: the defaults are
+    // used, and nothing is saved over it until it parses again
+    let (config, config_error) = match Config::load() {
+        Ok(config) => (config, None),
+        Err(err) => {
+            eprintln!("Warning: {:#}\nUsing the default settings.", err);
+            (Config::default(), Some(format!("{:#}", err)))
+        }
+    };
+    let token = args.token.or_else(|| config.fleet.token.clone());
+
+    let connect = match args.mode {
+        cli::Mode::Monitor { connect } => connect,
+        cli::Mode::Agent { listen } => {
+            let token = token
+                .filter(|token| !token.is_empty())
+                .context("the agent needs a token: --token, $MYWORKSPACE_TOKEN or [fleet] token")?;
+            return agent::run(&listen, token, &config);
+        }
+        cli::Mode::Export { format, output } => return export::run(format, output, config),
+        cli::Mode::Bench { processes } => return bench::run(processes),
+        cli::Mode::Help => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+    };
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
 
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new();
-    let mut system_info = SystemInfo::new();
+    theme::apply(&config.appearance);
+    let mut system_info = SystemInfo::new(&config);
+    let hosts: Vec<_> = config.fleet.hosts.iter().cloned().chain(connect).collect();
+    let mut app = App::with_config(config);
+    app.config_error = config_error;
+    app.fleet = fleet::Fleet::connect(&hosts, token.as_deref());
     let mut vpn_status = VpnStatus::new();
 
-    let tick_rate = Duration::from_millis(500); //refresh rate. Dont come here and say its an magix number
-    let mut last_tick = Instant::now();
-    let vpn_check_rate = Duration::from_secs(5);
-    let mut last_vpn_check = Instant::now();
-
-    let result = run_app(
-        &mut terminal,
-        &mut app,
-        &mut system_info,
-        &mut vpn_status,
-        tick_rate,
-        &mut last_tick,
-        vpn_check_rate,
-        &mut last_vpn_check,
-    );
+    let result = run_app(&mut terminal, &mut app, &mut system_info, &mut vpn_status);
 
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
     terminal.show_cursor()?;
 
     if let Err(err) = result {
@@ -64,34 +116,87 @@ fn run_app(
     app: &mut App,
     system_info: &mut SystemInfo,
     vpn_status: &mut VpnStatus,
-    tick_rate: Duration,
-    last_tick: &mut Instant,
-    vpn_check_rate: Duration,
-    last_vpn_check: &mut Instant,
 ) -> Result<()> {
+    let tick_rate = Duration::from_millis(500); //refresh rate. Dont come here and say its an magix number
+    let mut last_tick = Instant::now();
+    let vpn_check_rate = Duration::from_secs(5);
+    let mut last_vpn_check = Instant::now();
+
     // Initial VPN check
     *vpn_status = vpn::get_vpn_status();
 
     loop {
-        terminal.draw(|f| ui::render(f, app, system_info, vpn_status))?;
+        system_info
+            .process_view
+            .sort(app.process_sort, app.sort_ascending);
+        let frame = terminal.draw(|f| ui::render(f, app, system_info, vpn_status))?;
+        if app.screenshot_request {
+            app.screenshot_request = false;
+            let view = app.current_tab.title();
+            let view = view.split(" [").next().unwrap_or(view);
+            let result = screenshot::write(
+                frame.buffer,
+                app.config.export.screenshot_format,
+                &app.config.export.directory,
+                &view.to_lowercase().replace(' ', "-"),
+                app.config.appearance.theme == theme::ThemeName::Light,
+            );
+            app.export_status = Some((Instant::now(), result.map_err(|err| format!("{:#}", err))));
+        }
 
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_secs(0));
 
-        if let Some(event) = events::poll_events(timeout)? {
-            if let Event::Key(key) = event {
-                events::handle_key_event(key, app);
+        match events::poll_events(timeout)? {
+            Some(Event::Key(key)) => events::handle_key_event(key, app),
+            Some(Event::Mouse(mouse)) => events::handle_mouse_event(mouse, app),
+            _ => {}
+        }
+
+        if let Some(explorer) = app.explorer.as_mut() {
+            explorer.poll();
+        }
+
+        // The detail pane was opened or closed, fill it in straight away
+        if app.process_detail != system_info.detail_pid() {
+            system_info.refresh_process_detail(app.process_detail);
+        }
+
+        if let Some(request) = app.service_request.take() {
+            system_info.services.start_request(request);
+        }
+        while let Some(outcome) = system_info.services.poll_outcome() {
+            handle_service_outcome(outcome, app, system_info);
+        }
+
+        if app.export_request {
+            app.export_request = false;
+            // The process table is only kept fresh while its tab is open
+            if app.current_tab != app::Tab::Processes {
+                system_info.refresh_processes(None);
+                system_info
+                    .process_view
+                    .sort(app.process_sort, app.sort_ascending);
             }
+            let result = export::write(
+                app.config.export.format,
+                &app.config.export.directory,
+                app,
+                system_info,
+                vpn_status,
+            );
+            app.export_status = Some((Instant::now(), result.map_err(|err| format!("{:#}", err))));
         }
 
         if last_tick.elapsed() >= tick_rate {
             match app.current_tab {
                 app::Tab::Overview => {
-                    system_info.refresh_light();
+                    system_info.refresh_overview(&app.config.overview);
                 }
                 app::Tab::Processes => {
-                    system_info.refresh_full();
+                    system_info.track_process_network = app.wants_process_network();
+                    system_info.refresh_processes(app.process_detail);
                 }
                 app::Tab::SystemInfo => {
                     system_info.refresh_system_info();
@@ -99,16 +204,45 @@ fn run_app(
                 app::Tab::Vpn => {
                     system_info.refresh_minimal();
                 }
+                app::Tab::Containers => {
+                    system_info.refresh_containers();
+                }
+                app::Tab::Services => {
+                    system_info.refresh_services();
+                }
+                app::Tab::Sensors => {
+                    system_info.refresh_sensors();
+                }
+                app::Tab::Memory => {
+                    system_info.refresh_minimal();
+                }
+                app::Tab::Connections => {
+                    system_info.refresh_connections();
+                }
+                app::Tab::Fleet => {
+                    system_info.refresh_snapshot();
+                }
             }
 
+            system_info.sample_disk_space();
+            // Battery and memory history is kept whichever tab is open
+            system_info.power.refresh();
+            system_info.memory.refresh();
+            system_info.scheduler.refresh();
             app.add_cpu_data(system_info.get_cpu_usage());
             app.add_memory_data(system_info.get_memory_percentage());
-            *last_tick = Instant::now();
+            if let Some(process) = app
+                .process_detail
+                .and_then(|pid| system_info.system.process(sysinfo::Pid::from_u32(pid)))
+            {
+                app.add_detail_sample(process.cpu_usage(), process.memory());
+            }
+            last_tick = Instant::now();
         }
 
         if last_vpn_check.elapsed() >= vpn_check_rate {
             *vpn_status = vpn::get_vpn_status();
-            *last_vpn_check = Instant::now();
+            last_vpn_check = Instant::now();
         }
 
         if app.should_quit {
@@ -116,3 +250,35 @@ fn run_app(
         }
     }
 }
+
+fn handle_service_outcome(outcome: ServiceOutcome, app: &mut App, system_info: &mut SystemInfo) {
+    match outcome {
+        ServiceOutcome::Action(action, unit, result) => {
+            app.service_running = false;
+            app.service_message = Some(match result {
+                Ok(()) => format!("systemctl {} {}: done", action.name(), unit),
+                Err(err) => format!("systemctl {} {} failed: {}", action.name(), unit, err),
+            });
+            system_info.services.force_refresh();
+
+            // Keep an open journal current so the effect of the action shows up
+            if app
+                .service_journal
+                .as_ref()
+                .is_some_and(|(journal_unit, _)| *journal_unit == unit)
+            {
+                system_info
+                    .services
+                    .start_request(ServiceRequest::Journal(unit));
+            }
+        }
+        ServiceOutcome::Journal(unit, lines) => {
+            // Only if it is still open on that unit
+            if let Some((journal_unit, journal)) = app.service_journal.as_mut()
+                && *journal_unit == unit
+            {
+                *journal = lines;
+            }
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::sysfs::read_key_values;
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Figures from `/proc/meminfo`, in bytes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemInfo {
+    pub total: u64,
+    pub free: u64,
+    pub available: u64,
+    pub buffers: u64,
+    /// Page cache, including reclaimable slab like `free` does
+    pub cached: u64,
+    pub dirty: u64,
+    pub writeback: u64,
+    pub slab: u64,
+    pub slab_reclaimable: u64,
+    pub shmem: u64,
+    pub page_tables: u64,
+    pub committed: u64,
+    pub commit_limit: u64,
+    pub swap_total: u64,
+    pub swap_free: u64,
+    pub swap_cached: u64,
+    pub huge_pages_total: u64,
+    pub huge_pages_free: u64,
+    pub huge_page_size: u64,
+    pub anon_huge_pages: u64,
+}
+
+impl MemInfo {
+    /// Memory held by processes and the kernel that can't just be dropped.
+    pub fn used(&self) -> u64 {
+        self.total
+            .saturating_sub(self.free)
+            .saturating_sub(self.buffers)
+            .saturating_sub(self.cached)
+    }
+
+    pub fn swap_used(&self) -> u64 {
+        self.swap_total.saturating_sub(self.swap_free)
+    }
+}
+
+/// One line of a `/proc/pressure` file: the share of time some (or all)
+/// tasks were stalled, averaged over 10s, 60s and 300s.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StallAverages {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pressure {
+    pub some: StallAverages,
+    /// Not reported for CPU on older kernels
+    pub full: Option<StallAverages>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemorySample {
+    pub used: u64,
+    pub buffers: u64,
+    pub cached: u64,
+    pub free: u64,
+    /// Bytes per second swapped in and out
+    pub swap_in: u64,
+    pub swap_out: u64,
+    /// `some avg10` of the cpu, memory and io pressure files
+    pub cpu_pressure: f64,
+    pub memory_pressure: f64,
+    pub io_pressure: f64,
+}
+
+/// `/proc/meminfo`, swap activity from `/proc/vmstat` and pressure stall
+/// information from `/proc/pressure`.
+pub struct MemoryMonitor {
+    pub info: MemInfo,
+    /// None when the kernel was built without PSI
+    pub cpu_pressure: Option<Pressure>,
+    pub memory_pressure: Option<Pressure>,
+    pub io_pressure: Option<Pressure>,
+    pub swap_in_rate: u64,
+    pub swap_out_rate: u64,
+    pub history: VecDeque<MemorySample>,
+    pub history_size: usize,
+    previous_swap: Option<(Instant, u64, u64)>,
+    last_refresh: Option<Instant>,
+}
+
+impl MemoryMonitor {
+    pub fn new() -> Self {
+        Self {
+            info: MemInfo::default(),
+            cpu_pressure: None,
+            memory_pressure: None,
+            io_pressure: None,
+            swap_in_rate: 0,
+            swap_out_rate: 0,
+            history: VecDeque::with_capacity(100),
+            history_size: 100,
+            previous_swap: None,
+            last_refresh: None,
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        if self
+            .last_refresh
+            .is_some_and(|last| last.elapsed() < REFRESH_INTERVAL)
+        {
+            return;
+        }
+        let now = Instant::now();
+        self.last_refresh = Some(now);
+
+        self.info = read_meminfo();
+        self.cpu_pressure = read_pressure("cpu");
+        self.memory_pressure = read_pressure("memory");
+        self.io_pressure = read_pressure("io");
+
+        let vmstat = read_vmstat();
+        let page_size = page_size();
+        let swapped_in = vmstat.get("pswpin").copied().unwrap_or(0);
+        let swapped_out = vmstat.get("pswpout").copied().unwrap_or(0);
+        if let Some((at, previous_in, previous_out)) = self.previous_swap {
+            let seconds = now.duration_since(at).as_secs_f64();
+            if seconds > 0.0 {
+                let rate = |pages: u64| (pages as f64 * page_size as f64 / seconds) as u64;
+                self.swap_in_rate = rate(swapped_in.saturating_sub(previous_in));
+                self.swap_out_rate = rate(swapped_out.saturating_sub(previous_out));
+            }
+        }
+        self.previous_swap = Some((now, swapped_in, swapped_out));
+
+        let some_avg10 = |pressure: Option<Pressure>| pressure.map_or(0.0, |p| p.some.avg10);
+        if self.history.len() >= self.history_size {
+            self.history.pop_front();
+        }
+        self.history.push_back(MemorySample {
+            used: self.info.used(),
+            buffers: self.info.buffers,
+            cached: self.info.cached,
+            free: self.info.free,
+            swap_in: self.swap_in_rate,
+            swap_out: self.swap_out_rate,
+            cpu_pressure: some_avg10(self.cpu_pressure),
+            memory_pressure: some_avg10(self.memory_pressure),
+            io_pressure: some_avg10(self.io_pressure),
+        });
+    }
+
+    pub fn has_pressure(&self) -> bool {
+        self.cpu_pressure.is_some() || self.memory_pressure.is_some() || self.io_pressure.is_some()
+    }
+}
+
+fn read_meminfo() -> MemInfo {
+    // Values are in kB, except the HugePages_* counts
+    let fields: HashMap<String, u64> = fs::read_to_string("/proc/meminfo")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let value = value.split_whitespace().next()?.parse().ok()?;
+            Some((key.to_string(), value))
+        })
+        .collect();
+    let kb = |key: &str| fields.get(key).copied().unwrap_or(0) * 1024;
+    let count = |key: &str| fields.get(key).copied().unwrap_or(0);
+
+    MemInfo {
+        total: kb("MemTotal"),
+        free: kb("MemFree"),
+        available: kb("MemAvailable"),
+        buffers: kb("Buffers"),
+        cached: kb("Cached") + kb("SReclaimable"),
+        dirty: kb("Dirty"),
+        writeback: kb("Writeback"),
+        slab: kb("Slab"),
+        slab_reclaimable: kb("SReclaimable"),
+        shmem: kb("Shmem"),
+        page_tables: kb("PageTables"),
+        committed: kb("Committed_AS"),
+        commit_limit: kb("CommitLimit"),
+        swap_total: kb("SwapTotal"),
+        swap_free: kb("SwapFree"),
+        swap_cached: kb("SwapCached"),
+        huge_pages_total: count("HugePages_Total"),
+        huge_pages_free: count("HugePages_Free"),
+        huge_page_size: kb("Hugepagesize"),
+        anon_huge_pages: kb("AnonHugePages"),
+    }
+}
+
+fn read_vmstat() -> HashMap<String, u64> {
+    read_key_values(Path::new("/proc/vmstat"))
+}
+
+fn read_pressure(resource: &str) -> Option<Pressure> {
+    let content = fs::read_to_string(format!("/proc/pressure/{}", resource)).ok()?;
+    let mut pressure = Pressure::default();
+
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let kind = parts.next()?;
+        let mut averages = StallAverages::default();
+        for part in parts {
+            let Some((key, value)) = part.split_once('=') else {
+                continue;
+            };
+            let value = value.parse().unwrap_or(0.0);
+            match key {
+                "avg10" => averages.avg10 = value,
+                "avg60" => averages.avg60 = value,
+                "avg300" => averages.avg300 = value,
+                _ => {}
+            }
+        }
+
+        match kind {
+            "some" => pressure.some = averages,
+            "full" => pressure.full = Some(averages),
+            _ => {}
+        }
+    }
+
+    Some(pressure)
+}
+
+fn page_size() -> u64 {
+    // SAFETY: sysconf has no preconditions, it only reads a constant.
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if size > 0 { size as u64 } else { 4096 }
+}
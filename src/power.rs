@@ -0,0 +1,337 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::sysfs::{read_number, read_signed, read_string};
+
+/// Batteries report new values every few seconds at best.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+/// Samples kept per battery, 30 minutes at the refresh interval.
+const HISTORY_SIZE: usize = 360;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatteryState {
+    Charging,
+    Discharging,
+    Full,
+    NotCharging,
+    Unknown,
+}
+
+impl BatteryState {
+    fn parse(status: &str) -> Self {
+        match status {
+            "Charging" => BatteryState::Charging,
+            "Discharging" => BatteryState::Discharging,
+            "Full" => BatteryState::Full,
+            "Not charging" => BatteryState::NotCharging,
+            _ => BatteryState::Unknown,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            BatteryState::Charging => "Charging",
+            BatteryState::Discharging => "Discharging",
+            BatteryState::Full => "Full",
+            BatteryState::NotCharging => "Not charging",
+            BatteryState::Unknown => "Unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Battery {
+    /// Name of the power supply, e.g. `BAT0`
+    pub name: String,
+    pub model: Option<String>,
+    pub technology: Option<String>,
+    pub state: BatteryState,
+    /// Charge in percent of the current full capacity
+    pub percent: f64,
+    /// Energy figures in watt hours, when the battery reports them (or
+    /// charge and voltage to derive them from)
+    pub energy_now: Option<f64>,
+    pub energy_full: Option<f64>,
+    pub energy_full_design: Option<f64>,
+    /// Charge or discharge rate in watts, always positive
+    pub power: Option<f64>,
+    pub cycle_count: Option<u64>,
+}
+
+impl Battery {
+    /// Full capacity compared to what the battery was designed for.
+    pub fn health_percent(&self) -> Option<f64> {
+        match (self.energy_full, self.energy_full_design) {
+            (Some(full), Some(design)) if design > 0.0 => Some(full / design * 100.0),
+            _ => None,
+        }
+    }
+
+    /// Time until empty while discharging, or until full while charging, at
+    /// the current rate.
+    pub fn time_remaining(&self) -> Option<Duration> {
+        let power = self.power.filter(|power| *power > 0.0)?;
+        // Gauges can glitch below zero, and a tiny rate makes a huge figure
+        let now = self.energy_now?.max(0.0);
+        let hours = match self.state {
+            BatteryState::Discharging => now / power,
+            BatteryState::Charging => (self.energy_full? - now).max(0.0) / power,
+            _ => return None,
+        };
+        Duration::try_from_secs_f64(hours * 3600.0).ok()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Adapter {
+    pub name: String,
+    pub online: bool,
+}
+
+/// Batteries and AC adapters under `/sys/class/power_supply`.
+pub struct PowerMonitor {
+    root: PathBuf,
+    pub batteries: Vec<Battery>,
+    pub adapters: Vec<Adapter>,
+    /// (charge %, watts) per battery, oldest first
+    pub history: HashMap<String, VecDeque<(f64, f64)>>,
+    pub history_size: usize,
+    last_refresh: Option<Instant>,
+}
+
+impl PowerMonitor {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            batteries: Vec::new(),
+            adapters: Vec::new(),
+            history: HashMap::new(),
+            history_size: HISTORY_SIZE,
+            last_refresh: None,
+        }
+    }
+
+    /// Whether there is anything to show, so desktops don't get an empty panel.
+    pub fn has_battery(&self) -> bool {
+        !self.batteries.is_empty()
+    }
+
+    pub fn refresh(&mut self) {
+        if self
+            .last_refresh
+            .is_some_and(|last| last.elapsed() < REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.last_refresh = Some(Instant::now());
+
+        let mut supplies: Vec<PathBuf> = fs::read_dir(&self.root)
+            .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+            .unwrap_or_default();
+        supplies.sort();
+
+        self.batteries.clear();
+        self.adapters.clear();
+
+        for path in supplies {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            match read_string(&path.join("type")).as_deref() {
+                Some("Battery") => {
+                    // Mice and keyboards report themselves as "Device" scoped
+                    // batteries, they say nothing about this machine's power
+                    if read_string(&path.join("scope")).as_deref() == Some("Device") {
+                        continue;
+                    }
+                    self.batteries.push(read_battery(&path, name));
+                }
+                Some("Mains") | Some("USB") => self.adapters.push(Adapter {
+                    name,
+                    online: read_number(&path.join("online")) == Some(1),
+                }),
+                _ => {}
+            }
+        }
+
+        for battery in &self.batteries {
+            let history = self.history.entry(battery.name.clone()).or_default();
+            if history.len() >= self.history_size {
+                history.pop_front();
+            }
+            history.push_back((battery.percent, battery.power.unwrap_or(0.0)));
+        }
+
+        let batteries = &self.batteries;
+        self.history
+            .retain(|name, _| batteries.iter().any(|battery| &battery.name == name));
+    }
+}
+
+fn read_battery(path: &Path, name: String) -> Battery {
+    // Values are in micro units: µWh, µAh, µW, µA and µV
+    let micro = |file: &str| read_signed(&path.join(file)).map(|value| value as f64 / 1e6);
+    let voltage = micro("voltage_now");
+
+    // Some batteries only report charge (Ah), which needs the voltage to
+    // turn into energy
+    let energy = |energy_file: &str, charge_file: &str| {
+        micro(energy_file).or_else(|| Some(micro(charge_file)? * voltage?))
+    };
+    let energy_now = energy("energy_now", "charge_now");
+    let energy_full = energy("energy_full", "charge_full");
+    let energy_full_design = energy("energy_full_design", "charge_full_design");
+
+    // Discharge current is negative on some hardware
+    let power = micro("power_now")
+        .or_else(|| Some(micro("current_now")? * voltage?))
+        .map(f64::abs);
+
+    let percent = read_number(&path.join("capacity"))
+        .map(|capacity| capacity as f64)
+        .or_else(|| match (energy_now, energy_full) {
+            (Some(now), Some(full)) if full > 0.0 => Some(now / full * 100.0),
+            _ => None,
+        })
+        .unwrap_or(0.0)
+        .clamp(0.0, 100.0);
+
+    Battery {
+        name,
+        model: read_string(&path.join("model_name")),
+        technology: read_string(&path.join("technology")),
+        state: read_string(&path.join("status"))
+            .map(|status| BatteryState::parse(&status))
+            .unwrap_or(BatteryState::Unknown),
+        percent,
+        energy_now,
+        energy_full,
+        energy_full_design,
+        power,
+        cycle_count: read_number(&path.join("cycle_count")).filter(|count| *count > 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysfs::testing::write;
+
+    /// An empty directory to lay out a fake power_supply tree in.
+    fn fake_root(test: &str) -> PathBuf {
+        crate::sysfs::testing::fake_root("power", test)
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("no value");
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn reads_energy_battery_and_adapters() {
+        let root = fake_root("energy");
+        write(&root, "AC/type", "Mains");
+        write(&root, "AC/online", "1");
+        write(&root, "BAT0/type", "Battery");
+        write(&root, "BAT0/status", "Discharging");
+        write(&root, "BAT0/capacity", "50");
+        write(&root, "BAT0/energy_now", "30000000");
+        write(&root, "BAT0/energy_full", "60000000");
+        write(&root, "BAT0/energy_full_design", "80000000");
+        write(&root, "BAT0/power_now", "10000000");
+        write(&root, "BAT0/cycle_count", "0");
+        write(&root, "BAT0/model_name", "5B10W13930");
+        // A wireless mouse, not this machine's battery
+        write(&root, "hidpp_battery_0/type", "Battery");
+        write(&root, "hidpp_battery_0/scope", "Device");
+        write(&root, "hidpp_battery_0/capacity", "90");
+
+        let mut monitor = PowerMonitor::new(root.clone());
+        monitor.refresh();
+
+        assert_eq!(monitor.adapters.len(), 1);
+        assert!(monitor.adapters[0].online);
+        assert_eq!(monitor.batteries.len(), 1);
+        let battery = &monitor.batteries[0];
+        assert_eq!(battery.name, "BAT0");
+        assert_eq!(battery.model.as_deref(), Some("5B10W13930"));
+        assert_eq!(battery.state, BatteryState::Discharging);
+        assert_eq!(battery.percent, 50.0);
+        assert_close(battery.energy_now, 30.0);
+        assert_close(battery.power, 10.0);
+        assert_close(battery.health_percent(), 75.0);
+        assert_eq!(battery.cycle_count, None);
+        assert_eq!(
+            battery.time_remaining(),
+            Some(Duration::from_secs(3 * 3600))
+        );
+        assert_eq!(monitor.history["BAT0"], [(50.0, 10.0)]);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn derives_energy_from_charge_and_voltage() {
+        let root = fake_root("charge");
+        write(&root, "BAT1/type", "Battery");
+        write(&root, "BAT1/status", "Charging");
+        write(&root, "BAT1/charge_now", "2000000");
+        write(&root, "BAT1/charge_full", "4000000");
+        write(&root, "BAT1/voltage_now", "12000000");
+        // Negative while charging on this hardware
+        write(&root, "BAT1/current_now", "-2000000");
+
+        let mut monitor = PowerMonitor::new(root.clone());
+        monitor.refresh();
+
+        let battery = &monitor.batteries[0];
+        assert_eq!(battery.state, BatteryState::Charging);
+        assert_close(battery.energy_now, 24.0);
+        assert_close(battery.energy_full, 48.0);
+        assert_close(battery.power, 24.0);
+        assert_close(Some(battery.percent), 50.0);
+        assert_eq!(battery.health_percent(), None);
+        // Time to full while charging
+        assert_eq!(battery.time_remaining(), Some(Duration::from_secs(3600)));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn clamps_time_remaining() {
+        let root = fake_root("glitch");
+        write(&root, "BAT0/type", "Battery");
+        write(&root, "BAT0/status", "Discharging");
+        write(&root, "BAT0/energy_now", "-5000000");
+        write(&root, "BAT0/energy_full", "60000000");
+        write(&root, "BAT0/power_now", "10000000");
+
+        let mut monitor = PowerMonitor::new(root.clone());
+        monitor.refresh();
+
+        let battery = &mut monitor.batteries[0];
+        assert_close(battery.energy_now, -5.0);
+        assert_eq!(battery.time_remaining(), Some(Duration::ZERO));
+        battery.state = BatteryState::Charging;
+        assert_eq!(
+            battery.time_remaining(),
+            Some(Duration::from_secs(6 * 3600))
+        );
+        // Too long for a Duration
+        battery.power = Some(f64::MIN_POSITIVE);
+        assert_eq!(battery.time_remaining(), None);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
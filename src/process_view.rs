@@ -0,0 +1,118 @@
+use std::cmp::Ordering;
+
+use crate::{
+    app::ProcessColumn,
+    system::{ProcessCategory, ProcessInfo, ProcessNetwork},
+};
+
+/// The process list the Processes tab draws from: a snapshot taken once per
+/// refresh, sorted by the current column and split by category. Frames in
+/// between only read it, and it is only sorted again when a new snapshot
+/// arrives or the sort changes.
+#[derive(Debug, Default)]
+pub struct ProcessView {
+    processes: Vec<ProcessInfo>,
+    /// Column and direction `processes` is sorted by, None when unsorted
+    sorted_by: Option<(ProcessColumn, bool)>,
+    /// Indices into `processes` per category, in `ProcessCategory::ALL` order
+    categories: [Vec<usize>; ProcessCategory::ALL.len()],
+}
+
+impl ProcessView {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hands out the previous snapshot's buffer to be refilled, so its
+    /// allocation is reused.
+    pub fn take_buffer(&mut self) -> Vec<ProcessInfo> {
+        let mut buffer = std::mem::take(&mut self.processes);
+        buffer.clear();
+        buffer
+    }
+
+    pub fn set_processes(&mut self, processes: Vec<ProcessInfo>) {
+        self.processes = processes;
+        self.sorted_by = None;
+    }
+
+    pub fn sort(&mut self, column: ProcessColumn, ascending: bool) {
+        if self.sorted_by == Some((column, ascending)) {
+            return;
+        }
+
+        self.processes.sort_by(|a, b| {
+            let cmp = compare_processes(column, a, b);
+            if ascending { cmp } else { cmp.reverse() }
+        });
+        self.sorted_by = Some((column, ascending));
+
+        for indices in &mut self.categories {
+            indices.clear();
+        }
+        for (index, process) in self.processes.iter().enumerate() {
+            let slot = ProcessCategory::ALL
+                .iter()
+                .position(|category| *category == process.category)
+                .unwrap_or(0);
+            self.categories[slot].push(index);
+        }
+    }
+
+    /// Every process, in sort order.
+    pub fn all(&self) -> &[ProcessInfo] {
+        &self.processes
+    }
+
+    /// The processes of one category, in sort order.
+    pub fn category(
+        &self,
+        category: ProcessCategory,
+    ) -> impl ExactSizeIterator<Item = &ProcessInfo> + Clone {
+        let slot = ProcessCategory::ALL
+            .iter()
+            .position(|c| *c == category)
+            .unwrap_or(0);
+        self.categories[slot]
+            .iter()
+            .map(|&index| &self.processes[index])
+    }
+}
+
+fn compare_processes(column: ProcessColumn, a: &ProcessInfo, b: &ProcessInfo) -> Ordering {
+    match column {
+        ProcessColumn::Pid => a.pid.cmp(&b.pid),
+        ProcessColumn::Name => a.name.cmp(&b.name),
+        ProcessColumn::Cpu => a
+            .cpu_usage
+            .partial_cmp(&b.cpu_usage)
+            .unwrap_or(Ordering::Equal),
+        ProcessColumn::Memory => a.memory.cmp(&b.memory),
+        ProcessColumn::User => a.user.cmp(&b.user),
+        ProcessColumn::State => a.state.cmp(&b.state),
+        ProcessColumn::Threads => a.threads.cmp(&b.threads),
+        ProcessColumn::Command => a.command.cmp(&b.command),
+        ProcessColumn::StartTime => a.start_time.cmp(&b.start_time),
+        ProcessColumn::RunTime => a.run_time.cmp(&b.run_time),
+        ProcessColumn::VirtualMemory => a.virtual_memory.cmp(&b.virtual_memory),
+        ProcessColumn::DiskRead => a.disk_read.cmp(&b.disk_read),
+        ProcessColumn::DiskWrite => a.disk_written.cmp(&b.disk_written),
+        ProcessColumn::DiskReadRate => a.disk_read_rate.cmp(&b.disk_read_rate),
+        ProcessColumn::DiskWriteRate => a.disk_write_rate.cmp(&b.disk_write_rate),
+        ProcessColumn::Sockets => network_key(a, |net| net.usage.sockets as u64)
+            .cmp(&network_key(b, |net| net.usage.sockets as u64)),
+        ProcessColumn::NetReceived => network_key(a, |net| net.usage.bytes_received)
+            .cmp(&network_key(b, |net| net.usage.bytes_received)),
+        ProcessColumn::NetSent => network_key(a, |net| net.usage.bytes_sent)
+            .cmp(&network_key(b, |net| net.usage.bytes_sent)),
+        ProcessColumn::NetRate => network_key(a, ProcessNetwork::total_rate)
+            .cmp(&network_key(b, ProcessNetwork::total_rate)),
+        ProcessColumn::Nice => a.nice.cmp(&b.nice),
+        ProcessColumn::Priority => a.priority.cmp(&b.priority),
+    }
+}
+
+/// Unknown network usage sorts below every known value.
+fn network_key(proc: &ProcessInfo, key: impl Fn(&ProcessNetwork) -> u64) -> Option<u64> {
+    proc.network.as_ref().map(key)
+}
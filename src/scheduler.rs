@@ -0,0 +1,164 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    time::{Duration, Instant},
+};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+/// Rates are compared to their average over this many samples for the
+/// trend arrows.
+const TREND_SAMPLES: usize = 10;
+/// How far off the average a rate has to be to count as rising or falling.
+const TREND_THRESHOLD: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trend {
+    Rising,
+    Falling,
+    Steady,
+}
+
+impl Trend {
+    fn between(current: f64, reference: f64) -> Self {
+        if current > reference * (1.0 + TREND_THRESHOLD) {
+            Trend::Rising
+        } else if current < reference * (1.0 - TREND_THRESHOLD) {
+            Trend::Falling
+        } else {
+            Trend::Steady
+        }
+    }
+
+    pub fn arrow(&self) -> &str {
+        match self {
+            Trend::Rising => "↑",
+            Trend::Falling => "↓",
+            Trend::Steady => "→",
+        }
+    }
+}
+
+/// A per-second rate with enough recent history to tell where it's heading.
+#[derive(Debug, Clone, Default)]
+pub struct Rate {
+    pub per_second: f64,
+    recent: VecDeque<f64>,
+}
+
+impl Rate {
+    fn push(&mut self, per_second: f64) {
+        self.per_second = per_second;
+        if self.recent.len() >= TREND_SAMPLES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(per_second);
+    }
+
+    pub fn trend(&self) -> Trend {
+        if self.recent.len() < 2 {
+            return Trend::Steady;
+        }
+        let average = self.recent.iter().sum::<f64>() / self.recent.len() as f64;
+        Trend::between(self.per_second, average)
+    }
+}
+
+/// Cumulative counters from `/proc/stat`.
+#[derive(Debug, Clone, Copy, Default)]
+struct Counters {
+    context_switches: u64,
+    interrupts: u64,
+    forks: u64,
+}
+
+/// Load, run queue and scheduler activity for the Overview stats strip.
+pub struct SchedulerStats {
+    /// Load averages over 1, 5 and 15 minutes
+    pub load: (f64, f64, f64),
+    /// Tasks currently runnable and blocked on I/O
+    pub running: u64,
+    pub blocked: u64,
+    pub total_tasks: u64,
+    pub context_switches: Rate,
+    pub interrupts: Rate,
+    pub forks: Rate,
+    previous: Option<(Instant, Counters)>,
+    last_refresh: Option<Instant>,
+}
+
+impl SchedulerStats {
+    pub fn new() -> Self {
+        Self {
+            load: (0.0, 0.0, 0.0),
+            running: 0,
+            blocked: 0,
+            total_tasks: 0,
+            context_switches: Rate::default(),
+            interrupts: Rate::default(),
+            forks: Rate::default(),
+            previous: None,
+            last_refresh: None,
+        }
+    }
+
+    /// The 1 minute load compared to the 5 minute one.
+    pub fn load_trend(&self) -> Trend {
+        Trend::between(self.load.0, self.load.1)
+    }
+
+    pub fn refresh(&mut self) {
+        if self
+            .last_refresh
+            .is_some_and(|last| last.elapsed() < REFRESH_INTERVAL)
+        {
+            return;
+        }
+        let now = Instant::now();
+        self.last_refresh = Some(now);
+
+        let load = sysinfo::System::load_average();
+        self.load = (load.one, load.five, load.fifteen);
+        self.total_tasks = read_total_tasks().unwrap_or(0);
+
+        let stat = fs::read_to_string("/proc/stat").unwrap_or_default();
+        let mut counters = Counters::default();
+        for line in stat.lines() {
+            let mut fields = line.split_whitespace();
+            let key = fields.next().unwrap_or_default();
+            // intr is followed by per-interrupt counts, the first is the total
+            let value = fields
+                .next()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0);
+            match key {
+                "ctxt" => counters.context_switches = value,
+                "intr" => counters.interrupts = value,
+                "processes" => counters.forks = value,
+                "procs_running" => self.running = value,
+                "procs_blocked" => self.blocked = value,
+                _ => {}
+            }
+        }
+
+        if let Some((at, previous)) = self.previous {
+            let seconds = now.duration_since(at).as_secs_f64();
+            if seconds > 0.0 {
+                let rate =
+                    |current: u64, previous: u64| current.saturating_sub(previous) as f64 / seconds;
+                self.context_switches
+                    .push(rate(counters.context_switches, previous.context_switches));
+                self.interrupts
+                    .push(rate(counters.interrupts, previous.interrupts));
+                self.forks.push(rate(counters.forks, previous.forks));
+            }
+        }
+        self.previous = Some((now, counters));
+    }
+}
+
+/// The "running/total" field of `/proc/loadavg` has the total task count.
+fn read_total_tasks() -> Option<u64> {
+    let loadavg = fs::read_to_string("/proc/loadavg").ok()?;
+    let tasks = loadavg.split_whitespace().nth(3)?;
+    tasks.split_once('/')?.1.parse().ok()
+}
@@ -0,0 +1,453 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::export;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenshotFormat {
+    /// Characters only
+    Text,
+    /// Text with the escape codes to replay it in a terminal (`cat`, `less -R`)
+    Ansi,
+    #[default]
+    Html,
+    Svg,
+}
+
+impl ScreenshotFormat {
+    pub fn extension(&self) -> &str {
+        match self {
+            ScreenshotFormat::Text => "txt",
+            ScreenshotFormat::Ansi => "ans",
+            ScreenshotFormat::Html => "html",
+            ScreenshotFormat::Svg => "svg",
+        }
+    }
+}
+
+/// Terminal default colors, for cells that don't set their own.
+struct Palette {
+    foreground: &'static str,
+    background: &'static str,
+}
+
+const DARK: Palette = Palette {
+    foreground: "#e5e5e5",
+    background: "#1e1e1e",
+};
+const LIGHT: Palette = Palette {
+    foreground: "#1e1e1e",
+    background: "#ffffff",
+};
+
+/// SVG cell size in pixels, for a 14px monospace font.
+const CELL_WIDTH: f64 = 8.4;
+const CELL_HEIGHT: f64 = 17.0;
+
+/// Neighbouring cells of one row that look the same.
+struct Run {
+    text: String,
+    /// Columns covered, which wide characters make differ from `text.len()`
+    width: usize,
+    fg: Color,
+    bg: Color,
+    modifier: Modifier,
+}
+
+/// Writes `buffer` as `<directory>/myworkspace-<view>-<time>.<ext>`, with a
+/// number added if that file exists, and returns the path. `light` picks the default colors for HTML and SVG, to
+/// match a light terminal.
+pub fn write(
+    buffer: &Buffer,
+    format: ScreenshotFormat,
+    directory: &Path,
+    view: &str,
+    light: bool,
+) -> Result<PathBuf> {
+    let palette = if light { &LIGHT } else { &DARK };
+    let rows = runs(buffer);
+    let contents = match format {
+        ScreenshotFormat::Text => text(&rows),
+        ScreenshotFormat::Ansi => ansi(&rows),
+        ScreenshotFormat::Html => html(&rows, palette),
+        ScreenshotFormat::Svg => svg(&rows, palette, buffer.area.width, buffer.area.height),
+    };
+
+    fs::create_dir_all(directory)
+        .with_context(|| format!("cannot create {}", directory.display()))?;
+    let name = format!(
+        "myworkspace-{}-{}",
+        view,
+        Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let path = export::unused_path(directory, &name, Some(format.extension()));
+    fs::write(&path, contents).with_context(|| format!("cannot write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Each row of the buffer as runs. Cells hidden under the right half of a
+/// wide character are left out, as a terminal would.
+fn runs(buffer: &Buffer) -> Vec<Vec<Run>> {
+    buffer
+        .content
+        .chunks(buffer.area.width.max(1) as usize)
+        .map(|cells| {
+            let mut runs: Vec<Run> = Vec::new();
+            let mut skip = 0;
+            for cell in cells {
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                let symbol = cell.symbol();
+                let width = symbol.width().max(1);
+                skip = width - 1;
+                match runs.last_mut() {
+                    Some(run)
+                        if run.fg == cell.fg
+                            && run.bg == cell.bg
+                            && run.modifier == cell.modifier =>
+                    {
+                        run.text.push_str(symbol);
+                        run.width += width;
+                    }
+                    _ => runs.push(Run {
+                        text: symbol.to_string(),
+                        width,
+                        fg: cell.fg,
+                        bg: cell.bg,
+                        modifier: cell.modifier,
+                    }),
+                }
+            }
+            runs
+        })
+        .collect()
+}
+
+fn text(rows: &[Vec<Run>]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        let line: String = row.iter().map(|run| run.text.as_str()).collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn ansi(rows: &[Vec<Run>]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        for run in row {
+            let mut codes = vec!["0".to_string()];
+            for (modifier, code) in [
+                (Modifier::BOLD, "1"),
+                (Modifier::DIM, "2"),
+                (Modifier::ITALIC, "3"),
+                (Modifier::UNDERLINED, "4"),
+                (Modifier::SLOW_BLINK, "5"),
+                (Modifier::RAPID_BLINK, "6"),
+                (Modifier::REVERSED, "7"),
+                (Modifier::HIDDEN, "8"),
+                (Modifier::CROSSED_OUT, "9"),
+            ] {
+                if run.modifier.contains(modifier) {
+                    codes.push(code.to_string());
+                }
+            }
+            codes.extend(ansi_color(run.fg, false));
+            codes.extend(ansi_color(run.bg, true));
+            let _ = write!(out, "\x1b[{}m{}", codes.join(";"), run.text);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// SGR parameters selecting `color`, None for the terminal default.
+fn ansi_color(color: Color, background: bool) -> Option<String> {
+    let base = if background { 40 } else { 30 };
+    let extended = if background { 48 } else { 38 };
+    Some(match color {
+        Color::Reset => return None,
+        Color::Indexed(index) => format!("{};5;{}", extended, index),
+        Color::Rgb(r, g, b) => format!("{};2;{};{};{}", extended, r, g, b),
+        named => {
+            let index = named_index(named)?;
+            if index < 8 {
+                (base + index).to_string()
+            } else {
+                // The bright variants sit 60 above the normal ones
+                (base + 60 + index - 8).to_string()
+            }
+        }
+    })
+}
+
+/// Position of a named color in the 16-color palette.
+fn named_index(color: Color) -> Option<u8> {
+    Some(match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        _ => return None,
+    })
+}
+
+/// `color` as `#rrggbb`, using xterm's default palette for named and
+/// indexed colors. None for the terminal default.
+fn hex(color: Color) -> Option<String> {
+    const BASIC: [(u8, u8, u8); 16] = [
+        (0x00, 0x00, 0x00),
+        (0xcd, 0x00, 0x00),
+        (0x00, 0xcd, 0x00),
+        (0xcd, 0xcd, 0x00),
+        (0x00, 0x00, 0xee),
+        (0xcd, 0x00, 0xcd),
+        (0x00, 0xcd, 0xcd),
+        (0xe5, 0xe5, 0xe5),
+        (0x7f, 0x7f, 0x7f),
+        (0xff, 0x00, 0x00),
+        (0x00, 0xff, 0x00),
+        (0xff, 0xff, 0x00),
+        (0x5c, 0x5c, 0xff),
+        (0xff, 0x00, 0xff),
+        (0x00, 0xff, 0xff),
+        (0xff, 0xff, 0xff),
+    ];
+    let (r, g, b) = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(index @ 0..=15) => BASIC[index as usize],
+        // 6x6x6 color cube
+        Color::Indexed(index @ 16..=231) => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = index - 16;
+            (level(index / 36), level(index / 6 % 6), level(index % 6))
+        }
+        // Grayscale ramp
+        Color::Indexed(index) => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+        named => BASIC[named_index(named)? as usize],
+    };
+    Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+}
+
+/// Foreground and background of a run as they end up on screen, after
+/// defaults, reversing and hiding.
+fn resolved_colors(run: &Run, palette: &Palette) -> (String, String) {
+    let mut fg = hex(run.fg).unwrap_or_else(|| palette.foreground.to_string());
+    let mut bg = hex(run.bg).unwrap_or_else(|| palette.background.to_string());
+    if run.modifier.contains(Modifier::REVERSED) {
+        std::mem::swap(&mut fg, &mut bg);
+    }
+    if run.modifier.contains(Modifier::HIDDEN) {
+        fg = bg.clone();
+    }
+    (fg, bg)
+}
+
+/// CSS for the text attributes of a run, colors aside.
+fn font_style(modifier: Modifier) -> String {
+    let mut style = String::new();
+    if modifier.contains(Modifier::BOLD) {
+        style.push_str("font-weight:bold;");
+    }
+    if modifier.contains(Modifier::ITALIC) {
+        style.push_str("font-style:italic;");
+    }
+    if modifier.contains(Modifier::DIM) {
+        style.push_str("opacity:0.6;");
+    }
+    match (
+        modifier.contains(Modifier::UNDERLINED),
+        modifier.contains(Modifier::CROSSED_OUT),
+    ) {
+        (true, true) => style.push_str("text-decoration:underline line-through;"),
+        (true, false) => style.push_str("text-decoration:underline;"),
+        (false, true) => style.push_str("text-decoration:line-through;"),
+        (false, false) => {}
+    }
+    style
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html(rows: &[Vec<Run>], palette: &Palette) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>myWorkspace</title>\n</head>\n\
+         <body style=\"margin:0;background:{bg}\">\n\
+         <pre style=\"margin:0;padding:8px;font-family:monospace;line-height:1.2;color:{fg};background:{bg}\">",
+        fg = palette.foreground,
+        bg = palette.background
+    );
+    for row in rows {
+        for run in row {
+            let (fg, bg) = resolved_colors(run, palette);
+            // The page background already covers cells that don't set one
+            let background = if bg == palette.background {
+                String::new()
+            } else {
+                format!("background:{};", bg)
+            };
+            let _ = write!(
+                out,
+                "<span style=\"color:{};{}{}\">{}</span>",
+                fg,
+                background,
+                font_style(run.modifier),
+                escape(&run.text)
+            );
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+fn svg(rows: &[Vec<Run>], palette: &Palette, width: u16, height: u16) -> String {
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+         font-family=\"monospace\" font-size=\"14\">\n<rect width=\"100%\" height=\"100%\" fill=\"{bg}\"/>\n",
+        w = width as f64 * CELL_WIDTH,
+        h = height as f64 * CELL_HEIGHT,
+        bg = palette.background
+    );
+    for (y, row) in rows.iter().enumerate() {
+        let top = y as f64 * CELL_HEIGHT;
+        let mut column = 0;
+        for run in row {
+            let (fg, bg) = resolved_colors(run, palette);
+            let left = column as f64 * CELL_WIDTH;
+            let span = run.width as f64 * CELL_WIDTH;
+            if bg != palette.background {
+                let _ = writeln!(
+                    out,
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>",
+                    left, top, span, CELL_HEIGHT, bg
+                );
+            }
+            if !run.text.trim().is_empty() {
+                // textLength pins the run to its cells whatever font the
+                // viewer substitutes, so box drawing stays lined up
+                let _ = writeln!(
+                    out,
+                    "<text x=\"{:.1}\" y=\"{:.1}\" fill=\"{}\" style=\"{}\" textLength=\"{:.1}\" \
+                     lengthAdjust=\"spacingAndGlyphs\" xml:space=\"preserve\">{}</text>",
+                    left,
+                    top + CELL_HEIGHT * 0.8,
+                    fg,
+                    font_style(run.modifier),
+                    span,
+                    escape(&run.text)
+                );
+            }
+            column += run.width;
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{layout::Rect, style::Style};
+
+    #[test]
+    fn skips_cells_under_wide_characters() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buffer.set_string(0, 0, "a世b", Style::default().fg(Color::Red));
+        buffer.set_string(0, 1, "xy", Style::default().bg(Color::Blue));
+
+        let rows = runs(&buffer);
+        assert_eq!(rows.len(), 2);
+        let first: Vec<(&str, usize)> = rows[0]
+            .iter()
+            .map(|run| (run.text.as_str(), run.width))
+            .collect();
+        assert_eq!(first, [("a世b", 4), ("  ", 2)]);
+        assert_eq!(rows[0][0].fg, Color::Red);
+        assert_eq!(rows[1][0].text, "xy");
+        assert_eq!(rows[1][0].bg, Color::Blue);
+        assert_eq!(text(&rows), "a世b\nxy\n");
+    }
+
+    #[test]
+    fn escapes_markup() {
+        assert_eq!(
+            escape(r#"<a href="x">&</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+        assert_eq!(escape("plain │ text"), "plain │ text");
+    }
+
+    #[test]
+    fn converts_colors_to_hex() {
+        assert_eq!(hex(Color::Reset), None);
+        assert_eq!(hex(Color::Rgb(1, 2, 255)).as_deref(), Some("#0102ff"));
+        assert_eq!(hex(Color::LightBlue).as_deref(), Some("#5c5cff"));
+        assert_eq!(hex(Color::Indexed(12)).as_deref(), Some("#5c5cff"));
+        // Corners and a middle step of the 6x6x6 cube
+        assert_eq!(hex(Color::Indexed(16)).as_deref(), Some("#000000"));
+        assert_eq!(hex(Color::Indexed(231)).as_deref(), Some("#ffffff"));
+        assert_eq!(
+            hex(Color::Indexed(16 + 36 + 2 * 6 + 5)).as_deref(),
+            Some("#5f87ff")
+        );
+        // Both ends of the grayscale ramp
+        assert_eq!(hex(Color::Indexed(232)).as_deref(), Some("#080808"));
+        assert_eq!(hex(Color::Indexed(255)).as_deref(), Some("#eeeeee"));
+    }
+
+    #[test]
+    fn picks_sgr_codes() {
+        assert_eq!(ansi_color(Color::Reset, false), None);
+        assert_eq!(ansi_color(Color::Red, false).as_deref(), Some("31"));
+        assert_eq!(ansi_color(Color::Gray, true).as_deref(), Some("47"));
+        // Bright colors sit 60 above the normal ones
+        assert_eq!(ansi_color(Color::DarkGray, false).as_deref(), Some("90"));
+        assert_eq!(ansi_color(Color::LightRed, false).as_deref(), Some("91"));
+        assert_eq!(ansi_color(Color::White, true).as_deref(), Some("107"));
+        assert_eq!(
+            ansi_color(Color::Indexed(200), false).as_deref(),
+            Some("38;5;200")
+        );
+        assert_eq!(
+            ansi_color(Color::Rgb(1, 2, 3), true).as_deref(),
+            Some("48;2;1;2;3")
+        );
+    }
+}
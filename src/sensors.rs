@@ -0,0 +1,341 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::sysfs::{read_signed, read_string};
+
+/// Some drivers (nvme, amdgpu) wake the device to answer, so don't ask too
+/// often.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SensorKind {
+    Temperature,
+    Fan,
+    Voltage,
+    Current,
+    Power,
+}
+
+impl SensorKind {
+    /// Attribute prefix in hwmon and the factor that turns its raw value
+    /// into the unit shown.
+    const ALL: [(SensorKind, &'static str, f64); 5] = [
+        (SensorKind::Temperature, "temp", 1000.0),
+        (SensorKind::Fan, "fan", 1.0),
+        (SensorKind::Voltage, "in", 1000.0),
+        (SensorKind::Current, "curr", 1000.0),
+        (SensorKind::Power, "power", 1_000_000.0),
+    ];
+
+    pub fn unit(&self) -> &str {
+        match self {
+            SensorKind::Temperature => "°C",
+            SensorKind::Fan => "RPM",
+            SensorKind::Voltage => "V",
+            SensorKind::Current => "A",
+            SensorKind::Power => "W",
+        }
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            SensorKind::Temperature => format!("{:.1}{}", value, self.unit()),
+            SensorKind::Fan => format!("{:.0} {}", value, self.unit()),
+            SensorKind::Voltage | SensorKind::Current => format!("{:.3} {}", value, self.unit()),
+            SensorKind::Power => format!("{:.1} {}", value, self.unit()),
+        }
+    }
+}
+
+/// How a reading compares to the limits the chip reports for it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SensorLevel {
+    Normal,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone)]
+pub struct Sensor {
+    /// Unique within the monitor, e.g. `hwmon2/temp1`
+    pub id: String,
+    pub label: String,
+    pub kind: SensorKind,
+    pub value: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub crit: Option<f64>,
+    pub lcrit: Option<f64>,
+}
+
+impl Sensor {
+    pub fn level(&self) -> SensorLevel {
+        let above = |limit: Option<f64>| limit.is_some_and(|limit| self.value >= limit);
+        // Strictly below, so a stopped fan with a minimum of 0 RPM is fine
+        let below = |limit: Option<f64>| limit.is_some_and(|limit| self.value < limit);
+
+        if above(self.crit) || below(self.lcrit) {
+            SensorLevel::Critical
+        } else if above(self.max) || below(self.min) {
+            SensorLevel::Warning
+        } else {
+            SensorLevel::Normal
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Chip {
+    /// Driver name from the `name` attribute, e.g. `coretemp`
+    pub name: String,
+    /// Directory name under the hwmon root, e.g. `hwmon2`
+    pub hwmon: String,
+    pub sensors: Vec<Sensor>,
+}
+
+/// Temperatures, fans, voltages, currents and power readings from hwmon.
+pub struct SensorMonitor {
+    root: PathBuf,
+    pub chips: Vec<Chip>,
+    /// Readings per sensor id, oldest first
+    pub history: HashMap<String, VecDeque<f64>>,
+    pub history_size: usize,
+    last_refresh: Option<Instant>,
+}
+
+impl SensorMonitor {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            chips: Vec::new(),
+            history: HashMap::new(),
+            history_size: 100,
+            last_refresh: None,
+        }
+    }
+
+    /// All sensors in display order, chip by chip.
+    pub fn sensors(&self) -> impl Iterator<Item = (&Chip, &Sensor)> {
+        self.chips
+            .iter()
+            .flat_map(|chip| chip.sensors.iter().map(move |sensor| (chip, sensor)))
+    }
+
+    pub fn refresh(&mut self) {
+        if self
+            .last_refresh
+            .is_some_and(|last| last.elapsed() < REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.last_refresh = Some(Instant::now());
+
+        let mut entries: Vec<PathBuf> = fs::read_dir(&self.root)
+            .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+            .unwrap_or_default();
+        entries.sort_by_key(|path| hwmon_index(path));
+
+        self.chips = entries.iter().filter_map(|path| read_chip(path)).collect();
+
+        for sensor in self.chips.iter().flat_map(|chip| &chip.sensors) {
+            let history = self.history.entry(sensor.id.clone()).or_default();
+            if history.len() >= self.history_size {
+                history.pop_front();
+            }
+            history.push_back(sensor.value);
+        }
+
+        let chips = &self.chips;
+        self.history.retain(|id, _| {
+            chips
+                .iter()
+                .any(|chip| chip.sensors.iter().any(|sensor| &sensor.id == id))
+        });
+    }
+}
+
+/// Sorts `hwmon10` after `hwmon9`.
+fn hwmon_index(path: &Path) -> (u64, String) {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let index = name.trim_start_matches("hwmon").parse().unwrap_or(u64::MAX);
+    (index, name)
+}
+
+fn read_chip(path: &Path) -> Option<Chip> {
+    let hwmon = path.file_name()?.to_string_lossy().to_string();
+
+    // Older drivers keep their attributes under device/ instead
+    let dir = if path.join("name").exists() {
+        path.to_path_buf()
+    } else {
+        path.join("device")
+    };
+    let name = read_string(&dir.join("name")).unwrap_or_else(|| hwmon.clone());
+
+    let files: Vec<String> = fs::read_dir(&dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+
+    let mut sensors = Vec::new();
+    for (kind, prefix, scale) in SensorKind::ALL {
+        let mut channels: Vec<u32> = files
+            .iter()
+            .filter_map(|file| {
+                let channel = file.strip_prefix(prefix)?;
+                let (number, attribute) = channel.split_once('_')?;
+                // power1_average is what most power meters report
+                matches!(attribute, "input" | "average").then_some(())?;
+                number.parse().ok()
+            })
+            .collect();
+        channels.sort_unstable();
+        channels.dedup();
+
+        for channel in channels {
+            let attribute = |name: &str| {
+                read_signed(&dir.join(format!("{}{}_{}", prefix, channel, name)))
+                    .map(|raw| raw as f64 / scale)
+            };
+            let Some(value) = attribute("input").or_else(|| attribute("average")) else {
+                continue;
+            };
+
+            let label = read_string(&dir.join(format!("{}{}_label", prefix, channel)))
+                .unwrap_or_else(|| format!("{}{}", prefix, channel));
+
+            sensors.push(Sensor {
+                id: format!("{}/{}{}", hwmon, prefix, channel),
+                label,
+                kind,
+                value,
+                // Zero and negative limits are real ones, e.g. a -40°C lcrit
+                min: attribute("min"),
+                // Power meters call their limit a cap
+                max: attribute("max").or_else(|| attribute("cap")),
+                crit: attribute("crit"),
+                lcrit: attribute("lcrit"),
+            });
+        }
+    }
+
+    (!sensors.is_empty()).then_some(Chip {
+        name,
+        hwmon,
+        sensors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysfs::testing::write;
+
+    /// An empty directory to lay out a fake hwmon tree in.
+    fn fake_root(test: &str) -> PathBuf {
+        crate::sysfs::testing::fake_root("sensors", test)
+    }
+
+    #[test]
+    fn groups_sensors_by_chip_with_limits() {
+        let root = fake_root("chips");
+        write(&root, "hwmon0/name", "coretemp");
+        write(&root, "hwmon0/temp1_input", "45000");
+        write(&root, "hwmon0/temp1_label", "Package id 0");
+        write(&root, "hwmon0/temp1_max", "80000");
+        write(&root, "hwmon0/temp1_crit", "100000");
+        write(&root, "hwmon0/temp2_input", "91000");
+        write(&root, "hwmon0/temp2_max", "80000");
+        write(&root, "hwmon0/temp2_crit", "100000");
+        // Attributes under device/, like older drivers keep them
+        write(&root, "hwmon10/device/name", "nct6775");
+        write(&root, "hwmon10/device/fan1_input", "0");
+        write(&root, "hwmon10/device/fan1_min", "0");
+        write(&root, "hwmon10/device/in0_input", "1200");
+        write(&root, "hwmon10/device/in0_min", "0");
+        write(&root, "hwmon10/device/in0_max", "1500");
+        write(&root, "hwmon2/name", "battery_meter");
+        write(&root, "hwmon2/power1_average", "15000000");
+        write(&root, "hwmon2/power1_cap", "30000000");
+        // No readings at all, so no chip
+        write(&root, "hwmon3/name", "acpitz");
+
+        let mut monitor = SensorMonitor::new(root.clone());
+        monitor.refresh();
+
+        let chips: Vec<(&str, &str)> = monitor
+            .chips
+            .iter()
+            .map(|chip| (chip.hwmon.as_str(), chip.name.as_str()))
+            .collect();
+        assert_eq!(
+            chips,
+            [
+                ("hwmon0", "coretemp"),
+                ("hwmon2", "battery_meter"),
+                ("hwmon10", "nct6775")
+            ]
+        );
+
+        let coretemp = &monitor.chips[0].sensors;
+        assert_eq!(coretemp[0].id, "hwmon0/temp1");
+        assert_eq!(coretemp[0].label, "Package id 0");
+        assert_eq!(coretemp[0].kind, SensorKind::Temperature);
+        assert_eq!(coretemp[0].value, 45.0);
+        assert_eq!(coretemp[0].max, Some(80.0));
+        assert_eq!(coretemp[0].crit, Some(100.0));
+        assert_eq!(coretemp[0].level(), SensorLevel::Normal);
+        assert_eq!(coretemp[1].label, "temp2");
+        assert_eq!(coretemp[1].level(), SensorLevel::Warning);
+
+        let meter = &monitor.chips[1].sensors[0];
+        assert_eq!(meter.kind, SensorKind::Power);
+        assert_eq!(meter.value, 15.0);
+        assert_eq!(meter.max, Some(30.0));
+
+        let board = &monitor.chips[2].sensors;
+        assert_eq!(board[0].kind, SensorKind::Fan);
+        assert_eq!(board[0].min, Some(0.0));
+        assert_eq!(board[0].level(), SensorLevel::Normal);
+        assert_eq!(board[1].kind, SensorKind::Voltage);
+        assert_eq!(board[1].value, 1.2);
+        assert_eq!(board[1].min, Some(0.0));
+        assert_eq!(board[1].max, Some(1.5));
+
+        assert_eq!(monitor.history["hwmon0/temp1"], [45.0]);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn keeps_negative_lower_limits() {
+        let root = fake_root("negative");
+        write(&root, "hwmon0/name", "outdoor");
+        write(&root, "hwmon0/temp1_input", "-45000");
+        write(&root, "hwmon0/temp1_min", "-30000");
+        write(&root, "hwmon0/temp1_lcrit", "-40000");
+        write(&root, "hwmon0/temp2_input", "-35000");
+        write(&root, "hwmon0/temp2_min", "-30000");
+        write(&root, "hwmon0/temp2_lcrit", "-40000");
+
+        let mut monitor = SensorMonitor::new(root.clone());
+        monitor.refresh();
+
+        let sensors = &monitor.chips[0].sensors;
+        assert_eq!(sensors[0].value, -45.0);
+        assert_eq!(sensors[0].lcrit, Some(-40.0));
+        assert_eq!(sensors[0].level(), SensorLevel::Critical);
+        assert_eq!(sensors[1].min, Some(-30.0));
+        assert_eq!(sensors[1].level(), SensorLevel::Warning);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,358 @@
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    process::Command,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::sysfs::{read_key_values, read_number};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ServiceAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl ServiceAction {
+    pub fn name(&self) -> &str {
+        match self {
+            ServiceAction::Start => "start",
+            ServiceAction::Stop => "stop",
+            ServiceAction::Restart => "restart",
+        }
+    }
+}
+
+/// Something the UI asked for that has to run `systemctl`/`journalctl`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServiceRequest {
+    Action(ServiceAction, String),
+    Journal(String),
+}
+
+/// What came of a `ServiceRequest`, sent back from the worker thread.
+#[derive(Debug, Clone)]
+pub enum ServiceOutcome {
+    Action(ServiceAction, String, Result<(), String>),
+    Journal(String, Vec<String>),
+}
+
+/// Units with their `systemctl show` properties, or why they couldn't be
+/// listed.
+type Listing = Result<(Vec<UnitListEntry>, HashMap<String, HashMap<String, String>>), String>;
+
+/// What the worker threads send back.
+enum Finished {
+    Request(ServiceOutcome),
+    Listing(Listing),
+}
+
+/// One row of `systemctl list-units --output=json`.
+#[derive(Debug, Clone, Deserialize)]
+struct UnitListEntry {
+    unit: String,
+    load: String,
+    active: String,
+    sub: String,
+    #[serde(default)]
+    description: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Service {
+    pub unit: String,
+    pub description: String,
+    pub load: String,
+    pub active: String,
+    pub sub: String,
+    pub main_pid: Option<u32>,
+    pub control_group: Option<String>,
+    pub memory: Option<u64>,
+    pub cpu_percent: f64,
+}
+
+impl Service {
+    pub fn is_failed(&self) -> bool {
+        self.active == "failed"
+    }
+}
+
+/// Lists systemd services through `systemctl` and reads their resource usage
+/// from the cgroup systemd put them in.
+pub struct ServiceMonitor {
+    cgroup_root: PathBuf,
+    pub services: Vec<Service>,
+    /// Set when systemctl couldn't be run or talked to, e.g. no systemd as init
+    pub error: Option<String>,
+    previous_usage: HashMap<String, (u64, Instant)>,
+    last_refresh: Option<Instant>,
+    /// Everything that runs systemctl does so on a worker thread: a stuck
+    /// systemd or D-Bus mustn't freeze the UI
+    finished: (Sender<Finished>, Receiver<Finished>),
+    listing: bool,
+    /// Units changed while a listing was running, list again after it
+    relist: bool,
+}
+
+/// Listing every unit spawns systemctl twice, so don't do it every tick.
+const REFRESH_RATE: Duration = Duration::from_secs(2);
+
+/// Journal lines kept for the services tab.
+const JOURNAL_LINES: usize = 200;
+
+impl ServiceMonitor {
+    pub fn new(cgroup_root: PathBuf) -> Self {
+        Self {
+            cgroup_root,
+            services: Vec::new(),
+            error: None,
+            previous_usage: HashMap::new(),
+            last_refresh: None,
+            finished: mpsc::channel(),
+            listing: false,
+            relist: false,
+        }
+    }
+
+    /// Runs `request` on a worker thread, since starting or stopping a unit
+    /// takes as long as the unit does (up to systemd's timeout of 90s).
+    /// `poll_outcome` hands back the result.
+    pub fn start_request(&self, request: ServiceRequest) {
+        let sender = self.finished.0.clone();
+        thread::spawn(move || {
+            let outcome = match request {
+                ServiceRequest::Action(action, unit) => {
+                    let result = run_action(action, &unit);
+                    ServiceOutcome::Action(action, unit, result)
+                }
+                ServiceRequest::Journal(unit) => {
+                    let lines = journal(&unit, JOURNAL_LINES);
+                    ServiceOutcome::Journal(unit, lines)
+                }
+            };
+            let _ = sender.send(Finished::Request(outcome));
+        });
+    }
+
+    /// The next finished request, if any. Unit listings that have come in
+    /// meanwhile are taken in on the way.
+    pub fn poll_outcome(&mut self) -> Option<ServiceOutcome> {
+        while let Ok(finished) = self.finished.1.try_recv() {
+            match finished {
+                Finished::Request(outcome) => return Some(outcome),
+                Finished::Listing(listing) => {
+                    self.listing = false;
+                    self.update(listing);
+                    if self.relist {
+                        self.relist = false;
+                        self.force_refresh();
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    pub fn refresh(&mut self) {
+        if self.listing
+            || self
+                .last_refresh
+                .is_some_and(|last| last.elapsed() < REFRESH_RATE)
+        {
+            return;
+        }
+        self.force_refresh();
+    }
+
+    /// Lists the units again right away, e.g. after starting or stopping
+    /// one. The result is picked up by `poll_outcome`.
+    pub fn force_refresh(&mut self) {
+        self.last_refresh = Some(Instant::now());
+        // One listing at a time, however long the last one is taking
+        if self.listing {
+            self.relist = true;
+            return;
+        }
+        self.listing = true;
+
+        let sender = self.finished.0.clone();
+        thread::spawn(move || {
+            let listing = list_units().map(|units| {
+                let names: Vec<&str> = units.iter().map(|unit| unit.unit.as_str()).collect();
+                let properties = show_units(&names);
+                (units, properties)
+            });
+            let _ = sender.send(Finished::Listing(listing));
+        });
+    }
+
+    fn update(&mut self, listing: Listing) {
+        let (units, properties) = match listing {
+            Ok(listing) => listing,
+            Err(err) => {
+                self.error = Some(err);
+                self.services.clear();
+                return;
+            }
+        };
+        self.error = None;
+
+        let now = Instant::now();
+        let mut services: Vec<Service> = units
+            .into_iter()
+            .map(|unit| {
+                let props = properties.get(&unit.unit);
+                let control_group = props
+                    .and_then(|p| p.get("ControlGroup"))
+                    .filter(|cgroup| !cgroup.is_empty())
+                    .cloned();
+                let main_pid = props
+                    .and_then(|p| p.get("MainPID"))
+                    .and_then(|pid| pid.parse::<u32>().ok())
+                    .filter(|&pid| pid != 0);
+
+                let mut service = Service {
+                    unit: unit.unit,
+                    description: unit.description,
+                    load: unit.load,
+                    active: unit.active,
+                    sub: unit.sub,
+                    main_pid,
+                    control_group,
+                    memory: None,
+                    cpu_percent: 0.0,
+                };
+                self.read_cgroup(&mut service, now);
+                service
+            })
+            .collect();
+
+        // Failed units first so they can't be missed, then running ones
+        services.sort_by(|a, b| {
+            b.is_failed()
+                .cmp(&a.is_failed())
+                .then((b.active == "active").cmp(&(a.active == "active")))
+                .then(a.unit.cmp(&b.unit))
+        });
+
+        self.previous_usage
+            .retain(|unit, _| services.iter().any(|service| &service.unit == unit));
+        self.services = services;
+    }
+
+    fn read_cgroup(&mut self, service: &mut Service, now: Instant) {
+        let Some(cgroup) = &service.control_group else {
+            return;
+        };
+        let dir = self.cgroup_root.join(cgroup.trim_start_matches('/'));
+
+        service.memory = read_number(&dir.join("memory.current"));
+
+        let Some(&usage_usec) = read_key_values(&dir.join("cpu.stat")).get("usage_usec") else {
+            return;
+        };
+        if let Some(&(previous, at)) = self.previous_usage.get(&service.unit) {
+            let elapsed = now.duration_since(at).as_micros() as f64;
+            if elapsed > 0.0 {
+                service.cpu_percent = usage_usec.saturating_sub(previous) as f64 / elapsed * 100.0;
+            }
+        }
+        self.previous_usage
+            .insert(service.unit.clone(), (usage_usec, now));
+    }
+}
+
+fn list_units() -> Result<Vec<UnitListEntry>, String> {
+    let output = Command::new("systemctl")
+        .args([
+            "list-units",
+            "--type=service",
+            "--all",
+            "--output=json",
+            "--no-pager",
+        ])
+        .output()
+        .map_err(|err| format!("systemctl not available: {}", err))?;
+
+    if !output.status.success() {
+        return Err(first_line(&output.stderr));
+    }
+
+    serde_json::from_slice(&output.stdout)
+        .map_err(|err| format!("Unexpected systemctl output: {}", err))
+}
+
+/// Runs one `systemctl show` for all units and splits its `Key=Value` blocks
+/// by unit.
+fn show_units(units: &[&str]) -> HashMap<String, HashMap<String, String>> {
+    let mut properties = HashMap::new();
+    if units.is_empty() {
+        return properties;
+    }
+
+    let Ok(output) = Command::new("systemctl")
+        .args(["show", "--property=Id,MainPID,ControlGroup", "--no-pager"])
+        .args(units)
+        .output()
+    else {
+        return properties;
+    };
+
+    for block in String::from_utf8_lossy(&output.stdout).split("\n\n") {
+        let values: HashMap<String, String> = block
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        if let Some(id) = values.get("Id").cloned() {
+            properties.insert(id, values);
+        }
+    }
+
+    properties
+}
+
+/// Starts, stops or restarts a unit. Never prompts for a password: without
+/// the right privileges this fails and the error is passed back for display.
+fn run_action(action: ServiceAction, unit: &str) -> Result<(), String> {
+    let output = Command::new("systemctl")
+        .args(["--no-ask-password", action.name(), unit])
+        .output()
+        .map_err(|err| format!("Failed to run systemctl: {}", err))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(first_line(&output.stderr))
+    }
+}
+
+/// Most recent journal lines for a unit, oldest first.
+fn journal(unit: &str, lines: usize) -> Vec<String> {
+    let output = Command::new("journalctl")
+        .args(["--unit", unit, "--lines", &lines.to_string(), "--no-pager"])
+        .args(["--output", "short-iso"])
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect(),
+        Ok(output) => vec![first_line(&output.stderr)],
+        Err(err) => vec![format!("Failed to run journalctl: {}", err)],
+    }
+}
+
+fn first_line(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .lines()
+        .next()
+        .unwrap_or("Unknown error")
+        .to_string()
+}
@@ -0,0 +1,441 @@
+use std::{
+    collections::HashMap,
+    fs,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use crate::containers;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SocketUsage {
+    pub sockets: u32,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+/// Collects socket counts and TCP traffic per PID.
+///
+/// Only processes we are allowed to look into show up: without root that's
+/// basically our own user's processes.
+pub fn socket_usage() -> HashMap<u32, SocketUsage> {
+    let mut usage: HashMap<u32, SocketUsage> = HashMap::new();
+
+    for (pid, sockets) in socket_counts() {
+        usage.entry(pid).or_default().sockets = sockets;
+    }
+
+    for (pid, (sent, received)) in tcp_traffic() {
+        let entry = usage.entry(pid).or_default();
+        entry.bytes_sent += sent;
+        entry.bytes_received += received;
+    }
+
+    usage
+}
+
+/// Counts the `socket:[inode]` links in every readable `/proc/<pid>/fd`.
+fn socket_counts() -> HashMap<u32, u32> {
+    // Keep zero counts too, so "no sockets" can be told apart from "not
+    // allowed to look"
+    socket_inodes()
+        .into_iter()
+        .map(|(pid, inodes)| (pid, inodes.len() as u32))
+        .collect()
+}
+
+/// Inodes of the sockets each readable process has open.
+fn socket_inodes() -> HashMap<u32, Vec<u64>> {
+    let mut sockets = HashMap::new();
+
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return sockets;
+    };
+
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|n| n.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+
+        let inodes = fds
+            .flatten()
+            .filter_map(|fd| fs::read_link(fd.path()).ok())
+            .filter_map(|target| {
+                target
+                    .to_str()?
+                    .strip_prefix("socket:[")?
+                    .strip_suffix(']')?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+
+        sockets.insert(pid, inodes);
+    }
+
+    sockets
+}
+
+/// Sums the TCP byte counters `ss` reports for each process. There's no
+/// per-process network accounting in /proc, so this only covers TCP and is
+/// attributed to the first process holding the socket.
+fn tcp_traffic() -> HashMap<u32, (u64, u64)> {
+    let Ok(output) = Command::new("ss").args(["-tinpH"]).output() else {
+        return HashMap::new();
+    };
+
+    parse_ss_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_ss_output(output: &str) -> HashMap<u32, (u64, u64)> {
+    let mut traffic: HashMap<u32, (u64, u64)> = HashMap::new();
+    let mut current_pid = None;
+
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            // Socket line: ... users:(("name",pid=123,fd=4),("name",pid=124,fd=4))
+            current_pid = line
+                .split("pid=")
+                .nth(1)
+                .and_then(|rest| rest.split(|c: char| !c.is_ascii_digit()).next())
+                .and_then(|pid| pid.parse::<u32>().ok());
+            continue;
+        }
+
+        // Info line for the socket above it
+        let Some(pid) = current_pid else {
+            continue;
+        };
+
+        let mut sent = 0;
+        let mut received = 0;
+        for field in line.split_whitespace() {
+            if let Some(value) = field.strip_prefix("bytes_sent:") {
+                sent = value.parse().unwrap_or(0);
+            } else if let Some(value) = field.strip_prefix("bytes_received:") {
+                received = value.parse().unwrap_or(0);
+            }
+        }
+
+        let entry = traffic.entry(pid).or_default();
+        entry.0 += sent;
+        entry.1 += received;
+    }
+
+    traffic
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Protocol {
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+    Unix,
+}
+
+impl Protocol {
+    pub fn name(&self) -> &str {
+        match self {
+            Protocol::Tcp => "tcp",
+            Protocol::Tcp6 => "tcp6",
+            Protocol::Udp => "udp",
+            Protocol::Udp6 => "udp6",
+            Protocol::Unix => "unix",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Connection {
+    pub protocol: Protocol,
+    /// `address:port` for inet sockets, the socket path for unix ones
+    pub local: String,
+    pub remote: String,
+    pub state: String,
+    /// Whether this socket accepts connections (or is an unconnected UDP
+    /// socket waiting for datagrams)
+    pub listening: bool,
+    pub local_ip: Option<IpAddr>,
+    pub remote_ip: Option<IpAddr>,
+    /// Network interface the local address belongs to
+    pub interface: Option<String>,
+    pub inode: u64,
+    pub pid: Option<u32>,
+    pub process: Option<String>,
+}
+
+impl Connection {
+    /// An inet connection with a real peer outside this machine.
+    pub fn is_external(&self) -> bool {
+        self.remote_ip
+            .is_some_and(|ip| !ip.is_unspecified() && !ip.is_loopback())
+    }
+}
+
+/// Open sockets from `/proc/net`, refreshed every couple of seconds since
+/// finding their owners means walking every process's fd directory.
+pub struct ConnectionMonitor {
+    pub connections: Vec<Connection>,
+    last_refresh: Option<Instant>,
+}
+
+impl ConnectionMonitor {
+    pub fn new() -> Self {
+        Self {
+            connections: Vec::new(),
+            last_refresh: None,
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        if self
+            .last_refresh
+            .is_some_and(|last| last.elapsed() < Duration::from_secs(2))
+        {
+            return;
+        }
+        self.last_refresh = Some(Instant::now());
+
+        let owners: HashMap<u64, u32> = socket_inodes()
+            .into_iter()
+            .flat_map(|(pid, inodes)| inodes.into_iter().map(move |inode| (inode, pid)))
+            .collect();
+        let interfaces = interface_addresses();
+
+        let mut connections = Vec::new();
+        for (protocol, file) in [
+            (Protocol::Tcp, "tcp"),
+            (Protocol::Tcp6, "tcp6"),
+            (Protocol::Udp, "udp"),
+            (Protocol::Udp6, "udp6"),
+        ] {
+            let content = fs::read_to_string(format!("/proc/net/{}", file)).unwrap_or_default();
+            connections.extend(
+                content
+                    .lines()
+                    .skip(1)
+                    .filter_map(|line| parse_inet_line(line, protocol)),
+            );
+        }
+        let unix = fs::read_to_string("/proc/net/unix").unwrap_or_default();
+        connections.extend(unix.lines().skip(1).filter_map(parse_unix_line));
+
+        let mut names: HashMap<u32, String> = HashMap::new();
+        for connection in &mut connections {
+            connection.interface = connection
+                .local_ip
+                .and_then(|ip| interfaces.get(&ip).cloned());
+            connection.pid = owners.get(&connection.inode).copied();
+            connection.process = connection.pid.map(|pid| {
+                names
+                    .entry(pid)
+                    .or_insert_with(|| containers::process_name(pid))
+                    .clone()
+            });
+        }
+
+        connections.sort_by(|a, b| {
+            (a.protocol, !a.listening, &a.local).cmp(&(b.protocol, !b.listening, &b.local))
+        });
+        self.connections = connections;
+    }
+}
+
+/// Parses one line of `/proc/net/{tcp,tcp6,udp,udp6}`.
+fn parse_inet_line(line: &str, protocol: Protocol) -> Option<Connection> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 10 {
+        return None;
+    }
+
+    let (local_ip, local_port) = parse_address(fields[1])?;
+    let (remote_ip, remote_port) = parse_address(fields[2])?;
+    let state_code = u8::from_str_radix(fields[3], 16).ok()?;
+
+    let (state, listening) = match protocol {
+        Protocol::Tcp | Protocol::Tcp6 => (tcp_state(state_code), state_code == 0x0A),
+        // UDP only knows connected (1) and not (7)
+        _ if state_code == 0x01 => ("ESTABLISHED", false),
+        _ => ("UNCONN", true),
+    };
+
+    let remote = if remote_ip.is_unspecified() && remote_port == 0 {
+        "*".to_string()
+    } else {
+        format_socket_address(remote_ip, remote_port)
+    };
+
+    Some(Connection {
+        protocol,
+        local: format_socket_address(local_ip, local_port),
+        remote,
+        state: state.to_string(),
+        listening,
+        local_ip: Some(local_ip),
+        remote_ip: Some(remote_ip),
+        interface: None,
+        inode: fields[9].parse().ok()?,
+        pid: None,
+        process: None,
+    })
+}
+
+/// Parses one line of `/proc/net/unix`:
+/// `Num: RefCount Protocol Flags Type St Inode [Path]`.
+fn parse_unix_line(line: &str) -> Option<Connection> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 7 {
+        return None;
+    }
+
+    // __SO_ACCEPTCON is set on sockets that called listen()
+    let flags = u32::from_str_radix(fields[3], 16).ok()?;
+    let listening = flags & 0x0001_0000 != 0;
+    let kind = match fields[4] {
+        "0001" => "stream",
+        "0002" => "dgram",
+        "0005" => "seqpacket",
+        _ => "?",
+    };
+    let state = match (listening, fields[5]) {
+        (true, _) => "LISTEN",
+        (false, "03") => "CONNECTED",
+        (false, "01") => "UNCONN",
+        (false, "02") => "CONNECTING",
+        (false, "04") => "DISCONNECTING",
+        _ => "?",
+    };
+
+    Some(Connection {
+        protocol: Protocol::Unix,
+        local: fields.get(7).map_or("-", |path| path).to_string(),
+        remote: kind.to_string(),
+        state: state.to_string(),
+        listening,
+        local_ip: None,
+        remote_ip: None,
+        interface: None,
+        inode: fields[6].parse().ok()?,
+        pid: None,
+        process: None,
+    })
+}
+
+/// Decodes `0100007F:0035` (IPv4) or the 32 digit IPv6 form. The address
+/// is stored as 32-bit words in host byte order, the port in network order.
+fn parse_address(field: &str) -> Option<(IpAddr, u16)> {
+    let (address, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let words: Vec<u32> = (0..address.len() / 8)
+        .map(|i| u32::from_str_radix(&address[i * 8..i * 8 + 8], 16))
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_ne_bytes()).collect();
+
+    let ip = match bytes.len() {
+        4 => IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+        16 => {
+            let ip = Ipv6Addr::from(<[u8; 16]>::try_from(bytes).ok()?);
+            // Dual-stack sockets show IPv4 peers as ::ffff:a.b.c.d
+            ip.to_ipv4_mapped().map_or(IpAddr::V6(ip), IpAddr::V4)
+        }
+        _ => return None,
+    };
+
+    Some((ip, port))
+}
+
+fn format_socket_address(ip: IpAddr, port: u16) -> String {
+    let host = if ip.is_unspecified() {
+        "*".to_string()
+    } else {
+        ip.to_string()
+    };
+    let port = if port == 0 {
+        "*".to_string()
+    } else {
+        port.to_string()
+    };
+
+    match ip {
+        IpAddr::V6(_) if host != "*" => format!("[{}]:{}", host, port),
+        _ => format!("{}:{}", host, port),
+    }
+}
+
+fn tcp_state(code: u8) -> &'static str {
+    match code {
+        0x01 => "ESTABLISHED",
+        0x02 => "SYN_SENT",
+        0x03 => "SYN_RECV",
+        0x04 => "FIN_WAIT1",
+        0x05 => "FIN_WAIT2",
+        0x06 => "TIME_WAIT",
+        0x07 => "CLOSE",
+        0x08 => "CLOSE_WAIT",
+        0x09 => "LAST_ACK",
+        0x0A => "LISTEN",
+        0x0B => "CLOSING",
+        _ => "?",
+    }
+}
+
+/// Maps each local address to the interface it is configured on.
+fn interface_addresses() -> HashMap<IpAddr, String> {
+    let mut addresses = HashMap::new();
+    let mut list: *mut libc::ifaddrs = std::ptr::null_mut();
+
+    // SAFETY: getifaddrs fills `list` with a linked list we only read from
+    // and release with freeifaddrs below.
+    if unsafe { libc::getifaddrs(&mut list) } != 0 {
+        return addresses;
+    }
+
+    let mut current = list;
+    while !current.is_null() {
+        // SAFETY: `current` is a non-null node of the list from getifaddrs.
+        let entry = unsafe { &*current };
+        current = entry.ifa_next;
+
+        if entry.ifa_addr.is_null() || entry.ifa_name.is_null() {
+            continue;
+        }
+
+        // SAFETY: ifa_addr points to a sockaddr whose real type is given by
+        // its family, and ifa_name is a NUL-terminated string.
+        let (ip, name) = unsafe {
+            let ip = match (*entry.ifa_addr).sa_family as i32 {
+                libc::AF_INET => {
+                    let addr = &*(entry.ifa_addr as *const libc::sockaddr_in);
+                    IpAddr::V4(Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr)))
+                }
+                libc::AF_INET6 => {
+                    let addr = &*(entry.ifa_addr as *const libc::sockaddr_in6);
+                    IpAddr::V6(Ipv6Addr::from(addr.sin6_addr.s6_addr))
+                }
+                _ => continue,
+            };
+            let name = std::ffi::CStr::from_ptr(entry.ifa_name)
+                .to_string_lossy()
+                .to_string();
+            (ip, name)
+        };
+
+        addresses.insert(ip, name);
+    }
+
+    // SAFETY: `list` came from a successful getifaddrs call.
+    unsafe { libc::freeifaddrs(list) };
+
+    addresses
+}
@@ -0,0 +1,502 @@
+use anyhow::{Context, Result, bail};
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, RandomState},
+    io::{self, BufRead, BufReader, Read, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    agent::{HostSnapshot, RemoteProcess, SNAPSHOT_INTERVAL, VpnSummary},
+    vpn::{VPN_INTERFACES, VpnProvider},
+};
+
+/// Runs on the remote host under plain `sh`: prints a few facts that don't
+/// change, then dumps the parts of `/proc` a snapshot needs every time a
+/// line arrives on stdin. Kept free of single quotes so it can be wrapped
+/// in `sh -c '...'` whatever the remote login shell is. Every `@` starts a
+/// section marker, and gets a random tag per session appended (see
+/// `SshCollector::open`).
+const COLLECTOR: &str = r#"
+cat /proc/sys/kernel/hostname
+getconf PAGESIZE
+getconf CLK_TCK
+(. /etc/os-release 2>/dev/null; echo "${PRETTY_NAME:-$(uname -sr)}")
+while read -r _; do
+  echo @uptime; cat /proc/uptime
+  echo @loadavg; cat /proc/loadavg
+  echo @stat; grep "^cpu" /proc/stat
+  echo @meminfo; cat /proc/meminfo
+  echo @processes; cat /proc/[0-9]*/stat 2>/dev/null
+  echo @interfaces; ls /sys/class/net
+  echo @end
+done
+"#;
+
+const TOP_PROCESSES: usize = 5;
+/// Longest line the collector prints, well past any `/proc` line.
+const MAX_LINE: u64 = 64 * 1024;
+
+/// The ssh destination for `ssh:[user@]host` and `ssh://[user@]host[:port]`
+/// addresses, None for anything else.
+pub fn destination(address: &str) -> Option<&str> {
+    if address.starts_with("ssh://") {
+        Some(address)
+    } else {
+        address.strip_prefix("ssh:")
+    }
+}
+
+/// A host read over an `ssh` session, for machines that can't run an agent.
+/// Authentication is left to ssh itself: keys, the agent and `~/.ssh/config`
+/// all apply, but there's nobody to type a password.
+pub struct SshCollector {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    /// Starts every section marker. Process names are at most 15 bytes, so
+    /// one can't contain the random part and fake a marker.
+    marker: String,
+    pub hostname: String,
+    os: String,
+    page_size: u64,
+    ticks_per_second: f64,
+    previous: Sample,
+    polled: Instant,
+    /// How long the last poll took to come back
+    pub latency: Duration,
+}
+
+/// One dump of the remote `/proc`.
+#[derive(Default)]
+struct Sample {
+    uptime: f64,
+    load_average: [f64; 3],
+    cpu_count: usize,
+    /// Jiffies spent in total and idle, summed over every cpu
+    cpu_total: u64,
+    cpu_idle: u64,
+    memory_total: u64,
+    memory_used: u64,
+    swap_total: u64,
+    swap_used: u64,
+    processes: HashMap<u32, ProcessSample>,
+    interfaces: Vec<String>,
+}
+
+struct ProcessSample {
+    name: String,
+    /// User plus system time, in clock ticks
+    ticks: u64,
+    /// Resident pages
+    resident: u64,
+}
+
+impl SshCollector {
+    /// Starts the collector on `destination` and takes a first sample, so
+    /// the first snapshot already has something to compare against.
+    pub fn open(destination: &str, timeout: Duration) -> Result<Self> {
+        let marker = format!("@{:016x}:", RandomState::new().hash_one(destination));
+        let collector = COLLECTOR.replace('@', &marker);
+        let mut child = Command::new("ssh")
+            .args([
+                "-T",
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                &format!("ConnectTimeout={}", timeout.as_secs().max(1)),
+                // Lets ssh notice a dead link and hang up instead of leaving
+                // us waiting on a read forever
+                "-o",
+                "ServerAliveInterval=2",
+                "-o",
+                "ServerAliveCountMax=3",
+                "--",
+                destination,
+                &format!("sh -c '{}'", collector),
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("cannot run ssh")?;
+        let stdin = child.stdin.take().context("ssh has no stdin")?;
+        let stdout = BufReader::new(child.stdout.take().context("ssh has no stdout")?);
+
+        let mut collector = Self {
+            child,
+            stdin,
+            stdout,
+            marker,
+            hostname: String::new(),
+            os: String::new(),
+            page_size: 4096,
+            ticks_per_second: 100.0,
+            previous: Sample::default(),
+            polled: Instant::now(),
+            latency: Duration::ZERO,
+        };
+        collector.hostname = collector.read_line()?;
+        collector.page_size = collector.read_line()?.parse().unwrap_or(4096);
+        collector.ticks_per_second = collector.read_line()?.parse().unwrap_or(100.0);
+        collector.os = collector.read_line()?;
+        collector.previous = collector.poll()?;
+        Ok(collector)
+    }
+
+    /// Waits out the rest of the interval, then samples again and reports
+    /// the difference from the previous sample.
+    pub fn next_snapshot(&mut self) -> Result<HostSnapshot> {
+        thread::sleep(SNAPSHOT_INTERVAL.saturating_sub(self.polled.elapsed()));
+        let sample = self.poll()?;
+        let snapshot = self.snapshot(&sample);
+        self.previous = sample;
+        Ok(snapshot)
+    }
+
+    /// Asks for one dump and parses it.
+    fn poll(&mut self) -> Result<Sample> {
+        self.polled = Instant::now();
+        if self
+            .stdin
+            .write_all(b"\n")
+            .and_then(|_| self.stdin.flush())
+            .is_err()
+        {
+            return Err(self.failure());
+        }
+
+        let sample = match read_sample(&mut self.stdout, &self.marker) {
+            Ok(Some(sample)) => sample,
+            Ok(None) => return Err(self.failure()),
+            Err(err) => return Err(anyhow::Error::new(err).context("cannot read from ssh")),
+        };
+        self.latency = self.polled.elapsed();
+
+        if sample.cpu_total == 0 || sample.memory_total == 0 {
+            bail!(
+                "{} doesn't look like Linux, no /proc to read",
+                self.hostname
+            );
+        }
+        Ok(sample)
+    }
+
+    fn snapshot(&self, sample: &Sample) -> HostSnapshot {
+        let previous = &self.previous;
+        let total = sample.cpu_total.saturating_sub(previous.cpu_total);
+        let idle = sample.cpu_idle.saturating_sub(previous.cpu_idle);
+        let cpu_usage = if total > 0 {
+            total.saturating_sub(idle) as f32 / total as f32 * 100.0
+        } else {
+            0.0
+        };
+
+        // Same scale as sysinfo: percent of one cpu
+        let elapsed = (sample.uptime - previous.uptime).max(0.01) * self.ticks_per_second;
+        let mut processes: Vec<RemoteProcess> = sample
+            .processes
+            .iter()
+            .map(|(pid, process)| {
+                // A pid that wasn't there last time started since, so all of
+                // its time falls inside the interval
+                let before = previous.processes.get(pid).map_or(0, |old| old.ticks);
+                RemoteProcess {
+                    name: process.name.clone(),
+                    cpu_usage: (process.ticks.saturating_sub(before) as f64 / elapsed * 100.0)
+                        as f32,
+                    memory: process.resident * self.page_size,
+                }
+            })
+            .collect();
+        processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+        processes.truncate(TOP_PROCESSES);
+
+        let vpn = VPN_INTERFACES
+            .iter()
+            .find(|(name, _)| sample.interfaces.iter().any(|iface| iface == name))
+            .map(|(_, provider)| VpnSummary {
+                connected: true,
+                provider: (*provider != VpnProvider::Unknown).then(|| provider.name().to_string()),
+                ..VpnSummary::default()
+            })
+            .unwrap_or_default();
+
+        HostSnapshot {
+            hostname: self.hostname.clone(),
+            os: self.os.clone(),
+            uptime: sample.uptime as u64,
+            cpu_usage,
+            cpu_count: sample.cpu_count,
+            load_average: sample.load_average,
+            memory_used: sample.memory_used,
+            memory_total: sample.memory_total,
+            swap_used: sample.swap_used,
+            swap_total: sample.swap_total,
+            process_count: sample.processes.len(),
+            top_processes: processes,
+            vpn,
+        }
+    }
+
+    fn read_line(&mut self) -> Result<String> {
+        match next_line(&mut self.stdout) {
+            Ok(Some(line)) => Ok(line),
+            Ok(None) => Err(self.failure()),
+            Err(err) => Err(anyhow::Error::new(err).context("cannot read from ssh")),
+        }
+    }
+
+    /// Why the session ended, in ssh's own words where it left some.
+    fn failure(&mut self) -> anyhow::Error {
+        let _ = self.child.kill();
+        let status = self.child.wait();
+        let mut stderr = String::new();
+        if let Some(mut pipe) = self.child.stderr.take() {
+            let _ = pipe.read_to_string(&mut stderr);
+        }
+        match (
+            stderr.lines().rev().find(|line| !line.trim().is_empty()),
+            status,
+        ) {
+            (Some(line), _) => anyhow::anyhow!("ssh: {}", line.trim()),
+            (None, Ok(status)) => anyhow::anyhow!("ssh exited ({})", status),
+            (None, Err(err)) => err.into(),
+        }
+    }
+}
+
+impl Drop for SshCollector {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// One line of output, None once it has ended. Process names are whatever
+/// bytes the process chose, so anything that isn't UTF-8 is replaced rather
+/// than refused.
+fn next_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = Vec::new();
+    if reader.take(MAX_LINE).read_until(b'\n', &mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&line).trim_end().to_string()))
+}
+
+/// One dump of the collector, up to its end marker. None if the output
+/// ends before that.
+fn read_sample(reader: &mut impl BufRead, marker: &str) -> io::Result<Option<Sample>> {
+    let mut sample = Sample::default();
+    let mut section = String::new();
+    while let Some(line) = next_line(reader)? {
+        if let Some(name) = line.strip_prefix(marker) {
+            if name == "end" {
+                return Ok(Some(sample));
+            }
+            section = name.to_string();
+            continue;
+        }
+        parse_line(&mut sample, &section, &line);
+    }
+    Ok(None)
+}
+
+fn parse_line(sample: &mut Sample, section: &str, line: &str) {
+    let mut fields = line.split_whitespace();
+    match section {
+        "uptime" => {
+            sample.uptime = fields.next().and_then(|f| f.parse().ok()).unwrap_or(0.0);
+        }
+        "loadavg" => {
+            for load in &mut sample.load_average {
+                *load = fields.next().and_then(|f| f.parse().ok()).unwrap_or(0.0);
+            }
+        }
+        "stat" => match fields.next() {
+            // The first 8 columns: user nice system idle iowait irq softirq
+            // steal. Guest time is already counted in user.
+            Some("cpu") => {
+                let jiffies: Vec<u64> = fields.take(8).filter_map(|f| f.parse().ok()).collect();
+                sample.cpu_total = jiffies.iter().sum();
+                sample.cpu_idle = jiffies.iter().skip(3).take(2).sum();
+            }
+            Some(_) => sample.cpu_count += 1,
+            None => {}
+        },
+        "meminfo" => {
+            let (Some(key), Some(kib)) = (fields.next(), fields.next()) else {
+                return;
+            };
+            let bytes = kib.parse::<u64>().unwrap_or(0) * 1024;
+            match key {
+                "MemTotal:" => sample.memory_total = bytes,
+                // Filled in as total minus available, like sysinfo does
+                "MemAvailable:" => sample.memory_used = sample.memory_total.saturating_sub(bytes),
+                "SwapTotal:" => sample.swap_total = bytes,
+                "SwapFree:" => sample.swap_used = sample.swap_total.saturating_sub(bytes),
+                _ => {}
+            }
+        }
+        "processes" => {
+            if let Some((pid, process)) = parse_process(line) {
+                sample.processes.insert(pid, process);
+            }
+        }
+        "interfaces" => sample.interfaces.extend(fields.map(str::to_string)),
+        _ => {}
+    }
+}
+
+/// One `/proc/<pid>/stat` line. The name sits in parentheses and may hold
+/// spaces or parentheses itself, so the fields after it are found from the
+/// last `)`.
+fn parse_process(line: &str) -> Option<(u32, ProcessSample)> {
+    let open = line.find('(')?;
+    let close = line.rfind(')')?;
+    let pid = line[..open].trim().parse().ok()?;
+    let name = line.get(open + 1..close)?.to_string();
+    // Fields from the state on: utime, stime and rss are fields 14, 15 and
+    // 24 of the whole line
+    let fields: Vec<&str> = line[close + 1..].split_whitespace().collect();
+    let field = |index: usize| fields.get(index - 3)?.parse::<u64>().ok();
+    Some((
+        pid,
+        ProcessSample {
+            name,
+            ticks: field(14)? + field(15)?,
+            resident: field(24)?,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKER: &str = "@0123456789abcdef:";
+
+    /// A `/proc/<pid>/stat` line with the given name, times and resident pages.
+    fn stat(pid: u32, name: &[u8], utime: u64, stime: u64, rss: u64) -> Vec<u8> {
+        let mut line = format!("{} (", pid).into_bytes();
+        line.extend_from_slice(name);
+        line.extend_from_slice(
+            format!(
+                ") S 1 1 1 0 -1 4194560 100 0 0 0 {} {} 0 0 20 0 1 0 100 1000000 {} 18446744073709551615\n",
+                utime, stime, rss
+            )
+            .as_bytes(),
+        );
+        line
+    }
+
+    /// Collector output for one poll, `processes` going in the processes
+    /// section.
+    fn dump(processes: &[Vec<u8>]) -> Vec<u8> {
+        let section = |name: &str, lines: &str| format!("{}{}\n{}", MARKER, name, lines);
+        let mut output = Vec::new();
+        output.extend(section("uptime", "1000.50 3000.00\n").bytes());
+        output.extend(section("loadavg", "0.50 0.25 0.10 1/200 1234\n").bytes());
+        output.extend(
+            section(
+                "stat",
+                "cpu  100 10 50 800 40 5 5 0 20 0\n\
+                 cpu0 50 5 25 400 20 3 2 0 10 0\n\
+                 cpu1 50 5 25 400 20 2 3 0 10 0\n",
+            )
+            .bytes(),
+        );
+        output.extend(
+            section(
+                "meminfo",
+                "MemTotal:        8000 kB\n\
+                 MemFree:         1000 kB\n\
+                 MemAvailable:    6000 kB\n\
+                 SwapTotal:       2000 kB\n\
+                 SwapFree:         500 kB\n",
+            )
+            .bytes(),
+        );
+        output.extend(section("processes", "").bytes());
+        for process in processes {
+            output.extend(process);
+        }
+        output.extend(section("interfaces", "lo\neth0\nwg0\n").bytes());
+        output.extend(section("end", "").bytes());
+        output
+    }
+
+    #[test]
+    fn parses_cpu_and_memory_figures() {
+        let output = dump(&[stat(1, b"init", 7, 3, 100)]);
+        let sample = read_sample(&mut output.as_slice(), MARKER)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(sample.uptime, 1000.5);
+        assert_eq!(sample.load_average, [0.5, 0.25, 0.1]);
+        assert_eq!(sample.cpu_count, 2);
+        // user + nice + system + idle + iowait + irq + softirq + steal
+        assert_eq!(sample.cpu_total, 1010);
+        // idle + iowait
+        assert_eq!(sample.cpu_idle, 840);
+        assert_eq!(sample.memory_total, 8000 * 1024);
+        assert_eq!(sample.memory_used, 2000 * 1024);
+        assert_eq!(sample.swap_total, 2000 * 1024);
+        assert_eq!(sample.swap_used, 1500 * 1024);
+        assert_eq!(sample.interfaces, ["lo", "eth0", "wg0"]);
+
+        let init = &sample.processes[&1];
+        assert_eq!(init.name, "init");
+        assert_eq!(init.ticks, 10);
+        assert_eq!(init.resident, 100);
+    }
+
+    #[test]
+    fn parses_awkward_process_names() {
+        let output = dump(&[
+            stat(10, b"a) b (c", 1, 2, 3),
+            stat(11, b"caf\xe9", 4, 5, 6),
+            stat(12, b"@end", 7, 8, 9),
+            // A name with newlines in it splits into lines of its own, one
+            // of them an untagged marker
+            stat(13, b"x\n@end\ny", 1, 1, 1),
+            stat(14, b"last", 10, 20, 30),
+        ]);
+        let sample = read_sample(&mut output.as_slice(), MARKER)
+            .unwrap()
+            .unwrap();
+
+        let parens = &sample.processes[&10];
+        assert_eq!(parens.name, "a) b (c");
+        assert_eq!((parens.ticks, parens.resident), (3, 3));
+        assert_eq!(sample.processes[&11].name, "caf\u{fffd}");
+        assert_eq!(sample.processes[&12].name, "@end");
+        assert!(!sample.processes.contains_key(&13));
+        // The dump carried on past the fake markers
+        assert_eq!(sample.processes[&14].ticks, 30);
+        assert_eq!(sample.interfaces.len(), 3);
+    }
+
+    #[test]
+    fn stops_at_the_end_marker() {
+        let mut output = dump(&[]);
+        output.extend(b"left for the next poll\n");
+        let mut reader = output.as_slice();
+        assert!(read_sample(&mut reader, MARKER).unwrap().is_some());
+        assert_eq!(reader, b"left for the next poll\n");
+
+        // Output that ends halfway through a dump
+        let truncated = &output[..output.len() / 2];
+        assert!(read_sample(&mut &truncated[..], MARKER).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_process_lines() {
+        assert!(parse_process("").is_none());
+        assert!(parse_process("12 (name) S 1").is_none());
+        assert!(parse_process("x (name) S 1 1 1 0 -1 0 0 0 0 0 1 1").is_none());
+    }
+}
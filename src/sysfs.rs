@@ -0,0 +1,59 @@
+//! Readers for the one-value-per-file layout of sysfs, cgroupfs and procfs.
+
+use std::{collections::HashMap, fs, path::Path};
+
+pub fn read_number(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Readings can be negative (temperatures, some currents).
+pub fn read_signed(path: &Path) -> Option<i64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// The trimmed contents, or None for a missing or blank file.
+pub fn read_string(path: &Path) -> Option<String> {
+    let value = fs::read_to_string(path).ok()?.trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+/// `key value` lines, as in `cpu.stat` or `/proc/vmstat`.
+pub fn read_key_values(path: &Path) -> HashMap<String, u64> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            Some((key.to_string(), value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// Helpers for tests that lay out a fake sysfs tree.
+#[cfg(test)]
+pub mod testing {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    /// An empty directory for `module`'s `test` to write files into.
+    pub fn fake_root(module: &str, test: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "myworkspace-{}-{}-{}",
+            module,
+            test,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    /// Writes `contents` plus the trailing newline the kernel adds.
+    pub fn write(root: &Path, file: &str, contents: &str) {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, format!("{}\n", contents)).unwrap();
+    }
+}
@@ -1,33 +1,273 @@
 use std::collections::HashMap;
-use sysinfo::{Components, Disks, Networks, System};
+use std::fs;
+use std::time::{Duration, Instant};
+use sysinfo::{
+    Components, Disks, Groups, Networks, Pid, Process, ProcessRefreshKind, ProcessesToUpdate,
+    System, ThreadKind, UpdateKind, Users,
+};
+
+use crate::{
+    config::{Config, OverviewConfig, Panel},
+    containers::{self, ContainerMonitor},
+    disk_trend::DiskTrend,
+    diskio::{self, DiskIoMonitor},
+    memory::MemoryMonitor,
+    power::PowerMonitor,
+    process_view::ProcessView,
+    scheduler::SchedulerStats,
+    sensors::SensorMonitor,
+    services::ServiceMonitor,
+    sockets::{self, ConnectionMonitor, SocketUsage},
+};
 
 pub struct SystemInfo {
     pub system: System,
     pub components: Components,
     pub disks: Disks,
+    /// `disks` with their mount options, inode counts and block devices,
+    /// rebuilt whenever `disks` is refreshed. Those lookups can block on a
+    /// hung network mount, so drawing a frame mustn't make them.
+    disk_info: Vec<DiskInfo>,
     pub networks: Networks,
+    pub users: Users,
+    pub groups: Groups,
+    /// Socket usage per PID, only collected while `track_process_network` is set
+    pub process_network: HashMap<u32, ProcessNetwork>,
+    pub track_process_network: bool,
+    pub containers: ContainerMonitor,
+    pub services: ServiceMonitor,
+    pub disk_io: DiskIoMonitor,
+    pub disk_trend: DiskTrend,
+    pub power: PowerMonitor,
+    pub sensors: SensorMonitor,
+    pub memory: MemoryMonitor,
+    pub scheduler: SchedulerStats,
+    pub connections: ConnectionMonitor,
+    /// Snapshot of every process for the Processes tab, rebuilt by
+    /// `refresh_processes`
+    pub process_view: ProcessView,
+    /// The process open in the detail pane, rebuilt with the process table
+    /// so drawing a frame never reads `/proc`. None once it has exited.
+    pub process_detail: Option<ProcessDetail>,
+    detail_pid: Option<u32>,
+    /// What the Overview's process panels show, rebuilt by `refresh_overview`
+    pub overview_usage: OverviewUsage,
+    last_process_refresh: Instant,
+    process_interval: Duration,
+    last_network_refresh: Instant,
+    network_interval: Duration,
 }
 
 impl SystemInfo {
-    pub fn new() -> Self {
+    pub fn new(config: &Config) -> Self {
         let mut system = System::new_all();
         system.refresh_all();
+        let cgroup_root = containers::unified_root(&config.containers.cgroup_root);
 
-        Self {
+        let mut system_info = Self {
             system,
             components: Components::new_with_refreshed_list(),
             disks: Disks::new_with_refreshed_list(),
+            disk_info: Vec::new(),
             networks: Networks::new_with_refreshed_list(),
+            users: Users::new_with_refreshed_list(),
+            groups: Groups::new_with_refreshed_list(),
+            process_network: HashMap::new(),
+            track_process_network: false,
+            containers: ContainerMonitor::new(cgroup_root.clone()),
+            services: ServiceMonitor::new(cgroup_root),
+            disk_io: DiskIoMonitor::new(),
+            disk_trend: DiskTrend::new(),
+            power: PowerMonitor::new(config.power.sysfs_root.clone()),
+            sensors: SensorMonitor::new(config.sensors.hwmon_root.clone()),
+            memory: MemoryMonitor::new(),
+            scheduler: SchedulerStats::new(),
+            connections: ConnectionMonitor::new(),
+            process_view: ProcessView::new(),
+            process_detail: None,
+            detail_pid: None,
+            overview_usage: OverviewUsage::default(),
+            last_process_refresh: Instant::now(),
+            process_interval: Duration::from_secs(1),
+            last_network_refresh: Instant::now(),
+            network_interval: Duration::from_secs(1),
+        };
+        system_info.rebuild_process_view();
+        system_info.update_disk_info();
+        system_info
+    }
+
+    /// Refreshes only the process data the Processes tab shows, leaving
+    /// disks, networks and sensors alone, then rebuilds its snapshot.
+    /// `detail` is the process whose detail view is open, which also needs
+    /// its executable, working directory and environment.
+    pub fn refresh_processes(&mut self, detail: Option<u32>) {
+        self.refresh_minimal();
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_cpu()
+                .with_memory()
+                .with_disk_usage()
+                .with_user(UpdateKind::OnlyIfNotSet)
+                .with_cmd(UpdateKind::OnlyIfNotSet),
+        );
+        self.update_process_rates();
+        self.rebuild_process_view();
+        self.refresh_process_detail(detail);
+    }
+
+    /// Which process `process_detail` was last built for.
+    pub fn detail_pid(&self) -> Option<u32> {
+        self.detail_pid
+    }
+
+    /// Rereads everything about `pid` for the detail pane, on its own so
+    /// the pane doesn't wait for the next full refresh when it opens.
+    pub fn refresh_process_detail(&mut self, pid: Option<u32>) {
+        self.detail_pid = pid;
+        self.process_detail = pid.and_then(|pid| {
+            self.system.refresh_processes_specifics(
+                ProcessesToUpdate::Some(&[Pid::from_u32(pid)]),
+                false,
+                ProcessRefreshKind::everything(),
+            );
+            self.read_process_detail(pid)
+        });
+    }
+
+    fn rebuild_process_view(&mut self) {
+        let mut processes = self.process_view.take_buffer();
+        processes.extend(
+            self.system
+                .processes()
+                .iter()
+                .map(|(pid, process)| self.process_info(*pid, process)),
+        );
+        self.process_view.set_processes(processes);
+    }
+
+    /// Refreshes what the panels on the Overview dashboard need, so panels
+    /// that aren't shown cost nothing.
+    pub fn refresh_overview(&mut self, overview: &OverviewConfig) {
+        self.refresh_light();
+        if overview.contains(Panel::TopProcesses) || overview.contains(Panel::Categories) {
+            self.refresh_process_usage();
+            self.update_overview_usage();
+        }
+        if overview.contains(Panel::Network) {
+            self.refresh_networks();
         }
     }
 
-    pub fn refresh_full(&mut self) {
-        self.system.refresh_all();
-        self.components.refresh(true);
-        self.disks.refresh(true);
+    /// Sorts and totals the processes once per refresh rather than on
+    /// every frame.
+    fn update_overview_usage(&mut self) {
+        let processes = self.process_usage();
+        let categories = self.category_usage(&processes);
+
+        let mut by_cpu = processes.clone();
+        by_cpu.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+        by_cpu.truncate(OVERVIEW_TOP_PROCESSES);
+        let mut by_memory = processes;
+        by_memory.sort_by_key(|process| std::cmp::Reverse(process.memory));
+        by_memory.truncate(OVERVIEW_TOP_PROCESSES);
+
+        self.overview_usage = OverviewUsage {
+            by_cpu,
+            by_memory,
+            categories,
+        };
+    }
+
+    /// Refreshes what a fleet snapshot of this machine is built from.
+    pub fn refresh_snapshot(&mut self) {
+        self.refresh_minimal();
+        self.refresh_process_usage();
+    }
+
+    /// Only the CPU and memory of each process, command lines, users and
+    /// disk counters are left to the Processes tab.
+    fn refresh_process_usage(&mut self) {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+        self.update_process_rates();
+    }
+
+    fn refresh_networks(&mut self) {
+        let now = Instant::now();
+        self.network_interval = now - self.last_network_refresh;
+        self.last_network_refresh = now;
         self.networks.refresh(true);
     }
 
+    /// Receive and transmit rates per interface in bytes per second, without
+    /// loopback.
+    pub fn network_rates(&self) -> Vec<(String, u64, u64)> {
+        let seconds = self.network_interval.as_secs_f64().max(0.001);
+        let mut rates: Vec<(String, u64, u64)> = self
+            .networks
+            .iter()
+            .filter(|(name, _)| name.as_str() != "lo")
+            .map(|(name, data)| {
+                (
+                    name.clone(),
+                    (data.received() as f64 / seconds) as u64,
+                    (data.transmitted() as f64 / seconds) as u64,
+                )
+            })
+            .collect();
+        rates.sort_by(|a, b| a.0.cmp(&b.0));
+        rates
+    }
+
+    /// Remembers how long it's been since the last process refresh so the
+    /// per-refresh deltas can be turned into rates, and samples socket usage.
+    fn update_process_rates(&mut self) {
+        let now = Instant::now();
+        self.process_interval = now - self.last_process_refresh;
+        self.last_process_refresh = now;
+
+        if !self.track_process_network {
+            self.process_network.clear();
+            return;
+        }
+
+        let seconds = self.process_interval.as_secs_f64().max(0.001);
+        let previous = std::mem::take(&mut self.process_network);
+
+        self.process_network = sockets::socket_usage()
+            .into_iter()
+            .map(|(pid, usage)| {
+                let (sent_rate, received_rate) = match previous.get(&pid) {
+                    Some(prev) => (
+                        (usage.bytes_sent.saturating_sub(prev.usage.bytes_sent) as f64 / seconds)
+                            as u64,
+                        (usage
+                            .bytes_received
+                            .saturating_sub(prev.usage.bytes_received)
+                            as f64
+                            / seconds) as u64,
+                    ),
+                    None => (0, 0),
+                };
+
+                (
+                    pid,
+                    ProcessNetwork {
+                        usage,
+                        sent_rate,
+                        received_rate,
+                    },
+                )
+            })
+            .collect();
+    }
+
     pub fn refresh_light(&mut self) {
         use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind};
         self.system.refresh_specifics(
@@ -46,6 +286,8 @@ impl SystemInfo {
                 .with_memory(MemoryRefreshKind::everything()),
         );
         self.disks.refresh(true);
+        self.update_disk_info();
+        self.disk_io.refresh();
     }
 
     pub fn refresh_minimal(&mut self) {
@@ -57,6 +299,41 @@ impl SystemInfo {
         );
     }
 
+    /// Samples free space for the fill-rate estimates. Runs on every tab so
+    /// the trend is already known when the System Info tab is opened.
+    pub fn sample_disk_space(&mut self) {
+        if !self.disk_trend.is_due() {
+            return;
+        }
+
+        self.disks.refresh(true);
+        self.update_disk_info();
+        self.disk_trend.record(
+            self.disks
+                .iter()
+                .filter_map(|disk| Some((disk.mount_point().to_str()?, disk.available_space()))),
+        );
+    }
+
+    pub fn refresh_containers(&mut self) {
+        self.refresh_minimal();
+        self.containers.refresh();
+    }
+
+    pub fn refresh_services(&mut self) {
+        self.refresh_minimal();
+        self.services.refresh();
+    }
+
+    pub fn refresh_connections(&mut self) {
+        self.refresh_minimal();
+        self.connections.refresh();
+    }
+
+    pub fn refresh_sensors(&mut self) {
+        self.refresh_minimal();
+        self.sensors.refresh();
+    }
 
     pub fn get_cpu_usage(&self) -> f32 {
         self.system.global_cpu_usage()
@@ -117,36 +394,192 @@ impl SystemInfo {
         info
     }
 
-    pub fn get_processes(&self) -> Vec<ProcessInfo> {
+    /// CPU and memory of every process, without the extra `/proc` reads
+    /// and user lookups the Processes tab needs. Userland threads are left
+    /// out since their process already counts their CPU time and shares
+    /// their memory.
+    pub fn process_usage(&self) -> Vec<ProcessUsage> {
         self.system
             .processes()
-            .iter()
-            .map(|(pid, process)| {
+            .values()
+            .filter(|process| process.thread_kind() != Some(ThreadKind::Userland))
+            .map(|process| {
                 let name = process.name().to_string_lossy().to_string();
-                let category = categorize_process(&name);
-
-                ProcessInfo {
-                    pid: pid.as_u32(),
+                ProcessUsage {
+                    category: categorize_process(&name),
                     name,
                     cpu_usage: process.cpu_usage(),
                     memory: process.memory(),
-                    category,
                 }
             })
             .collect()
     }
 
-    pub fn get_disk_info(&self) -> Vec<DiskInfo> {
-        self.disks
+    /// Total CPU and memory of each category in `ProcessCategory::ALL`
+    /// order. CPU is a share of all cores, so the categories add up to at
+    /// most 100%.
+    fn category_usage(&self, processes: &[ProcessUsage]) -> Vec<(ProcessCategory, f64, u64)> {
+        let cores = self.system.cpus().len().max(1) as f64;
+        ProcessCategory::ALL
             .iter()
-            .map(|disk| DiskInfo {
-                mount_point: disk.mount_point().to_string_lossy().to_string(),
-                total_space: disk.total_space(),
-                available_space: disk.available_space(),
+            .map(|&category| {
+                let (cpu, memory) = processes
+                    .iter()
+                    .filter(|process| process.category == category)
+                    .fold((0.0, 0), |(cpu, memory), process| {
+                        (cpu + process.cpu_usage as f64, memory + process.memory)
+                    });
+                (category, cpu / cores, memory)
             })
             .collect()
     }
 
+    fn process_info(&self, pid: Pid, process: &Process) -> ProcessInfo {
+        let name = process.name().to_string_lossy().to_string();
+        let category = categorize_process(&name);
+        let sched = read_sched_stat(pid.as_u32());
+        let disk_usage = process.disk_usage();
+        let seconds = self.process_interval.as_secs_f64().max(0.001);
+
+        let user = process
+            .user_id()
+            .and_then(|uid| self.users.get_user_by_id(uid))
+            .map(|user| user.name().to_string())
+            .unwrap_or_default();
+
+        let command = process
+            .cmd()
+            .iter()
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        ProcessInfo {
+            pid: pid.as_u32(),
+            // Kernel threads have no command line, show them like ps does
+            command: if command.is_empty() {
+                format!("[{}]", name)
+            } else {
+                command
+            },
+            name,
+            cpu_usage: process.cpu_usage(),
+            memory: process.memory(),
+            virtual_memory: process.virtual_memory(),
+            user,
+            state: process.status().to_string(),
+            threads: sched.threads,
+            start_time: process.start_time(),
+            run_time: process.run_time(),
+            disk_read: disk_usage.total_read_bytes,
+            disk_written: disk_usage.total_written_bytes,
+            disk_read_rate: (disk_usage.read_bytes as f64 / seconds) as u64,
+            disk_write_rate: (disk_usage.written_bytes as f64 / seconds) as u64,
+            network: self.process_network.get(&pid.as_u32()).copied(),
+            nice: sched.nice,
+            priority: sched.priority,
+            category,
+        }
+    }
+
+    /// Everything we know about a single process, for the detail pane.
+    fn read_process_detail(&self, pid: u32) -> Option<ProcessDetail> {
+        let sys_pid = Pid::from_u32(pid);
+        let process = self.system.process(sys_pid)?;
+
+        let group = process
+            .group_id()
+            .and_then(|gid| self.groups.iter().find(|group| *group.id() == gid))
+            .map(|group| group.name().to_string())
+            .unwrap_or_default();
+
+        let mut parent_chain = Vec::new();
+        let mut parent = process.parent();
+        while let Some(parent_pid) = parent {
+            let Some(parent_process) = self.system.process(parent_pid) else {
+                break;
+            };
+            parent_chain.push((
+                parent_pid.as_u32(),
+                parent_process.name().to_string_lossy().to_string(),
+            ));
+            // Guard against a pid being reused as its own ancestor
+            if parent_chain.len() > 64 {
+                break;
+            }
+            parent = parent_process.parent();
+        }
+
+        let mut environ: Vec<String> = process
+            .environ()
+            .iter()
+            .map(|var| var.to_string_lossy().to_string())
+            .collect();
+        environ.sort();
+
+        let proc_dir = format!("/proc/{}", pid);
+
+        Some(ProcessDetail {
+            info: self.process_info(sys_pid, process),
+            exe: process.exe().map(|path| path.display().to_string()),
+            cwd: process.cwd().map(|path| path.display().to_string()),
+            group,
+            environ,
+            parent_chain,
+            threads: read_threads(&proc_dir),
+            open_fds: fs::read_dir(format!("{}/fd", proc_dir))
+                .ok()
+                .map(|entries| entries.count()),
+            memory_maps: read_memory_maps(&proc_dir),
+            cgroup: fs::read_to_string(format!("{}/cgroup", proc_dir))
+                .ok()
+                .map(|content| {
+                    // cgroup v1 lists one line per controller, mostly the same path
+                    let mut paths: Vec<&str> = Vec::new();
+                    for path in content
+                        .lines()
+                        .filter_map(|line| line.splitn(3, ':').nth(2))
+                    {
+                        if !paths.contains(&path) {
+                            paths.push(path);
+                        }
+                    }
+                    paths.join(", ")
+                }),
+        })
+    }
+
+    pub fn get_disk_info(&self) -> &[DiskInfo] {
+        &self.disk_info
+    }
+
+    fn update_disk_info(&mut self) {
+        let mount_options = read_mount_options();
+
+        self.disk_info = self
+            .disks
+            .iter()
+            .map(|disk| {
+                let mount_point = disk.mount_point().to_string_lossy().to_string();
+                let source = disk.name().to_string_lossy().to_string();
+                let (inodes_total, inodes_free) = inode_usage(disk.mount_point()).unzip();
+
+                DiskInfo {
+                    device: diskio::block_device_name(&source),
+                    source,
+                    file_system: disk.file_system().to_string_lossy().to_string(),
+                    is_removable: disk.is_removable(),
+                    is_read_only: disk.is_read_only(),
+                    mount_options: mount_options.get(&mount_point).cloned().unwrap_or_default(),
+                    total_space: disk.total_space(),
+                    available_space: disk.available_space(),
+                    inodes_total,
+                    inodes_free,
+                    mount_point,
+                }
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -162,6 +595,18 @@ pub enum ProcessCategory {
 }
 
 impl ProcessCategory {
+    /// Display order of the category boxes on the Processes tab.
+    pub const ALL: [ProcessCategory; 8] = [
+        ProcessCategory::System,
+        ProcessCategory::Browser,
+        ProcessCategory::Development,
+        ProcessCategory::Terminal,
+        ProcessCategory::Editor,
+        ProcessCategory::Media,
+        ProcessCategory::Background,
+        ProcessCategory::User,
+    ];
+
     pub fn name(&self) -> &str {
         match self {
             ProcessCategory::System => "System",
@@ -176,16 +621,16 @@ impl ProcessCategory {
     }
 
     pub fn color(&self) -> ratatui::style::Color {
-        use ratatui::style::Color;
+        let theme = crate::theme::current();
         match self {
-            ProcessCategory::System => Color::Red,
-            ProcessCategory::Browser => Color::Cyan,
-            ProcessCategory::Development => Color::Magenta,
-            ProcessCategory::Terminal => Color::Green,
-            ProcessCategory::Editor => Color::Yellow,
-            ProcessCategory::Media => Color::Blue,
-            ProcessCategory::Background => Color::Gray,
-            ProcessCategory::User => Color::White,
+            ProcessCategory::System => theme.critical,
+            ProcessCategory::Browser => theme.accent,
+            ProcessCategory::Development => theme.secondary,
+            ProcessCategory::Terminal => theme.good,
+            ProcessCategory::Editor => theme.highlight,
+            ProcessCategory::Media => theme.tertiary,
+            ProcessCategory::Background => theme.muted,
+            ProcessCategory::User => theme.text,
         }
     }
 }
@@ -301,17 +746,259 @@ pub fn categorize_process(name: &str) -> ProcessCategory {
 #[derive(Debug, Clone)]
 pub struct ProcessInfo {
     pub pid: u32,
+    pub name: String,
+    pub cpu_usage: f32,
+    pub memory: u64,
+    pub virtual_memory: u64,
+    pub user: String,
+    pub state: String,
+    pub threads: u32,
+    pub command: String,
+    pub start_time: u64,
+    pub run_time: u64,
+    pub disk_read: u64,
+    pub disk_written: u64,
+    pub disk_read_rate: u64,
+    pub disk_write_rate: u64,
+    /// `None` when network tracking is off or the process isn't ours to inspect
+    pub network: Option<ProcessNetwork>,
+    pub nice: i32,
+    pub priority: i32,
+    pub category: ProcessCategory,
+}
+
+/// The slice of `ProcessInfo` the Overview panels need.
+#[derive(Debug, Clone)]
+pub struct ProcessUsage {
     pub name: String,
     pub cpu_usage: f32,
     pub memory: u64,
     pub category: ProcessCategory,
 }
 
+/// Rows kept for the Top Processes panel, more than fit on a screen.
+const OVERVIEW_TOP_PROCESSES: usize = 100;
+
+/// The Overview's process panels, worked out from `ProcessUsage`.
+#[derive(Debug, Clone, Default)]
+pub struct OverviewUsage {
+    /// Busiest first
+    pub by_cpu: Vec<ProcessUsage>,
+    /// Largest first
+    pub by_memory: Vec<ProcessUsage>,
+    /// Total CPU and memory per category, see `category_usage`
+    pub categories: Vec<(ProcessCategory, f64, u64)>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessNetwork {
+    pub usage: SocketUsage,
+    pub sent_rate: u64,
+    pub received_rate: u64,
+}
+
+impl ProcessNetwork {
+    pub fn total_rate(&self) -> u64 {
+        self.sent_rate + self.received_rate
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessDetail {
+    pub info: ProcessInfo,
+    pub exe: Option<String>,
+    pub cwd: Option<String>,
+    pub group: String,
+    pub environ: Vec<String>,
+    /// Ancestors from the direct parent up to init
+    pub parent_chain: Vec<(u32, String)>,
+    pub threads: Vec<(u32, String)>,
+    pub open_fds: Option<usize>,
+    pub memory_maps: Option<MemoryMapSummary>,
+    pub cgroup: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemoryMapSummary {
+    pub mappings: usize,
+    pub file_backed: usize,
+    pub anonymous: usize,
+    pub rss: u64,
+    pub pss: u64,
+    pub swap: u64,
+}
+
+fn read_threads(proc_dir: &str) -> Vec<(u32, String)> {
+    let Ok(entries) = fs::read_dir(format!("{}/task", proc_dir)) else {
+        return Vec::new();
+    };
+
+    let mut threads: Vec<(u32, String)> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let tid = entry.file_name().to_str()?.parse().ok()?;
+            let name = fs::read_to_string(entry.path().join("comm")).unwrap_or_default();
+            Some((tid, name.trim().to_string()))
+        })
+        .collect();
+    threads.sort();
+    threads
+}
+
+/// Counts the mappings in `maps` and pulls the totals out of `smaps_rollup`.
+/// Both need ptrace access, so this is `None` for other users' processes.
+fn read_memory_maps(proc_dir: &str) -> Option<MemoryMapSummary> {
+    let maps = fs::read_to_string(format!("{}/maps", proc_dir)).ok()?;
+    let mut summary = MemoryMapSummary::default();
+
+    for line in maps.lines() {
+        summary.mappings += 1;
+        // address perms offset dev inode [path]
+        match line.split_whitespace().nth(5) {
+            Some(path) if path.starts_with('/') => summary.file_backed += 1,
+            _ => summary.anonymous += 1,
+        }
+    }
+
+    if let Ok(rollup) = fs::read_to_string(format!("{}/smaps_rollup", proc_dir)) {
+        for line in rollup.lines() {
+            let mut parts = line.split_whitespace();
+            let (Some(key), Some(value)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let bytes = value.parse::<u64>().unwrap_or(0) * 1024;
+            match key {
+                "Rss:" => summary.rss = bytes,
+                "Pss:" => summary.pss = bytes,
+                "Swap:" => summary.swap = bytes,
+                _ => {}
+            }
+        }
+    }
+
+    Some(summary)
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct SchedStat {
+    priority: i32,
+    nice: i32,
+    threads: u32,
+}
+
+/// sysinfo doesn't expose scheduling info, so pull priority, nice and the
+/// thread count straight out of `/proc/<pid>/stat`.
+fn read_sched_stat(pid: u32) -> SchedStat {
+    let Ok(content) = fs::read_to_string(format!("/proc/{}/stat", pid)) else {
+        return SchedStat::default();
+    };
+
+    // The process name is wrapped in parens and may contain spaces, so start
+    // counting fields after the last ')'. Field 3 (state) is index 0 here.
+    let Some(rest) = content.rsplit_once(')').map(|(_, rest)| rest) else {
+        return SchedStat::default();
+    };
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+    let field = |index: usize| fields.get(index).and_then(|f| f.parse::<i64>().ok());
+
+    SchedStat {
+        priority: field(15).unwrap_or(0) as i32,
+        nice: field(16).unwrap_or(0) as i32,
+        threads: field(17).unwrap_or(0) as u32,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DiskInfo {
     pub mount_point: String,
+    /// What is mounted, e.g. `/dev/sda1` or `tmpfs`
+    pub source: String,
+    /// Kernel name of the underlying block device, as used in /proc/diskstats
+    pub device: Option<String>,
+    pub file_system: String,
+    pub is_removable: bool,
+    pub is_read_only: bool,
+    pub mount_options: String,
     pub total_space: u64,
     pub available_space: u64,
+    pub inodes_total: Option<u64>,
+    pub inodes_free: Option<u64>,
+}
+
+impl DiskInfo {
+    /// Percentage of inodes in use, for filesystems that have a fixed count.
+    pub fn inode_percent(&self) -> Option<f64> {
+        match (self.inodes_total, self.inodes_free) {
+            (Some(total), Some(free)) if total > 0 => {
+                Some(total.saturating_sub(free) as f64 / total as f64 * 100.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Mounts that aren't real storage: anything in `hidden_filesystems`,
+    /// snap images and the layers container runtimes mount.
+    pub fn is_pseudo(&self, hidden_filesystems: &[String]) -> bool {
+        hidden_filesystems.contains(&self.file_system)
+            || self.mount_point.starts_with("/snap/")
+            || self.mount_point.starts_with("/var/lib/docker/")
+            || self.mount_point.starts_with("/var/lib/containers/")
+    }
+}
+
+/// Mount options per mount point from `/proc/mounts`. Later entries win, as
+/// they do for mounts stacked on the same directory.
+fn read_mount_options() -> HashMap<String, String> {
+    fs::read_to_string("/proc/mounts")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mount_point = fields.nth(1)?;
+            let options = fields.nth(1)?;
+            // Spaces and friends in paths are octal-escaped, e.g. "\040"
+            Some((unescape_mount_path(mount_point), options.to_string()))
+        })
+        .collect()
+}
+
+fn unescape_mount_path(path: &str) -> String {
+    let mut result = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(index) = rest.find('\\') {
+        result.push_str(&rest[..index]);
+        let code = rest.get(index + 1..index + 4);
+        match code.and_then(|code| u8::from_str_radix(code, 8).ok()) {
+            Some(byte) => {
+                result.push(byte as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                result.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Total and free inodes of the filesystem mounted at `path`. Filesystems
+/// that allocate inodes dynamically (btrfs, ...) report zero for both.
+fn inode_usage(path: &std::path::Path) -> Option<(u64, u64)> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is a valid NUL-terminated string and `stats` is a
+    // properly sized, writable statvfs struct.
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stats) } != 0 {
+        return None;
+    }
+
+    Some((stats.f_files as u64, stats.f_ffree as u64))
 }
 
 pub fn format_bytes(bytes: u64) -> String {
@@ -327,6 +1014,26 @@ pub fn format_bytes(bytes: u64) -> String {
     format!("{:.2} {}", size, UNITS[unit_index])
 }
 
+pub fn format_rate(bytes_per_second: u64) -> String {
+    format!("{}/s", format_bytes(bytes_per_second))
+}
+
+/// Formats a unix timestamp as local time, dropping the date for today.
+pub fn format_timestamp(timestamp: u64) -> String {
+    use chrono::{DateTime, Local};
+
+    let Some(time) = DateTime::from_timestamp(timestamp as i64, 0) else {
+        return String::new();
+    };
+    let time = time.with_timezone(&Local);
+
+    if time.date_naive() == Local::now().date_naive() {
+        time.format("%H:%M:%S").to_string()
+    } else {
+        time.format("%b %d %H:%M").to_string()
+    }
+}
+
 pub fn format_uptime(seconds: u64) -> String {
     let days = seconds / 86400;
     let hours = (seconds % 86400) / 3600;
@@ -0,0 +1,244 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+use std::{str::FromStr, sync::RwLock};
+
+use crate::config::AppearanceConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    Solarized,
+    HighContrast,
+    Monochrome,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 5] = [
+        ThemeName::Dark,
+        ThemeName::Light,
+        ThemeName::Solarized,
+        ThemeName::HighContrast,
+        ThemeName::Monochrome,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            ThemeName::Dark => "dark",
+            ThemeName::Light => "light",
+            ThemeName::Solarized => "solarized",
+            ThemeName::HighContrast => "high contrast",
+            ThemeName::Monochrome => "monochrome",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|name| name == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    fn palette(&self) -> Theme {
+        match self {
+            ThemeName::Dark => DARK,
+            ThemeName::Light => LIGHT,
+            ThemeName::Solarized => SOLARIZED,
+            ThemeName::HighContrast => HIGH_CONTRAST,
+            ThemeName::Monochrome => MONOCHROME,
+        }
+    }
+}
+
+/// Colors by what they mean rather than what they look like, so a palette
+/// can be swapped without touching the views.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Regular text
+    pub text: Color,
+    /// Labels and secondary details
+    pub muted: Color,
+    /// Hints and things that are switched off
+    pub dim: Color,
+    /// Borders, headings and the focused pane
+    pub accent: Color,
+    /// Values, key bindings and anything that should stand out
+    pub highlight: Color,
+    pub good: Color,
+    pub warning: Color,
+    pub critical: Color,
+    /// Extra series colors for charts and categories
+    pub secondary: Color,
+    pub tertiary: Color,
+    /// Background of the selected row
+    pub selection_bg: Color,
+    /// Background of the unfilled part of gauges
+    pub gauge_bg: Color,
+    /// Text drawn on top of `accent`
+    pub on_accent: Color,
+    /// Without colors the selection is shown reversed instead
+    pub reverse_selection: bool,
+}
+
+impl Theme {
+    /// Style for the selected row of lists and tables.
+    pub fn selection(&self) -> Style {
+        let style = Style::default().add_modifier(Modifier::BOLD);
+        if self.reverse_selection {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style.bg(self.selection_bg)
+        }
+    }
+
+    /// Style for an item picked from a popup, drawn on the accent color.
+    pub fn picked(&self) -> Style {
+        if self.reverse_selection {
+            self.selection()
+        } else {
+            Style::default()
+                .fg(self.on_accent)
+                .bg(self.accent)
+                .add_modifier(Modifier::BOLD)
+        }
+    }
+
+    /// Replaces the roles named in `colors`, skipping unknown roles and
+    /// colors that don't parse.
+    fn with_overrides<'a>(
+        mut self,
+        colors: impl Iterator<Item = (&'a String, &'a String)>,
+    ) -> Self {
+        for (role, value) in colors {
+            let Ok(color) = Color::from_str(value) else {
+                continue;
+            };
+            let slot = match role.as_str() {
+                "text" => &mut self.text,
+                "muted" => &mut self.muted,
+                "dim" => &mut self.dim,
+                "accent" => &mut self.accent,
+                "highlight" => &mut self.highlight,
+                "good" => &mut self.good,
+                "warning" => &mut self.warning,
+                "critical" => &mut self.critical,
+                "secondary" => &mut self.secondary,
+                "tertiary" => &mut self.tertiary,
+                "selection_bg" => &mut self.selection_bg,
+                "gauge_bg" => &mut self.gauge_bg,
+                "on_accent" => &mut self.on_accent,
+                _ => continue,
+            };
+            *slot = color;
+        }
+        self
+    }
+}
+
+const DARK: Theme = Theme {
+    text: Color::White,
+    muted: Color::Gray,
+    dim: Color::DarkGray,
+    accent: Color::Cyan,
+    highlight: Color::Yellow,
+    good: Color::Green,
+    warning: Color::Yellow,
+    critical: Color::Red,
+    secondary: Color::Magenta,
+    tertiary: Color::Blue,
+    selection_bg: Color::DarkGray,
+    gauge_bg: Color::Black,
+    on_accent: Color::Black,
+    reverse_selection: false,
+};
+
+/// For terminals with a light background, where white text and black gauge
+/// tracks are unreadable.
+const LIGHT: Theme = Theme {
+    text: Color::Black,
+    muted: Color::DarkGray,
+    dim: Color::Gray,
+    accent: Color::Blue,
+    highlight: Color::Rgb(0xa0, 0x60, 0x00),
+    good: Color::Rgb(0x00, 0x80, 0x00),
+    warning: Color::Rgb(0xc0, 0x70, 0x00),
+    critical: Color::Red,
+    secondary: Color::Magenta,
+    tertiary: Color::Rgb(0x00, 0x80, 0x80),
+    selection_bg: Color::Rgb(0xd0, 0xd0, 0xd0),
+    gauge_bg: Color::Rgb(0xe4, 0xe4, 0xe4),
+    on_accent: Color::White,
+    reverse_selection: false,
+};
+
+const SOLARIZED: Theme = Theme {
+    text: Color::Rgb(0x93, 0xa1, 0xa1),
+    muted: Color::Rgb(0x83, 0x94, 0x96),
+    dim: Color::Rgb(0x58, 0x6e, 0x75),
+    accent: Color::Rgb(0x26, 0x8b, 0xd2),
+    highlight: Color::Rgb(0xb5, 0x89, 0x00),
+    good: Color::Rgb(0x85, 0x99, 0x00),
+    warning: Color::Rgb(0xcb, 0x4b, 0x16),
+    critical: Color::Rgb(0xdc, 0x32, 0x2f),
+    secondary: Color::Rgb(0xd3, 0x36, 0x82),
+    tertiary: Color::Rgb(0x2a, 0xa1, 0x98),
+    selection_bg: Color::Rgb(0x07, 0x36, 0x42),
+    gauge_bg: Color::Rgb(0x00, 0x2b, 0x36),
+    on_accent: Color::Rgb(0xfd, 0xf6, 0xe3),
+    reverse_selection: false,
+};
+
+const HIGH_CONTRAST: Theme = Theme {
+    text: Color::White,
+    muted: Color::White,
+    dim: Color::Gray,
+    accent: Color::LightCyan,
+    highlight: Color::LightYellow,
+    good: Color::LightGreen,
+    warning: Color::LightYellow,
+    critical: Color::LightRed,
+    secondary: Color::LightMagenta,
+    tertiary: Color::LightBlue,
+    selection_bg: Color::Blue,
+    gauge_bg: Color::Black,
+    on_accent: Color::Black,
+    reverse_selection: false,
+};
+
+/// Leaves every color to the terminal, for `NO_COLOR` and monochrome
+/// displays.
+const MONOCHROME: Theme = Theme {
+    text: Color::Reset,
+    muted: Color::Reset,
+    dim: Color::Reset,
+    accent: Color::Reset,
+    highlight: Color::Reset,
+    good: Color::Reset,
+    warning: Color::Reset,
+    critical: Color::Reset,
+    secondary: Color::Reset,
+    tertiary: Color::Reset,
+    selection_bg: Color::Reset,
+    gauge_bg: Color::Reset,
+    on_accent: Color::Reset,
+    reverse_selection: true,
+};
+
+static CURRENT: RwLock<Theme> = RwLock::new(DARK);
+
+/// The palette the views draw with.
+pub fn current() -> Theme {
+    *CURRENT.read().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Switches to the configured palette. `NO_COLOR` wins over the config, see
+/// https://no-color.org.
+pub fn apply(config: &AppearanceConfig) {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let theme = if no_color {
+        MONOCHROME
+    } else {
+        config.theme.palette().with_overrides(config.colors.iter())
+    };
+    *CURRENT.write().unwrap_or_else(|err| err.into_inner()) = theme;
+}
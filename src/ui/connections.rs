@@ -0,0 +1,160 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table, TableState},
+};
+
+use crate::{
+    app::{App, ConnectionFilter, HitAreas},
+    sockets::{Connection, Protocol},
+    system::SystemInfo,
+    theme,
+    vpn::VpnStatus,
+};
+
+pub fn render_connections(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    vpn_status: &VpnStatus,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let theme = theme::current();
+    let vpn_interface = vpn_status
+        .interface
+        .as_deref()
+        .filter(|_| vpn_status.connected);
+
+    let connections: Vec<&Connection> = system_info
+        .connections
+        .connections
+        .iter()
+        .filter(|connection| app.show_unix_sockets || connection.protocol != Protocol::Unix)
+        .filter(|connection| match app.connection_filter {
+            ConnectionFilter::All => true,
+            ConnectionFilter::Listening => connection.listening,
+            ConnectionFilter::OutsideVpn => {
+                connection.is_external() && connection.interface.as_deref() != vpn_interface
+            }
+        })
+        .collect();
+
+    let header = Row::new(
+        [
+            "Proto", "Local", "Remote", "State", "Iface", "PID", "Process",
+        ]
+        .map(|title| {
+            Cell::from(title).style(
+                Style::default()
+                    .fg(theme.muted)
+                    .add_modifier(Modifier::BOLD),
+            )
+        }),
+    );
+
+    let rows = connections.iter().map(|connection| {
+        let state_color = match connection.state.as_str() {
+            "LISTEN" | "UNCONN" => theme.good,
+            "ESTABLISHED" | "CONNECTED" => theme.accent,
+            _ => theme.highlight,
+        };
+
+        // Outside traffic that skips the VPN is what this view is for
+        let interface_color = match (vpn_interface, connection.interface.as_deref()) {
+            (Some(vpn), Some(interface)) if interface == vpn => theme.good,
+            _ if connection.is_external() => theme.critical,
+            _ => theme.muted,
+        };
+
+        Row::new(vec![
+            Cell::from(connection.protocol.name().to_string())
+                .style(Style::default().fg(theme.secondary)),
+            Cell::from(connection.local.clone()).style(Style::default().fg(theme.text)),
+            Cell::from(connection.remote.clone()).style(Style::default().fg(theme.text)),
+            Cell::from(connection.state.clone()).style(Style::default().fg(state_color)),
+            Cell::from(
+                connection
+                    .interface
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+            )
+            .style(Style::default().fg(interface_color)),
+            Cell::from(
+                connection
+                    .pid
+                    .map_or("-".to_string(), |pid| pid.to_string()),
+            )
+            .style(Style::default().fg(theme.muted)),
+            Cell::from(
+                connection
+                    .process
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+            )
+            .style(Style::default().fg(theme.highlight)),
+        ])
+    });
+
+    let count = |protocols: &[Protocol]| {
+        system_info
+            .connections
+            .connections
+            .iter()
+            .filter(|connection| protocols.contains(&connection.protocol))
+            .count()
+    };
+    let vpn = match vpn_interface {
+        Some(interface) => format!("VPN on {}", interface),
+        None => "VPN down".to_string(),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(
+            " Connections ({}) | TCP: {} UDP: {} Unix: {} | {} ",
+            connections.len(),
+            count(&[Protocol::Tcp, Protocol::Tcp6]),
+            count(&[Protocol::Udp, Protocol::Udp6]),
+            count(&[Protocol::Unix]),
+            vpn
+        ))
+        .title_bottom(format!(
+            " f: showing {} | u: unix sockets {} ",
+            app.connection_filter.name(),
+            if app.show_unix_sockets { "on" } else { "off" }
+        ));
+    let inner = block.inner(area);
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(5),
+            Constraint::Min(22),
+            Constraint::Min(22),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Min(16),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(theme.selection())
+    .block(block);
+
+    let mut state = TableState::default()
+        .with_offset(app.hit_areas.list_offset)
+        .with_selected(Some(app.connection_selected));
+    frame.render_stateful_widget(table, area, &mut state);
+
+    hits.list_area = Some(Rect::new(
+        inner.x,
+        inner.y.saturating_add(1),
+        inner.width,
+        inner.height.saturating_sub(1),
+    ));
+    hits.list_offset = state.offset();
+    hits.list_rows = connections.len();
+}
@@ -0,0 +1,288 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table, TableState, Wrap},
+};
+
+use crate::{
+    app::{App, HitAreas},
+    containers::{self, CgroupStats},
+    system::{SystemInfo, format_bytes},
+    theme,
+};
+
+pub fn render_containers(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let groups: Vec<&CgroupStats> = system_info
+        .containers
+        .groups
+        .iter()
+        .filter(|group| !app.containers_only || group.container.is_some())
+        .collect();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area);
+
+    render_group_table(frame, app, &groups, chunks[0], hits);
+
+    let selected = groups.get(app.container_selected).copied();
+    if super::is_narrow(area) {
+        render_group_details(frame, selected, chunks[1]);
+        return;
+    }
+
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+
+    render_group_details(frame, selected, bottom[0]);
+    render_group_processes(frame, selected, bottom[1]);
+}
+
+fn render_group_table(
+    frame: &mut Frame,
+    app: &App,
+    groups: &[&CgroupStats],
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let theme = theme::current();
+    let container_count = groups.iter().filter(|g| g.container.is_some()).count();
+
+    let header = Row::new(
+        [
+            "Container / Cgroup",
+            "Runtime",
+            "CPU",
+            "Memory",
+            "Mem %",
+            "PIDs",
+            "Throttled",
+        ]
+        .map(|title| {
+            Cell::from(title).style(
+                Style::default()
+                    .fg(theme.muted)
+                    .add_modifier(Modifier::BOLD),
+            )
+        }),
+    );
+
+    let rows = groups.iter().map(|group| {
+        let (name, runtime, name_color) = match &group.container {
+            Some(container) => (
+                container.display_name(),
+                container.runtime.name().to_string(),
+                theme.accent,
+            ),
+            None => (group.path.clone(), "-".to_string(), theme.text),
+        };
+
+        let memory = match (group.memory_current, group.memory_max) {
+            (Some(current), Some(max)) => {
+                format!("{} / {}", format_bytes(current), format_bytes(max))
+            }
+            (Some(current), None) => format_bytes(current),
+            _ => "-".to_string(),
+        };
+
+        let memory_percent = group.memory_percent();
+        let memory_color = match memory_percent {
+            Some(p) if p > 90.0 => theme.critical,
+            Some(p) if p > 70.0 => theme.warning,
+            _ => theme.secondary,
+        };
+
+        let throttled = group.throttled_percent();
+
+        Row::new(vec![
+            Cell::from(name).style(Style::default().fg(name_color)),
+            Cell::from(runtime).style(Style::default().fg(theme.muted)),
+            Cell::from(format!("{:5.1}%", group.cpu_percent)).style(Style::default().fg(
+                if group.cpu_percent > 80.0 {
+                    theme.critical
+                } else if group.cpu_percent > 30.0 {
+                    theme.warning
+                } else {
+                    theme.good
+                },
+            )),
+            Cell::from(memory).style(Style::default().fg(theme.secondary)),
+            Cell::from(memory_percent.map_or("-".to_string(), |p| format!("{:.1}%", p)))
+                .style(Style::default().fg(memory_color)),
+            Cell::from(
+                group
+                    .pids_current
+                    .unwrap_or(group.pids.len() as u64)
+                    .to_string(),
+            ),
+            Cell::from(format!("{:.1}%", throttled)).style(Style::default().fg(
+                if throttled > 10.0 {
+                    theme.critical
+                } else if throttled > 0.0 {
+                    theme.warning
+                } else {
+                    theme.good
+                },
+            )),
+        ])
+    });
+
+    let filter = if app.containers_only {
+        "containers only"
+    } else {
+        "all cgroups"
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(
+            " Cgroups ({}) | Containers: {} | Showing {} (f to toggle) ",
+            groups.len(),
+            container_count,
+            filter
+        ));
+    let inner = block.inner(area);
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(30),
+            Constraint::Length(11),
+            Constraint::Length(7),
+            Constraint::Length(22),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Length(9),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(theme.selection())
+    .block(block);
+
+    let mut state = TableState::default()
+        .with_offset(app.hit_areas.list_offset)
+        .with_selected(Some(app.container_selected));
+    frame.render_stateful_widget(table, area, &mut state);
+
+    hits.list_area = Some(Rect::new(
+        inner.x,
+        inner.y.saturating_add(1),
+        inner.width,
+        inner.height.saturating_sub(1),
+    ));
+    hits.list_offset = state.offset();
+    hits.list_rows = groups.len();
+}
+
+fn render_group_details(frame: &mut Frame, group: Option<&CgroupStats>, area: Rect) {
+    let theme = theme::current();
+    let label = |text: &str| {
+        Span::styled(
+            format!("{:15}", text),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+
+    let lines = match group {
+        Some(group) => {
+            let mut lines = vec![Line::from(vec![
+                label("Cgroup:"),
+                Span::raw(group.path.clone()),
+            ])];
+
+            if let Some(container) = &group.container {
+                lines.push(Line::from(vec![
+                    label("Container ID:"),
+                    Span::raw(container.short_id().to_string()),
+                ]));
+                lines.push(Line::from(vec![
+                    label("Image:"),
+                    Span::raw(container.image.clone().unwrap_or_else(|| "-".to_string())),
+                ]));
+            }
+
+            lines.push(Line::from(vec![
+                label("Memory limit:"),
+                Span::raw(
+                    group
+                        .memory_max
+                        .map_or("unlimited".to_string(), format_bytes),
+                ),
+            ]));
+            lines.push(Line::from(vec![
+                label("CPU periods:"),
+                Span::raw(group.nr_periods.to_string()),
+            ]));
+            lines.push(Line::from(vec![
+                label("Throttled:"),
+                Span::styled(
+                    format!(
+                        "{} periods ({:.1}%), {:.2}s total",
+                        group.nr_throttled,
+                        group.throttled_percent(),
+                        group.throttled_usec as f64 / 1_000_000.0
+                    ),
+                    Style::default().fg(if group.nr_throttled > 0 {
+                        theme.warning
+                    } else {
+                        theme.good
+                    }),
+                ),
+            ]));
+
+            lines
+        }
+        None => vec![Line::from(Span::styled(
+            "No cgroup selected",
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::ITALIC),
+        ))],
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().title(" Limits ").borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, area);
+}
+
+fn render_group_processes(frame: &mut Frame, group: Option<&CgroupStats>, area: Rect) {
+    let theme = theme::current();
+    let pids = group.map_or(&[][..], |group| group.pids.as_slice());
+
+    let items: Vec<ListItem> = pids
+        .iter()
+        .map(|pid| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:8} ", pid), Style::default().fg(theme.accent)),
+                Span::styled(
+                    containers::process_name(*pid),
+                    Style::default().fg(theme.text),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .title(format!(" Processes ({}) ", pids.len()))
+            .borders(Borders::ALL),
+    );
+
+    frame.render_widget(list, area);
+}
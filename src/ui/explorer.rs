@@ -0,0 +1,208 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+};
+use std::{ffi::OsStr, sync::atomic::Ordering};
+
+use crate::{
+    app::{App, HitAreas},
+    explorer::{Explorer, ExplorerSort},
+    system::format_bytes,
+    theme,
+};
+
+pub fn render_explorer(
+    frame: &mut Frame,
+    app: &App,
+    explorer: &Explorer,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let theme = theme::current();
+    if explorer.is_scanning() {
+        render_progress(frame, explorer, area);
+        return;
+    }
+
+    let Some(current) = explorer.current() else {
+        let paragraph = Paragraph::new(explorer.message.clone().unwrap_or_default()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Explorer | Esc to close "),
+        );
+        frame.render_widget(paragraph, area);
+        return;
+    };
+
+    let header = Row::new(["Size", "", "Items", "Name"].map(|title| {
+        Cell::from(title).style(
+            Style::default()
+                .fg(theme.muted)
+                .add_modifier(Modifier::BOLD),
+        )
+    }));
+
+    let bar_width = 20;
+    let rows = current.children.iter().map(|entry| {
+        let share = if current.size > 0 {
+            entry.size as f64 / current.size as f64
+        } else {
+            0.0
+        };
+        let filled = (share * bar_width as f64).round() as usize;
+        let bar = format!(
+            "[{}{}] {:5.1}%",
+            "#".repeat(filled),
+            " ".repeat(bar_width - filled),
+            share * 100.0
+        );
+
+        let name = entry.name.to_string_lossy();
+        let name = if entry.is_dir {
+            format!("{}/", name)
+        } else {
+            name.to_string()
+        };
+        let name_style = match (entry.is_dir, entry.unreadable) {
+            (_, true) => Style::default().fg(theme.critical),
+            (true, false) => Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+            (false, false) => Style::default().fg(theme.text),
+        };
+
+        Row::new(vec![
+            Cell::from(format_bytes(entry.size)).style(Style::default().fg(theme.highlight)),
+            Cell::from(bar).style(Style::default().fg(theme.good)),
+            Cell::from(entry.items.to_string()).style(Style::default().fg(theme.muted)),
+            Cell::from(name).style(name_style),
+        ])
+    });
+
+    let sort = match explorer.sort {
+        ExplorerSort::Size => "size",
+        ExplorerSort::Name => "name",
+    };
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(
+            " {} | {} in {} items | sorted by {} ",
+            explorer.current_path().display(),
+            format_bytes(current.size),
+            current.items,
+            sort
+        ))
+        .title_bottom(
+            " Enter: open | Backspace: up | s: sort | D: delete | r: rescan | Esc: close ",
+        );
+    if let Some(message) = &explorer.message {
+        block = block.title_bottom(Line::from(format!(" {} ", message)).right_aligned());
+    }
+    let inner = block.inner(area);
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Length(bar_width as u16 + 9),
+            Constraint::Length(9),
+            Constraint::Min(20),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(theme.selection())
+    .block(block);
+
+    let mut state = TableState::default()
+        .with_offset(app.hit_areas.list_offset)
+        .with_selected(Some(explorer.selected));
+    frame.render_stateful_widget(table, area, &mut state);
+
+    hits.list_area = Some(Rect::new(
+        inner.x,
+        inner.y.saturating_add(1),
+        inner.width,
+        inner.height.saturating_sub(1),
+    ));
+    hits.list_offset = state.offset();
+    hits.list_rows = current.children.len();
+
+    if explorer.confirm_delete
+        && let Some(entry) = explorer.selected_entry()
+    {
+        render_confirm_delete(frame, explorer, &entry.name, entry.size, area);
+    }
+}
+
+fn render_progress(frame: &mut Frame, explorer: &Explorer, area: Rect) {
+    let theme = theme::current();
+    let progress = &explorer.progress;
+    let current = progress
+        .current
+        .lock()
+        .map(|current| current.clone())
+        .unwrap_or_default();
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "  Scanned {} items, {}",
+                progress.items.load(Ordering::Relaxed),
+                format_bytes(progress.bytes.load(Ordering::Relaxed))
+            ),
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("  {}", current),
+            Style::default().fg(theme.muted),
+        )),
+    ];
+
+    let paragraph = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(
+        format!(" Scanning {} | Esc to cancel ", explorer.root.display()),
+    ));
+    frame.render_widget(paragraph, area);
+}
+
+fn render_confirm_delete(
+    frame: &mut Frame,
+    explorer: &Explorer,
+    name: &OsStr,
+    size: u64,
+    area: Rect,
+) {
+    let theme = theme::current();
+    let question = format!(
+        "Delete {} ({})?",
+        explorer.current_path().join(name).display(),
+        format_bytes(size)
+    );
+    let popup_area = super::centered(area, question.chars().count() as u16 + 6, 5);
+
+    let paragraph = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            question,
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        )),
+    ])
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.critical))
+            .title(" Confirm | y/n ")
+            .title_alignment(Alignment::Center),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(paragraph, popup_area);
+}
@@ -0,0 +1,479 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Sparkline, Table, TableState,
+    },
+};
+use std::time::Duration;
+
+use super::overview::usage_color;
+use crate::{
+    agent::HostSnapshot,
+    app::{App, HitAreas},
+    fleet::HostStatus,
+    history::{self, TimeSeries},
+    system::{SystemInfo, format_bytes, format_uptime},
+    theme,
+    vpn::VpnStatus,
+};
+
+/// How far back the detail sparklines reach.
+const SPARKLINE_SECONDS: f64 = 120.0;
+
+/// One row of the fleet table, this machine or an agent.
+struct HostEntry {
+    name: String,
+    address: Option<String>,
+    status: HostStatus,
+    snapshot: Option<HostSnapshot>,
+    /// Seconds since the last snapshot, None for this machine
+    age: Option<u64>,
+    latency: Option<Duration>,
+    stale: bool,
+}
+
+pub fn render_fleet(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    vpn_status: &VpnStatus,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let local = HostSnapshot::collect(system_info, vpn_status);
+    let mut entries = vec![HostEntry {
+        name: format!("{} (this machine)", local.hostname),
+        address: None,
+        status: HostStatus::Connected,
+        snapshot: Some(local),
+        age: None,
+        latency: None,
+        stale: false,
+    }];
+    entries.extend(app.fleet.hosts().map(|host| HostEntry {
+        name: host.name.clone(),
+        address: Some(host.address.clone()),
+        status: host.status.clone(),
+        snapshot: host.snapshot.clone(),
+        age: host.updated.map(|updated| updated.elapsed().as_secs()),
+        latency: host.latency,
+        stale: host.is_stale(),
+    }));
+    let selected = app.fleet_selected.min(entries.len() - 1);
+
+    if super::is_short(area) {
+        render_host_table(frame, app, &entries, selected, area, hits);
+        return;
+    }
+    // The table gets its rows' worth of room, the selected host the rest
+    let table_height = (entries.len() as u16 + 3).clamp(5, area.height / 2);
+    let chunks =
+        Layout::vertical([Constraint::Length(table_height), Constraint::Min(0)]).split(area);
+    render_host_table(frame, app, &entries, selected, chunks[0], hits);
+
+    let history = if selected == 0 {
+        (recent(&app.cpu_history), recent(&app.memory_history))
+    } else {
+        app.fleet
+            .host(selected - 1)
+            .map(|host| (recent(&host.cpu_history), recent(&host.memory_history)))
+            .unwrap_or_default()
+    };
+    render_host_detail(frame, &entries[selected], history, chunks[1]);
+}
+
+/// The last `SPARKLINE_SECONDS` of a series, as sparkline data.
+fn recent(series: &TimeSeries) -> Vec<u64> {
+    let now = history::now();
+    series
+        .range(now - SPARKLINE_SECONDS, now)
+        .into_iter()
+        .map(|(_, value)| value.round() as u64)
+        .collect()
+}
+
+fn render_host_table(
+    frame: &mut Frame,
+    app: &App,
+    entries: &[HostEntry],
+    selected: usize,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let theme = theme::current();
+    let narrow = super::is_narrow(area);
+
+    let mut titles = vec!["Host", "Status", "CPU", "Memory", "VPN"];
+    let mut widths = vec![
+        Constraint::Min(16),
+        Constraint::Length(12),
+        Constraint::Length(7),
+        Constraint::Length(7),
+        Constraint::Length(14),
+    ];
+    if !narrow {
+        titles.extend(["Load", "Uptime", "Seen"]);
+        widths.extend([
+            Constraint::Length(6),
+            Constraint::Length(12),
+            Constraint::Length(8),
+        ]);
+    }
+    let header = Row::new(titles.into_iter().map(|title| {
+        Cell::from(title).style(
+            Style::default()
+                .fg(theme.muted)
+                .add_modifier(Modifier::BOLD),
+        )
+    }));
+
+    let rows = entries.iter().map(|entry| {
+        let (status, status_color) = status_label(entry);
+        let mut cells = vec![
+            Cell::from(entry.name.clone()).style(Style::default().fg(theme.text)),
+            Cell::from(status).style(Style::default().fg(status_color)),
+        ];
+        let Some(snapshot) = &entry.snapshot else {
+            return Row::new(cells);
+        };
+
+        let cpu = snapshot.cpu_usage as f64;
+        let memory = snapshot.memory_percent();
+        let (vpn, vpn_color) = vpn_label(snapshot);
+        cells.extend([
+            Cell::from(format!("{:>5.1}%", cpu))
+                .style(Style::default().fg(usage_color(cpu, 50.0, 80.0))),
+            Cell::from(format!("{:>5.1}%", memory))
+                .style(Style::default().fg(usage_color(memory, 70.0, 90.0))),
+            Cell::from(vpn).style(Style::default().fg(vpn_color)),
+        ]);
+        if !narrow {
+            let load = snapshot.load_average[0];
+            let cores = snapshot.cpu_count.max(1) as f64;
+            cells.extend([
+                Cell::from(format!("{:.2}", load)).style(Style::default().fg(usage_color(
+                    load / cores * 100.0,
+                    70.0,
+                    100.0,
+                ))),
+                Cell::from(format_uptime(snapshot.uptime)).style(Style::default().fg(theme.muted)),
+                Cell::from(
+                    entry
+                        .age
+                        .map_or("-".to_string(), |age| format!("{}s ago", age)),
+                )
+                .style(Style::default().fg(theme.muted)),
+            ]);
+        }
+        Row::new(cells)
+    });
+
+    let online = entries
+        .iter()
+        .filter(|entry| entry.status == HostStatus::Connected)
+        .count();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(
+            " Fleet - {} of {} hosts online ",
+            online,
+            entries.len()
+        ))
+        .title_bottom(if app.fleet.is_empty() {
+            " no agents, see --connect or [fleet] in the config "
+        } else {
+            " H: switch host "
+        });
+    let inner = block.inner(area);
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(theme.selection())
+        .block(block);
+
+    let mut state = TableState::default()
+        .with_offset(app.hit_areas.list_offset)
+        .with_selected(Some(selected));
+    frame.render_stateful_widget(table, area, &mut state);
+
+    hits.list_area = Some(Rect::new(
+        inner.x,
+        inner.y.saturating_add(1),
+        inner.width,
+        inner.height.saturating_sub(1),
+    ));
+    hits.list_offset = state.offset();
+    hits.list_rows = entries.len();
+}
+
+fn status_label(entry: &HostEntry) -> (String, Color) {
+    let theme = theme::current();
+    match (&entry.status, &entry.address) {
+        (_, None) => ("● local".to_string(), theme.good),
+        (HostStatus::Connected, _) if entry.stale => ("◐ stale".to_string(), theme.warning),
+        (HostStatus::Connected, _) => ("● online".to_string(), theme.good),
+        (HostStatus::Connecting, _) => ("○ connecting".to_string(), theme.warning),
+        (HostStatus::Failed(_), _) => ("○ offline".to_string(), theme.critical),
+    }
+}
+
+fn vpn_label(snapshot: &HostSnapshot) -> (String, Color) {
+    let theme = theme::current();
+    if snapshot.vpn.connected {
+        (
+            format!("● {}", snapshot.vpn.provider.as_deref().unwrap_or("on")),
+            theme.good,
+        )
+    } else {
+        ("○ off".to_string(), theme.critical)
+    }
+}
+
+fn render_host_detail(
+    frame: &mut Frame,
+    entry: &HostEntry,
+    (cpu_data, memory_data): (Vec<u64>, Vec<u64>),
+    area: Rect,
+) {
+    let theme = theme::current();
+    let columns = if super::is_narrow(area) {
+        Layout::vertical([Constraint::Length(6), Constraint::Min(0)]).split(area)
+    } else {
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).split(area)
+    };
+    let charts =
+        Layout::vertical([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]).split(columns[0]);
+
+    let snapshot = entry.snapshot.as_ref();
+
+    let cpu = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!(
+                    " CPU {:.1}% ",
+                    snapshot.map_or(0.0, |snapshot| snapshot.cpu_usage)
+                ))
+                .borders(Borders::ALL),
+        )
+        .data(&cpu_data)
+        .max(100)
+        .style(Style::default().fg(theme.accent));
+    let memory = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!(
+                    " Memory {:.1}% ",
+                    snapshot.map_or(0.0, HostSnapshot::memory_percent)
+                ))
+                .borders(Borders::ALL),
+        )
+        .data(&memory_data)
+        .max(100)
+        .style(Style::default().fg(theme.secondary));
+    frame.render_widget(cpu, charts[0]);
+    frame.render_widget(memory, charts[1]);
+
+    let label = |text: &str| Span::styled(format!("{:10}", text), Style::default().fg(theme.muted));
+    let value = |text: String| Span::styled(text, Style::default().fg(theme.text));
+    let mut lines = Vec::new();
+    if let Some(address) = &entry.address {
+        lines.push(Line::from(vec![label("Address"), value(address.clone())]));
+    }
+    if let Some(latency) = entry.latency {
+        lines.push(Line::from(vec![
+            label("Latency"),
+            value(format!("{} ms", latency.as_millis())),
+        ]));
+    }
+    if let HostStatus::Failed(reason) = &entry.status {
+        lines.push(Line::from(vec![
+            label("Error"),
+            Span::styled(reason.clone(), Style::default().fg(theme.critical)),
+        ]));
+    }
+    if let Some(snapshot) = snapshot {
+        let vpn = &snapshot.vpn;
+        let vpn_details: Vec<&str> = [&vpn.provider, &vpn.server, &vpn.country, &vpn.ip]
+            .into_iter()
+            .filter_map(|detail| detail.as_deref())
+            .collect();
+        let (vpn_state, vpn_color) = vpn_label(snapshot);
+
+        lines.extend([
+            Line::from(vec![label("Hostname"), value(snapshot.hostname.clone())]),
+            Line::from(vec![label("OS"), value(snapshot.os.clone())]),
+            Line::from(vec![label("Uptime"), value(format_uptime(snapshot.uptime))]),
+            Line::from(vec![
+                label("Load"),
+                value(format!(
+                    "{:.2} {:.2} {:.2} on {} cpus",
+                    snapshot.load_average[0],
+                    snapshot.load_average[1],
+                    snapshot.load_average[2],
+                    snapshot.cpu_count
+                )),
+            ]),
+            Line::from(vec![
+                label("Memory"),
+                value(format!(
+                    "{} / {}",
+                    format_bytes(snapshot.memory_used),
+                    format_bytes(snapshot.memory_total)
+                )),
+            ]),
+            Line::from(vec![
+                label("Swap"),
+                value(format!(
+                    "{} / {}",
+                    format_bytes(snapshot.swap_used),
+                    format_bytes(snapshot.swap_total)
+                )),
+            ]),
+            Line::from(vec![
+                label("Processes"),
+                value(snapshot.process_count.to_string()),
+            ]),
+            Line::from(vec![
+                label("VPN"),
+                Span::styled(vpn_state, Style::default().fg(vpn_color)),
+                value(if vpn_details.is_empty() {
+                    String::new()
+                } else {
+                    format!(" {}", vpn_details.join(" | "))
+                }),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Top processes",
+                Style::default()
+                    .fg(theme.muted)
+                    .add_modifier(Modifier::BOLD),
+            )),
+        ]);
+        lines.extend(snapshot.top_processes.iter().map(|process| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:20.20}", process.name),
+                    Style::default().fg(theme.text),
+                ),
+                Span::styled(
+                    format!("{:>6.1}%", process.cpu_usage),
+                    Style::default().fg(theme.highlight),
+                ),
+                Span::styled(
+                    format!(" {:>10}", format_bytes(process.memory)),
+                    Style::default().fg(theme.secondary),
+                ),
+            ])
+        }));
+    } else {
+        lines.push(Line::from(Span::styled(
+            "Nothing received yet",
+            Style::default().fg(theme.muted),
+        )));
+    }
+
+    let details = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", entry.name)),
+    );
+    frame.render_widget(details, columns[1]);
+}
+
+/// The selected remote host for the header: how it's reached, its round
+/// trip and whether its data is falling behind. None for this machine.
+pub fn host_indicator(app: &App) -> Option<Line<'static>> {
+    let theme = theme::current();
+    let host = app.fleet.host(app.fleet_selected.checked_sub(1)?)?;
+    let age = host.updated.map(|updated| updated.elapsed().as_secs());
+
+    let (marker, state, color) = match &host.status {
+        HostStatus::Connected if host.is_stale() => (
+            "◐",
+            format!("stale, {}s old", age.unwrap_or(0)),
+            theme.warning,
+        ),
+        HostStatus::Connected => (
+            "●",
+            host.latency.map_or(String::new(), |latency| {
+                format!("{} ms", latency.as_millis())
+            }),
+            theme.good,
+        ),
+        HostStatus::Connecting => ("○", "connecting".to_string(), theme.warning),
+        HostStatus::Failed(_) => (
+            "○",
+            age.map_or("offline".to_string(), |age| {
+                format!("offline, last data {}s ago", age)
+            }),
+            theme.critical,
+        ),
+    };
+    let via = if host.over_ssh() { "ssh" } else { "agent" };
+
+    Some(
+        Line::from(vec![
+            Span::raw(" "),
+            Span::styled(format!("{} ", marker), Style::default().fg(color)),
+            Span::styled(host.name.clone(), Style::default().fg(theme.text)),
+            Span::styled(format!(" via {} ", via), Style::default().fg(theme.muted)),
+            Span::styled(state, Style::default().fg(color)),
+            Span::raw(" "),
+        ])
+        .right_aligned(),
+    )
+}
+
+/// Popup listing this machine and every agent, to pick the one the Fleet
+/// tab shows in detail.
+pub fn render_host_picker(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = theme::current();
+
+    let mut hosts = vec![("this machine".to_string(), theme.good)];
+    hosts.extend(app.fleet.hosts().map(|host| {
+        let color = match host.status {
+            HostStatus::Connected => theme.good,
+            HostStatus::Connecting => theme.warning,
+            HostStatus::Failed(_) => theme.critical,
+        };
+        (host.name.clone(), color)
+    }));
+
+    let items: Vec<ListItem> = hosts
+        .iter()
+        .enumerate()
+        .map(|(index, (name, color))| {
+            let marker = Span::styled("● ", Style::default().fg(*color));
+            let style = if index == app.host_picker_index {
+                theme.picked()
+            } else {
+                Style::default().fg(theme.text)
+            };
+            ListItem::new(Line::from(vec![marker, Span::styled(name.clone(), style)]))
+        })
+        .collect();
+
+    let width = hosts
+        .iter()
+        .map(|(name, _)| name.chars().count() as u16 + 6)
+        .max()
+        .unwrap_or(0)
+        .max(24);
+    let popup_area = super::centered(area, width, hosts.len() as u16 + 2);
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.highlight))
+            .title(" Hosts | Enter: show ")
+            .title_alignment(Alignment::Center),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(list, popup_area);
+}
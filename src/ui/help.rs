@@ -1,92 +1,237 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
+use crate::theme;
+
 pub fn render_help(frame: &mut Frame) {
+    let theme = theme::current();
     let area = frame.area();
 
     let help_text = vec![
         Line::from(vec![Span::styled(
             "Keyboard Shortcuts",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "General",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.good).add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("q / Ctrl+C       ", Style::default().fg(Color::Yellow)),
+            Span::styled("q / Ctrl+C       ", Style::default().fg(theme.highlight)),
             Span::raw("Quit application"),
         ]),
         Line::from(vec![
-            Span::styled("?                ", Style::default().fg(Color::Yellow)),
+            Span::styled("?                ", Style::default().fg(theme.highlight)),
             Span::raw("Toggle help screen"),
         ]),
+        Line::from(vec![
+            Span::styled("t                ", Style::default().fg(theme.highlight)),
+            Span::raw("Cycle color theme"),
+        ]),
+        Line::from(vec![
+            Span::styled("+ / -            ", Style::default().fg(theme.highlight)),
+            Span::raw("Overview: zoom charts (1m, 5m, 1h, 24h)"),
+        ]),
+        Line::from(vec![
+            Span::styled("[ / ]  End       ", Style::default().fg(theme.highlight)),
+            Span::raw("Overview: pan charts back / forward, back to live"),
+        ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Tab Navigation",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.good).add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("Tab              ", Style::default().fg(Color::Yellow)),
+            Span::styled("Tab              ", Style::default().fg(theme.highlight)),
             Span::raw("Next tab"),
         ]),
         Line::from(vec![
-            Span::styled("Shift+Tab        ", Style::default().fg(Color::Yellow)),
+            Span::styled("Shift+Tab        ", Style::default().fg(theme.highlight)),
             Span::raw("Previous tab"),
         ]),
         Line::from(vec![
-            Span::styled("1 / 2 / 3 / 4    ", Style::default().fg(Color::Yellow)),
+            Span::styled("1 - 9            ", Style::default().fg(theme.highlight)),
             Span::raw("Switch to specific tab"),
         ]),
+        Line::from(vec![
+            Span::styled("0                ", Style::default().fg(theme.highlight)),
+            Span::raw("Fleet tab"),
+        ]),
+        Line::from(vec![
+            Span::styled("H                ", Style::default().fg(theme.highlight)),
+            Span::raw("Switch host (this machine or a connected agent)"),
+        ]),
+        Line::from(vec![
+            Span::styled("E                ", Style::default().fg(theme.highlight)),
+            Span::raw("Export history, processes, disks and VPN state"),
+        ]),
+        Line::from(vec![
+            Span::styled("P                ", Style::default().fg(theme.highlight)),
+            Span::raw("Save a screenshot of the current view"),
+        ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Processes Tab - Box Selection Mode",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.good).add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("h/j/k/l or Arrows", Style::default().fg(Color::Yellow)),
+            Span::styled("h/j/k/l or Arrows", Style::default().fg(theme.highlight)),
             Span::raw("Navigate between category boxes"),
         ]),
         Line::from(vec![
-            Span::styled("Enter            ", Style::default().fg(Color::Yellow)),
+            Span::styled("Enter            ", Style::default().fg(theme.highlight)),
             Span::raw("Expand selected category (show all processes)"),
         ]),
         Line::from(vec![
-            Span::styled("s                ", Style::default().fg(Color::Yellow)),
-            Span::raw("Cycle sort field (CPU/Memory/Name/PID)"),
+            Span::styled("s                ", Style::default().fg(theme.highlight)),
+            Span::raw("Cycle sort through the visible columns"),
         ]),
         Line::from(vec![
-            Span::styled("o                ", Style::default().fg(Color::Yellow)),
+            Span::styled("o                ", Style::default().fg(theme.highlight)),
             Span::raw("Toggle sort order (Asc/Desc)"),
         ]),
+        Line::from(vec![
+            Span::styled("< / >            ", Style::default().fg(theme.highlight)),
+            Span::raw("Narrow / widen the sorted column"),
+        ]),
+        Line::from(vec![
+            Span::styled("c                ", Style::default().fg(theme.highlight)),
+            Span::raw("Choose visible columns"),
+        ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Processes Tab - Expanded Mode",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.good).add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("k/j or Up/Down   ", Style::default().fg(theme.highlight)),
+            Span::raw("Move the selection through the process list"),
+        ]),
+        Line::from(vec![
+            Span::styled("Enter            ", Style::default().fg(theme.highlight)),
+            Span::raw("Open details for the selected process"),
+        ]),
+        Line::from(vec![
+            Span::styled("Esc              ", Style::default().fg(theme.highlight)),
+            Span::raw("Close details / exit expanded mode (back to boxes)"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "System Info Tab",
+            Style::default().fg(theme.good).add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("k/j or Up/Down   ", Style::default().fg(Color::Yellow)),
+            Span::styled("k/j or Up/Down   ", Style::default().fg(theme.highlight)),
+            Span::raw("Select a mount, or the device shown in the I/O chart"),
+        ]),
+        Line::from(vec![
+            Span::styled("a                ", Style::default().fg(theme.highlight)),
+            Span::raw("Show / hide pseudo mounts (tmpfs, overlay, snaps)"),
+        ]),
+        Line::from(vec![
+            Span::styled("d                ", Style::default().fg(theme.highlight)),
+            Span::raw("Switch between the mount list and the device table"),
+        ]),
+        Line::from(vec![
+            Span::styled("e / Enter        ", Style::default().fg(theme.highlight)),
+            Span::raw("Explore directory sizes of the selected mount"),
+        ]),
+        Line::from(vec![
+            Span::styled("Enter / Bksp     ", Style::default().fg(theme.highlight)),
+            Span::raw("Explorer: open directory / go up (s: sort, D: delete)"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Containers Tab",
+            Style::default().fg(theme.good).add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("k/j or Up/Down   ", Style::default().fg(theme.highlight)),
+            Span::raw("Select a cgroup"),
+        ]),
+        Line::from(vec![
+            Span::styled("f                ", Style::default().fg(theme.highlight)),
+            Span::raw("Toggle containers only / all cgroups"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Services Tab",
+            Style::default().fg(theme.good).add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("k/j or Up/Down   ", Style::default().fg(theme.highlight)),
+            Span::raw("Select a unit"),
+        ]),
+        Line::from(vec![
+            Span::styled("S / X / R        ", Style::default().fg(theme.highlight)),
+            Span::raw("Start / stop / restart the selected unit (asks first)"),
+        ]),
+        Line::from(vec![
+            Span::styled("Enter            ", Style::default().fg(theme.highlight)),
+            Span::raw("Show / hide recent journal lines"),
+        ]),
+        Line::from(vec![
+            Span::styled("f                ", Style::default().fg(theme.highlight)),
+            Span::raw("Toggle failed units only"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Sensors Tab",
+            Style::default().fg(theme.good).add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("k/j or Up/Down   ", Style::default().fg(theme.highlight)),
+            Span::raw("Select the sensor shown in the history chart"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Connections Tab",
+            Style::default().fg(theme.good).add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("f                ", Style::default().fg(theme.highlight)),
+            Span::raw("Cycle all / listening only / not via VPN"),
+        ]),
+        Line::from(vec![
+            Span::styled("u                ", Style::default().fg(theme.highlight)),
+            Span::raw("Show / hide unix sockets"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Mouse",
+            Style::default().fg(theme.good).add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("Click tab        ", Style::default().fg(theme.highlight)),
+            Span::raw("Switch to that tab"),
+        ]),
+        Line::from(vec![
+            Span::styled("Click category   ", Style::default().fg(theme.highlight)),
+            Span::raw("Select box, click again to expand"),
+        ]),
+        Line::from(vec![
+            Span::styled("Click header     ", Style::default().fg(theme.highlight)),
+            Span::raw("Sort by column, click again to flip order"),
+        ]),
+        Line::from(vec![
+            Span::styled("Scroll wheel     ", Style::default().fg(theme.highlight)),
             Span::raw("Scroll through process list"),
         ]),
         Line::from(vec![
-            Span::styled("Esc              ", Style::default().fg(Color::Yellow)),
-            Span::raw("Exit expanded mode (back to boxes)"),
+            Span::styled("Click row        ", Style::default().fg(theme.highlight)),
+            Span::raw("Select process, click again for details"),
+        ]),
+        Line::from(vec![
+            Span::styled("Hover chart      ", Style::default().fg(theme.highlight)),
+            Span::raw("Crosshair with the time and value there"),
         ]),
     ];
 
@@ -95,13 +240,18 @@ pub fn render_help(frame: &mut Frame) {
             Block::default()
                 .title(" Help ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(theme.accent)),
         )
         .alignment(Alignment::Left)
         .wrap(Wrap { trim: true });
 
-    let centered_area = centered_rect(60, 60, area);
-    frame.render_widget(paragraph, centered_area);
+    // Small terminals need every line they have for the help text
+    let help_area = if super::is_narrow(area) || super::is_short(area) {
+        area
+    } else {
+        centered_rect(60, 60, area)
+    };
+    frame.render_widget(paragraph, help_area);
 }
 
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -0,0 +1,503 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+};
+
+use crate::{
+    memory::{MemoryMonitor, MemorySample, Pressure},
+    system::{SystemInfo, format_bytes, format_rate},
+    theme,
+};
+
+pub fn render_memory(frame: &mut Frame, system_info: &SystemInfo, area: Rect) {
+    let memory = &system_info.memory;
+
+    // Short terminals lose the bottom charts and narrow ones the pressure
+    // panes, PSI being the least likely to be looked at
+    let short = area.height < 12 + 8 + 10;
+    let narrow = super::is_narrow(area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(if short {
+            vec![
+                Constraint::Length(12.min(area.height / 2)),
+                Constraint::Min(0),
+            ]
+        } else {
+            vec![
+                Constraint::Length(12),
+                Constraint::Min(8),
+                Constraint::Length(10),
+            ]
+        })
+        .split(area);
+
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(if narrow {
+            vec![Constraint::Percentage(55), Constraint::Percentage(45)]
+        } else {
+            vec![
+                Constraint::Percentage(40),
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+            ]
+        })
+        .split(chunks[0]);
+
+    render_breakdown(frame, memory, top[0]);
+    render_swap(frame, memory, top[1]);
+    if !narrow {
+        render_pressure(frame, memory, top[2]);
+    }
+    render_memory_chart(frame, memory, chunks[1]);
+
+    if !short {
+        if narrow {
+            render_swap_chart(frame, memory, chunks[2]);
+        } else {
+            let bottom = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(chunks[2]);
+            render_swap_chart(frame, memory, bottom[0]);
+            render_pressure_chart(frame, memory, bottom[1]);
+        }
+    }
+}
+
+fn value_line(label: &str, value: String, color: Color) -> Line<'static> {
+    let theme = theme::current();
+    Line::from(vec![
+        Span::styled(
+            format!("{:14}", label),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(value, Style::default().fg(color)),
+    ])
+}
+
+fn with_percent(bytes: u64, total: u64) -> String {
+    if total > 0 {
+        format!(
+            "{:>10} ({:4.1}%)",
+            format_bytes(bytes),
+            bytes as f64 / total as f64 * 100.0
+        )
+    } else {
+        format!("{:>10}", format_bytes(bytes))
+    }
+}
+
+fn render_breakdown(frame: &mut Frame, memory: &MemoryMonitor, area: Rect) {
+    let theme = theme::current();
+    let info = &memory.info;
+
+    let lines = vec![
+        value_line(
+            "Total",
+            format!("{:>10}", format_bytes(info.total)),
+            theme.text,
+        ),
+        value_line("Used", with_percent(info.used(), info.total), theme.good),
+        value_line(
+            "Buffers",
+            with_percent(info.buffers, info.total),
+            theme.tertiary,
+        ),
+        value_line(
+            "Cached",
+            with_percent(info.cached, info.total),
+            theme.highlight,
+        ),
+        value_line("Free", with_percent(info.free, info.total), theme.muted),
+        value_line(
+            "Available",
+            with_percent(info.available, info.total),
+            theme.text,
+        ),
+        value_line(
+            "Dirty / WB",
+            format!(
+                "{:>10} / {}",
+                format_bytes(info.dirty),
+                format_bytes(info.writeback)
+            ),
+            theme.secondary,
+        ),
+        value_line(
+            "Slab",
+            format!(
+                "{:>10} ({} reclaimable)",
+                format_bytes(info.slab),
+                format_bytes(info.slab_reclaimable)
+            ),
+            theme.text,
+        ),
+        value_line(
+            "Shmem",
+            format!("{:>10}", format_bytes(info.shmem)),
+            theme.text,
+        ),
+        value_line(
+            "Page tables",
+            format!("{:>10}", format_bytes(info.page_tables)),
+            theme.text,
+        ),
+    ];
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Memory (/proc/meminfo) "),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+fn render_swap(frame: &mut Frame, memory: &MemoryMonitor, area: Rect) {
+    let theme = theme::current();
+    let info = &memory.info;
+
+    let swap_color = if memory.swap_in_rate > 0 || memory.swap_out_rate > 0 {
+        theme.critical
+    } else {
+        theme.good
+    };
+
+    let mut lines = vec![
+        value_line(
+            "Swap used",
+            format!(
+                "{} / {}",
+                format_bytes(info.swap_used()),
+                format_bytes(info.swap_total)
+            ),
+            theme.text,
+        ),
+        value_line("Swap cached", format_bytes(info.swap_cached), theme.text),
+        value_line("Swap in", format_rate(memory.swap_in_rate), swap_color),
+        value_line("Swap out", format_rate(memory.swap_out_rate), swap_color),
+        value_line(
+            "Committed",
+            format!(
+                "{} / {}",
+                format_bytes(info.committed),
+                format_bytes(info.commit_limit)
+            ),
+            if info.commit_limit > 0 && info.committed > info.commit_limit {
+                theme.warning
+            } else {
+                theme.text
+            },
+        ),
+        Line::from(""),
+    ];
+
+    if info.huge_pages_total > 0 {
+        lines.push(value_line(
+            "Huge pages",
+            format!(
+                "{} / {} free ({} each)",
+                info.huge_pages_free,
+                info.huge_pages_total,
+                format_bytes(info.huge_page_size)
+            ),
+            theme.text,
+        ));
+    } else {
+        lines.push(value_line(
+            "Huge pages",
+            "none reserved".to_string(),
+            theme.muted,
+        ));
+    }
+    lines.push(value_line(
+        "Anon THP",
+        format_bytes(info.anon_huge_pages),
+        theme.text,
+    ));
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Swap & Huge Pages "),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+fn pressure_color(percent: f64) -> Color {
+    let theme = theme::current();
+    if percent >= 10.0 {
+        theme.critical
+    } else if percent >= 1.0 {
+        theme.warning
+    } else {
+        theme.good
+    }
+}
+
+fn render_pressure(frame: &mut Frame, memory: &MemoryMonitor, area: Rect) {
+    let theme = theme::current();
+    let header = Line::from(Span::styled(
+        format!("{:14}{:>7}{:>7}{:>7}", "", "10s", "60s", "300s"),
+        Style::default()
+            .fg(theme.muted)
+            .add_modifier(Modifier::BOLD),
+    ));
+
+    let mut lines = vec![header];
+    if memory.has_pressure() {
+        for (name, pressure) in [
+            ("cpu", memory.cpu_pressure),
+            ("memory", memory.memory_pressure),
+            ("io", memory.io_pressure),
+        ] {
+            let Some(pressure) = pressure else {
+                continue;
+            };
+            lines.extend(pressure_lines(name, &pressure));
+        }
+    } else {
+        lines.push(Line::from(Span::styled(
+            "PSI not available (needs CONFIG_PSI)",
+            Style::default().fg(theme.muted),
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Pressure Stall (% of time) "),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+fn pressure_lines(name: &str, pressure: &Pressure) -> Vec<Line<'static>> {
+    let theme = theme::current();
+    let mut rows = vec![(format!("{} some", name), pressure.some)];
+    if let Some(full) = pressure.full {
+        rows.push((format!("{} full", name), full));
+    }
+
+    rows.into_iter()
+        .map(|(label, averages)| {
+            let mut spans = vec![Span::styled(
+                format!("{:14}", label),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )];
+            for value in [averages.avg10, averages.avg60, averages.avg300] {
+                spans.push(Span::styled(
+                    format!("{:>7.2}", value),
+                    Style::default().fg(pressure_color(value)),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Points drawn per sample in the stacked chart.
+const BAR_STEPS: usize = 4;
+
+/// Legend name, colour and how to get the value out of a sample.
+type Series<T> = (&'static str, Color, fn(&MemorySample) -> T);
+
+/// Stacks the memory categories on top of each other. Each layer is drawn as
+/// bars from zero up to its cumulative total, tallest first, so the layers
+/// drawn later cover the bottom part of the earlier ones.
+fn render_memory_chart(frame: &mut Frame, memory: &MemoryMonitor, area: Rect) {
+    let theme = theme::current();
+    let layers: [Series<u64>; 4] = [
+        ("Free", theme.dim, |s| {
+            s.used + s.buffers + s.cached + s.free
+        }),
+        ("Cached", theme.highlight, |s| s.used + s.buffers + s.cached),
+        ("Buffers", theme.tertiary, |s| s.used + s.buffers),
+        ("Used", theme.good, |s| s.used),
+    ];
+
+    let data: Vec<Vec<(f64, f64)>> = layers
+        .iter()
+        .map(|(_, _, value)| {
+            memory
+                .history
+                .iter()
+                .enumerate()
+                // Bars are a single column wide, so fill in between samples
+                // to leave no gaps when a sample spans several columns
+                .flat_map(|(i, sample)| {
+                    let value = value(sample) as f64;
+                    (0..BAR_STEPS)
+                        .map(move |step| (i as f64 + step as f64 / BAR_STEPS as f64, value))
+                })
+                .collect()
+        })
+        .collect();
+
+    let datasets = layers
+        .iter()
+        .zip(&data)
+        .map(|((name, color, _), data)| {
+            Dataset::default()
+                .name(*name)
+                .marker(symbols::Marker::HalfBlock)
+                .graph_type(GraphType::Bar)
+                .style(Style::default().fg(*color))
+                .data(data)
+        })
+        .collect();
+
+    let total = memory.info.total.max(1) as f64;
+    let chart = Chart::new(datasets)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            " Memory History | used {} / buffers {} / cached {} / free {} ",
+            format_bytes(memory.info.used()),
+            format_bytes(memory.info.buffers),
+            format_bytes(memory.info.cached),
+            format_bytes(memory.info.free)
+        )))
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .bounds([0.0, memory.history_size as f64]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .labels(vec![
+                    Line::from("0"),
+                    Line::from(format_bytes(memory.info.total / 2)),
+                    Line::from(format_bytes(memory.info.total)),
+                ])
+                .bounds([0.0, total]),
+        );
+
+    frame.render_widget(chart, area);
+}
+
+fn render_swap_chart(frame: &mut Frame, memory: &MemoryMonitor, area: Rect) {
+    let theme = theme::current();
+    let swap_in: Vec<(f64, f64)> = memory
+        .history
+        .iter()
+        .enumerate()
+        .map(|(i, sample)| (i as f64, sample.swap_in as f64))
+        .collect();
+    let swap_out: Vec<(f64, f64)> = memory
+        .history
+        .iter()
+        .enumerate()
+        .map(|(i, sample)| (i as f64, sample.swap_out as f64))
+        .collect();
+
+    let max = memory
+        .history
+        .iter()
+        .map(|sample| sample.swap_in.max(sample.swap_out))
+        .max()
+        .unwrap_or(0)
+        .max(1024);
+
+    let datasets = vec![
+        Dataset::default()
+            .name("in")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.accent))
+            .data(&swap_in),
+        Dataset::default()
+            .name("out")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.critical))
+            .data(&swap_out),
+    ];
+
+    let chart = Chart::new(datasets)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            " Swap I/O | in {} / out {} ",
+            format_rate(memory.swap_in_rate),
+            format_rate(memory.swap_out_rate)
+        )))
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .bounds([0.0, memory.history_size as f64]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .labels(vec![Line::from("0"), Line::from(format_rate(max))])
+                .bounds([0.0, max as f64]),
+        );
+
+    frame.render_widget(chart, area);
+}
+
+fn render_pressure_chart(frame: &mut Frame, memory: &MemoryMonitor, area: Rect) {
+    let theme = theme::current();
+    let series: [Series<f64>; 3] = [
+        ("cpu", theme.accent, |s| s.cpu_pressure),
+        ("memory", theme.secondary, |s| s.memory_pressure),
+        ("io", theme.highlight, |s| s.io_pressure),
+    ];
+
+    let data: Vec<Vec<(f64, f64)>> = series
+        .iter()
+        .map(|(_, _, value)| {
+            memory
+                .history
+                .iter()
+                .enumerate()
+                .map(|(i, sample)| (i as f64, value(sample)))
+                .collect()
+        })
+        .collect();
+
+    let datasets = series
+        .iter()
+        .zip(&data)
+        .map(|((name, color, _), data)| {
+            Dataset::default()
+                .name(*name)
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(*color))
+                .data(data)
+        })
+        .collect();
+
+    let max = data
+        .iter()
+        .flatten()
+        .fold(10.0f64, |max, (_, value)| max.max(*value));
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Pressure History (some, avg10 %) "),
+        )
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .bounds([0.0, memory.history_size as f64]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .labels(vec![Line::from("0"), Line::from(format!("{:.0}", max))])
+                .bounds([0.0, max]),
+        );
+
+    frame.render_widget(chart, area);
+}
@@ -1,25 +1,94 @@
+mod connections;
+mod containers;
+mod explorer;
+mod fleet;
 mod help;
+mod memory;
 mod overview;
+mod power;
+mod process_detail;
 mod processes;
+mod sensors;
+mod services;
 mod system_info;
 mod vpn;
 
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Tabs},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Tabs, Wrap},
 };
+use std::time::Duration;
 
 use crate::{
-    app::{App, Tab},
+    app::{App, HitAreas, Tab},
     system::SystemInfo,
+    theme,
     vpn::VpnStatus,
 };
 
-pub fn render(frame: &mut Frame, app: &App, system_info: &SystemInfo, vpn_status: &VpnStatus) {
+/// Views switch to their compact layouts when their area is narrower or
+/// shorter than this.
+const NARROW_WIDTH: u16 = 60;
+const SHORT_HEIGHT: u16 = 16;
+
+/// Below this the whole screen is replaced by a note asking for more room.
+const MIN_WIDTH: u16 = 20;
+const MIN_HEIGHT: u16 = 6;
+
+/// How long the header shows where the last export or screenshot went.
+const EXPORT_NOTICE: Duration = Duration::from_secs(8);
+
+fn is_narrow(area: Rect) -> bool {
+    area.width < NARROW_WIDTH
+}
+
+fn is_short(area: Rect) -> bool {
+    area.height < SHORT_HEIGHT
+}
+
+/// A popup of at most `width` x `height` in the middle of `area`.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// A strip of at most `height` lines along the bottom of `area`.
+fn bottom(area: Rect, height: u16) -> Rect {
+    let height = height.min(area.height);
+    Rect {
+        y: area.bottom() - height,
+        height,
+        ..area
+    }
+}
+
+pub fn render(frame: &mut Frame, app: &mut App, system_info: &SystemInfo, vpn_status: &VpnStatus) {
+    let mut hits = HitAreas::default();
+
+    let size = frame.area();
+    if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+        let note = Paragraph::new(format!(
+            "Terminal too small ({}x{}), need {}x{}",
+            size.width, size.height, MIN_WIDTH, MIN_HEIGHT
+        ))
+        .wrap(Wrap { trim: true });
+        frame.render_widget(note, size);
+        app.hit_areas = hits;
+        return;
+    }
+
     if app.show_help {
         help::render_help(frame);
+        app.hit_areas = hits;
         return;
     }
 
@@ -28,42 +97,126 @@ pub fn render(frame: &mut Frame, app: &App, system_info: &SystemInfo, vpn_status
         .constraints([Constraint::Length(3), Constraint::Min(0)])
         .split(frame.area());
 
-    render_tabs(frame, app, chunks[0]);
+    render_tabs(frame, app, chunks[0], &mut hits);
 
     match app.current_tab {
-        Tab::Overview => overview::render_overview(frame, app, system_info, chunks[1]),
-        Tab::Processes => processes::render_processes(frame, app, system_info, chunks[1]),
-        Tab::SystemInfo => system_info::render_system_info(frame, system_info, chunks[1]),
+        Tab::Overview => {
+            overview::render_overview(frame, app, system_info, vpn_status, chunks[1], &mut hits)
+        }
+        Tab::Processes => {
+            processes::render_processes(frame, app, system_info, chunks[1], &mut hits)
+        }
+        Tab::SystemInfo => {
+            system_info::render_system_info(frame, app, system_info, chunks[1], &mut hits)
+        }
         Tab::Vpn => vpn::render_vpn(frame, vpn_status, chunks[1]),
+        Tab::Containers => {
+            containers::render_containers(frame, app, system_info, chunks[1], &mut hits)
+        }
+        Tab::Services => services::render_services(frame, app, system_info, chunks[1], &mut hits),
+        Tab::Sensors => sensors::render_sensors(frame, app, system_info, chunks[1], &mut hits),
+        Tab::Memory => memory::render_memory(frame, system_info, chunks[1]),
+        Tab::Connections => connections::render_connections(
+            frame,
+            app,
+            system_info,
+            vpn_status,
+            chunks[1],
+            &mut hits,
+        ),
+        Tab::Fleet => {
+            fleet::render_fleet(frame, app, system_info, vpn_status, chunks[1], &mut hits)
+        }
     }
+
+    if app.host_picker {
+        fleet::render_host_picker(frame, app, chunks[1]);
+    }
+
+    app.hit_areas = hits;
 }
 
-fn render_tabs(frame: &mut Frame, app: &App, area: Rect) {
-    let titles = vec![
-        "Overview [1]",
-        "Processes [2]",
-        "System Info [3]",
-        "VPN [4]",
-    ];
-    let selected = match app.current_tab {
-        Tab::Overview => 0,
-        Tab::Processes => 1,
-        Tab::SystemInfo => 2,
-        Tab::Vpn => 3,
-    };
+fn render_tabs(frame: &mut Frame, app: &App, area: Rect, hits: &mut HitAreas) {
+    let theme = theme::current();
+    // When the full titles don't fit, only the current tab keeps its name
+    // and the others are shown by their number key.
+    let full_width: usize = Tab::ALL
+        .iter()
+        .map(|tab| tab.title().chars().count() + 3)
+        .sum::<usize>()
+        + 1;
+    let compact = full_width > area.width as usize;
+    let titles: Vec<String> = Tab::ALL
+        .iter()
+        .map(|tab| {
+            if compact && *tab != app.current_tab {
+                tab.key().to_string()
+            } else {
+                tab.title().to_string()
+            }
+        })
+        .collect();
 
-    let tabs = Tabs::new(titles)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" System Monitor ")
-                .title_alignment(Alignment::Center),
+    // Mirror the Tabs widget layout: one space of padding either side of
+    // each title and a one-column divider between them.
+    let mut x = area.x + 1;
+    let right = area.right().saturating_sub(1);
+    for (tab, title) in Tab::ALL.into_iter().zip(&titles) {
+        if x >= right {
+            break;
+        }
+        let width = (title.chars().count() as u16 + 2).min(right - x);
+        hits.tabs
+            .push((Rect::new(x, area.y, width, area.height), tab));
+        x += width + 1;
+    }
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(" System Monitor ")
+        .title_bottom(
+            Line::from(format!(" t: {} theme ", app.config.appearance.theme.name()))
+                .right_aligned(),
         )
-        .select(selected)
-        .style(Style::default().fg(Color::White))
+        .title_alignment(Alignment::Center);
+    // Which remote host the Fleet tab is showing, and how fresh its data is,
+    // when there's room for it right of the centered title
+    if app.current_tab == Tab::Fleet
+        && let Some(indicator) = fleet::host_indicator(app)
+        && indicator.width() + 12 < area.width as usize / 2
+    {
+        block = block.title(indicator);
+    }
+
+    // The outcome of the last export or screenshot, for a few seconds
+    if let Some((at, result)) = &app.export_status
+        && at.elapsed() < EXPORT_NOTICE
+    {
+        let (text, color) = match result {
+            Ok(path) => (format!(" Saved to {} ", path.display()), theme.good),
+            Err(err) => (format!(" Export failed: {} ", err), theme.critical),
+        };
+        // Leave room for the theme hint on the right
+        let room = (area.width as usize).saturating_sub(22);
+        let text: String = text.chars().take(room).collect();
+        block = block.title_bottom(Line::styled(text, Style::default().fg(color)).left_aligned());
+    } else if let Some(err) = &app.config_error {
+        // Stays up while the config file is broken, since nothing is saved
+        let reason = err.lines().next().unwrap_or(err);
+        let text = format!(" Config not loaded, changes aren't saved: {} ", reason);
+        let room = (area.width as usize).saturating_sub(22);
+        let text: String = text.chars().take(room).collect();
+        block = block
+            .title_bottom(Line::styled(text, Style::default().fg(theme.critical)).left_aligned());
+    }
+
+    let tabs = Tabs::new(titles)
+        .block(block)
+        .select(app.current_tab.index())
+        .style(Style::default().fg(theme.text))
         .highlight_style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         );
 
@@ -1,60 +1,197 @@
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
     symbols,
     text::{Line, Span},
-    widgets::{Axis, Block, Borders, Chart, Dataset, Gauge, GraphType, List, ListItem},
+    widgets::{
+        Axis, Block, Borders, Cell, Chart, Dataset, Gauge, GraphType, List, ListItem, Paragraph,
+        Row, Table,
+    },
 };
 
 use crate::{
-    app::App,
-    system::{SystemInfo, format_bytes},
+    app::{App, ChartWindow, HitAreas},
+    config::{DashboardPanel, DashboardRow, Panel, PanelSize},
+    history::TimeSeries,
+    scheduler::Trend,
+    system::{ProcessUsage, SystemInfo, format_bytes, format_rate},
+    theme,
+    vpn::{VpnProvider, VpnStatus},
 };
 
-pub fn render_overview(frame: &mut Frame, app: &App, system_info: &SystemInfo, area: Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Length(10),
-            Constraint::Length(10),
-            Constraint::Min(0),
-        ])
-        .split(area);
+pub fn render_overview(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    vpn_status: &VpnStatus,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    // Panels with nothing to show (no battery, say) leave their space to the
+    // rest of the row, and rows left empty are dropped.
+    let rows: Vec<(&DashboardRow, Vec<&DashboardPanel>)> = app
+        .config
+        .overview
+        .rows
+        .iter()
+        .map(|row| {
+            let panels: Vec<&DashboardPanel> = row
+                .panels
+                .iter()
+                .filter(|entry| is_available(entry.panel, system_info))
+                .collect();
+            (row, panels)
+        })
+        .filter(|(_, panels)| !panels.is_empty())
+        .collect();
 
-    render_cpu_memory_bars(frame, system_info, chunks[0]);
-    render_cpu_graph(frame, app, chunks[1]);
-    render_memory_graph(frame, app, chunks[2]);
-    render_temperatures(frame, system_info, chunks[3]);
+    // Rows are kept in order while their minimum heights fit, so on a short
+    // terminal the ones further down are hidden instead of squashed.
+    let mut remaining = area.height;
+    let rows: Vec<_> = rows
+        .into_iter()
+        .take_while(|(row, _)| {
+            let needed = min_height(row.size);
+            if needed > remaining {
+                return false;
+            }
+            remaining -= needed;
+            true
+        })
+        .collect();
+
+    let row_areas = Layout::vertical(rows.iter().map(|(row, _)| constraint(row.size))).split(area);
+    for ((_, panels), row_area) in rows.iter().zip(row_areas.iter()) {
+        for (entry, panel_area) in arrange_row(panels, *row_area) {
+            render_panel(
+                frame,
+                app,
+                system_info,
+                vpn_status,
+                entry.panel,
+                panel_area,
+                hits,
+            );
+        }
+    }
 }
 
-fn render_cpu_memory_bars(frame: &mut Frame, system_info: &SystemInfo, area: Rect) {
-    let cpu_usage = system_info.get_cpu_usage();
-    let mem_percent = system_info.get_memory_percentage();
-    let (used_mem, total_mem) = system_info.get_memory_usage();
+/// Panels narrower than this are unreadable, so crowded rows are stacked
+/// or cut short instead.
+const MIN_PANEL_WIDTH: u16 = 24;
+/// A panel's border and one line of content.
+const MIN_PANEL_HEIGHT: u16 = 3;
 
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(area);
+fn min_height(size: PanelSize) -> u16 {
+    match size {
+        PanelSize::Length(length) => length,
+        PanelSize::Min(min) => min.max(MIN_PANEL_HEIGHT),
+        PanelSize::Percentage(_) | PanelSize::Ratio(_) => MIN_PANEL_HEIGHT,
+    }
+}
+
+/// Lays a row's panels out side by side. When they don't fit they're
+/// stacked if the row is tall enough, otherwise the trailing ones are
+/// dropped.
+fn arrange_row<'a>(panels: &[&'a DashboardPanel], area: Rect) -> Vec<(&'a DashboardPanel, Rect)> {
+    let fit = (area.width / MIN_PANEL_WIDTH).max(1) as usize;
+    let areas = if panels.len() <= fit {
+        Layout::horizontal(panels.iter().map(|entry| constraint(entry.size))).split(area)
+    } else if (area.height / MIN_PANEL_HEIGHT) as usize >= panels.len() {
+        Layout::vertical(panels.iter().map(|_| Constraint::Fill(1))).split(area)
+    } else {
+        Layout::horizontal(panels[..fit].iter().map(|entry| constraint(entry.size))).split(area)
+    };
+
+    panels
+        .iter()
+        .zip(areas.iter())
+        .map(|(entry, area)| (*entry, *area))
+        .collect()
+}
+
+fn constraint(size: PanelSize) -> Constraint {
+    match size {
+        PanelSize::Length(length) => Constraint::Length(length),
+        PanelSize::Percentage(percent) => Constraint::Percentage(percent),
+        PanelSize::Ratio(ratio) => Constraint::Fill(ratio),
+        PanelSize::Min(min) => Constraint::Min(min),
+    }
+}
+
+fn is_available(panel: Panel, system_info: &SystemInfo) -> bool {
+    match panel {
+        Panel::Battery => system_info.power.has_battery(),
+        _ => true,
+    }
+}
+
+fn render_panel(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    vpn_status: &VpnStatus,
+    panel: Panel,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    match panel {
+        Panel::CpuGauge => render_cpu_gauge(frame, system_info, area),
+        Panel::MemoryGauge => render_memory_gauge(frame, system_info, area),
+        Panel::Scheduler => render_stats_strip(frame, system_info, area),
+        Panel::CpuChart => {
+            render_cpu_graph(frame, app, area);
+            hits.cpu_chart = Some(area);
+        }
+        Panel::MemoryChart => {
+            render_memory_graph(frame, app, area);
+            hits.memory_chart = Some(area);
+        }
+        Panel::Temperatures => render_temperatures(frame, system_info, area),
+        Panel::Battery => super::power::render_battery(frame, &system_info.power, area),
+        Panel::TopProcesses => render_top_processes(frame, system_info, area),
+        Panel::Categories => render_categories(frame, system_info, area),
+        Panel::Network => render_network(frame, system_info, area),
+        Panel::Vpn => render_vpn_badge(frame, vpn_status, area),
+        Panel::Disks => render_disks(frame, app, system_info, area),
+    }
+}
+
+/// Fine up to `warning`, then the warning and critical colors.
+pub(super) fn usage_color(value: f64, warning: f64, critical: f64) -> Color {
+    let theme = theme::current();
+    if value > critical {
+        theme.critical
+    } else if value > warning {
+        theme.warning
+    } else {
+        theme.good
+    }
+}
+
+fn render_cpu_gauge(frame: &mut Frame, system_info: &SystemInfo, area: Rect) {
+    let theme = theme::current();
+    let cpu_usage = system_info.get_cpu_usage();
 
     let cpu_gauge = Gauge::default()
         .block(Block::default().borders(Borders::ALL).title(" CPU Usage "))
         .gauge_style(
             Style::default()
-                .fg(if cpu_usage > 80.0 {
-                    Color::Red
-                } else if cpu_usage > 50.0 {
-                    Color::Yellow
-                } else {
-                    Color::Green
-                })
-                .bg(Color::Black),
+                .fg(usage_color(cpu_usage as f64, 50.0, 80.0))
+                .bg(theme.gauge_bg),
         )
         .percent(cpu_usage as u16)
         .label(format!("{:.1}%", cpu_usage));
 
+    frame.render_widget(cpu_gauge, area);
+}
+
+fn render_memory_gauge(frame: &mut Frame, system_info: &SystemInfo, area: Rect) {
+    let theme = theme::current();
+    let mem_percent = system_info.get_memory_percentage();
+    let (used_mem, total_mem) = system_info.get_memory_usage();
+
     let memory_gauge = Gauge::default()
         .block(Block::default().borders(Borders::ALL).title(format!(
             " Memory: {} / {} ",
@@ -63,120 +200,299 @@ fn render_cpu_memory_bars(frame: &mut Frame, system_info: &SystemInfo, area: Rec
         )))
         .gauge_style(
             Style::default()
-                .fg(if mem_percent > 80.0 {
-                    Color::Red
-                } else if mem_percent > 50.0 {
-                    Color::Yellow
-                } else {
-                    Color::Green
-                })
-                .bg(Color::Black),
+                .fg(usage_color(mem_percent, 50.0, 80.0))
+                .bg(theme.gauge_bg),
         )
         .percent(mem_percent as u16)
         .label(format!("{:.1}%", mem_percent));
 
-    frame.render_widget(cpu_gauge, chunks[0]);
-    frame.render_widget(memory_gauge, chunks[1]);
+    frame.render_widget(memory_gauge, area);
 }
 
-fn render_cpu_graph(frame: &mut Frame, app: &App, area: Rect) {
-    let data: Vec<(f64, f64)> = app
-        .cpu_history
-        .iter()
-        .enumerate()
-        .map(|(i, &val)| (i as f64, val as f64))
-        .collect();
+/// Colour for a value measured against the number of CPUs: fine below 70%
+/// of them, busy up to all of them, overloaded past that.
+fn per_cpu_color(value: f64, cpus: usize) -> Color {
+    let theme = theme::current();
+    let ratio = value / cpus.max(1) as f64;
+    if ratio > 1.0 {
+        theme.critical
+    } else if ratio > 0.7 {
+        theme.warning
+    } else {
+        theme.good
+    }
+}
 
-    let dataset = Dataset::default()
-        .name("CPU %")
-        .marker(symbols::Marker::Braille)
-        .graph_type(GraphType::Line)
-        .style(Style::default().fg(Color::Cyan))
-        .data(&data);
+fn format_count_rate(per_second: f64) -> String {
+    if per_second >= 1_000_000.0 {
+        format!("{:.1}M/s", per_second / 1_000_000.0)
+    } else if per_second >= 1_000.0 {
+        format!("{:.1}k/s", per_second / 1_000.0)
+    } else {
+        format!("{:.0}/s", per_second)
+    }
+}
 
-    let max_y = app
-        .cpu_history
-        .iter()
-        .fold(100.0f32, |max, &val| max.max(val));
+fn trend_span(trend: Trend) -> Span<'static> {
+    let theme = theme::current();
+    let color = match trend {
+        Trend::Rising => theme.warning,
+        Trend::Falling => theme.accent,
+        Trend::Steady => theme.muted,
+    };
+    Span::styled(trend.arrow().to_string(), Style::default().fg(color))
+}
 
-    let chart = Chart::new(vec![dataset])
-        .block(
-            Block::default()
-                .title(" CPU Usage History ")
-                .borders(Borders::ALL),
-        )
-        .x_axis(
-            Axis::default()
-                .style(Style::default().fg(Color::Gray))
-                .bounds([0.0, app.history_size as f64]),
-        )
-        .y_axis(
-            Axis::default()
-                .style(Style::default().fg(Color::Gray))
-                .labels(vec![Line::from("0"), Line::from("50"), Line::from("100")])
-                .bounds([0.0, max_y as f64]),
-        );
+fn render_stats_strip(frame: &mut Frame, system_info: &SystemInfo, area: Rect) {
+    let theme = theme::current();
+    let stats = &system_info.scheduler;
+    let cpus = system_info.system.cpus().len();
+    let label = |text: &str| Span::styled(text.to_string(), Style::default().fg(theme.muted));
+    let separator = || Span::styled(" | ", Style::default().fg(theme.dim));
+
+    let (one, five, fifteen) = stats.load;
+    let mut spans = vec![
+        label("Load "),
+        Span::styled(
+            format!("{:.2}", one),
+            Style::default()
+                .fg(per_cpu_color(one, cpus))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        trend_span(stats.load_trend()),
+        Span::styled(
+            format!(" {:.2} {:.2}", five, fifteen),
+            Style::default().fg(theme.text),
+        ),
+        separator(),
+        label("Run queue "),
+        Span::styled(
+            format!("{}", stats.running),
+            Style::default()
+                .fg(per_cpu_color(stats.running as f64, cpus))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("/{} cpus", cpus), Style::default().fg(theme.text)),
+        separator(),
+        label("Blocked "),
+        Span::styled(
+            stats.blocked.to_string(),
+            Style::default().fg(if stats.blocked > 0 {
+                theme.warning
+            } else {
+                theme.text
+            }),
+        ),
+        separator(),
+        label("Tasks "),
+        Span::styled(
+            stats.total_tasks.to_string(),
+            Style::default().fg(theme.text),
+        ),
+    ];
+
+    for (name, rate) in [
+        ("Ctx sw ", &stats.context_switches),
+        ("Intr ", &stats.interrupts),
+        ("Forks ", &stats.forks),
+    ] {
+        spans.push(separator());
+        spans.push(label(name));
+        spans.push(Span::styled(
+            format_count_rate(rate.per_second),
+            Style::default().fg(theme.text),
+        ));
+        spans.push(Span::raw(" "));
+        spans.push(trend_span(rate.trend()));
+    }
+
+    let paragraph = Paragraph::new(Line::from(spans))
+        .block(Block::default().borders(Borders::ALL).title(" Scheduler "));
+    frame.render_widget(paragraph, area);
+}
 
-    frame.render_widget(chart, area);
+fn render_cpu_graph(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = theme::current();
+    let chart = HistoryChart {
+        name: "CPU Usage History",
+        legend: "CPU %",
+        color: theme.accent,
+    };
+    chart.render(frame, app, &app.cpu_history, area);
 }
 
 fn render_memory_graph(frame: &mut Frame, app: &App, area: Rect) {
-    let data: Vec<(f64, f64)> = app
-        .memory_history
-        .iter()
-        .enumerate()
-        .map(|(i, &val)| (i as f64, val))
-        .collect();
+    let theme = theme::current();
+    let chart = HistoryChart {
+        name: "Memory Usage History",
+        legend: "Memory %",
+        color: theme.secondary,
+    };
+    chart.render(frame, app, &app.memory_history, area);
+}
+
+/// A percentage over time, drawn across the window picked with the zoom and
+/// pan keys.
+struct HistoryChart {
+    name: &'static str,
+    legend: &'static str,
+    color: Color,
+}
+
+impl HistoryChart {
+    fn render(&self, frame: &mut Frame, app: &App, series: &TimeSeries, area: Rect) {
+        let theme = theme::current();
+        let (from, to) = app.chart_range();
+        let data = series.range(from, to);
+        let summary = series.summary(from, to);
+
+        // Keep 0-100% in view and only grow past it
+        let max_y = summary.map_or(100.0, |summary| summary.max.max(100.0));
+        let y_labels = [0.0, max_y / 2.0, max_y].map(|value| format!("{:.0}", value));
+        let x_labels = [from, (from + to) / 2.0, to].map(|time| format_chart_time(time, app));
 
-    let dataset = Dataset::default()
-        .name("Memory %")
-        .marker(symbols::Marker::Braille)
-        .graph_type(GraphType::Line)
-        .style(Style::default().fg(Color::Magenta))
-        .data(&data);
+        let plot = plot_area(area, &y_labels, &x_labels[0]);
+        let hovered = hovered_sample(app, plot, (from, to), &data);
+        let crosshair: Vec<(f64, f64)> = hovered
+            .map(|(time, _)| vec![(time, 0.0), (time, max_y)])
+            .unwrap_or_default();
+        let hover_point: Vec<(f64, f64)> = hovered.into_iter().collect();
+
+        let datasets = vec![
+            Dataset::default()
+                .name(self.legend)
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(self.color))
+                .data(&data),
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.dim))
+                .data(&crosshair),
+            Dataset::default()
+                .marker(symbols::Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(theme.highlight))
+                .data(&hover_point),
+        ];
+
+        let mut title = format!(" {} | {} ", self.name, app.chart_window.name());
+        if app.chart_end.is_some() {
+            title.push_str("| paused, End: live ");
+        }
+        if let Some((time, value)) = hovered {
+            title.push_str(&format!(
+                "| {}: {:.1}% ",
+                format_chart_time(time, app),
+                value
+            ));
+        }
+        let stats = summary.map_or(String::new(), |summary| {
+            format!(
+                " min {:.1}% avg {:.1}% max {:.1}% ",
+                summary.min, summary.avg, summary.max
+            )
+        });
+
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_bottom(stats)
+                    .title_bottom(Line::from(" +/-: zoom | [ ]: pan ").right_aligned())
+                    .borders(Borders::ALL),
+            )
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(theme.muted))
+                    .labels(x_labels.map(Line::from).to_vec())
+                    .bounds([from, to]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(theme.muted))
+                    .labels(y_labels.map(Line::from).to_vec())
+                    .bounds([0.0, max_y]),
+            );
+
+        frame.render_widget(chart, area);
+    }
+}
+
+/// Wall-clock time for the chart axis, with seconds unless a whole day is
+/// shown.
+fn format_chart_time(time: f64, app: &App) -> String {
+    use chrono::{DateTime, Local};
+
+    let Some(time) = DateTime::from_timestamp(time as i64, 0) else {
+        return String::new();
+    };
+    let format = if app.chart_window == ChartWindow::Day {
+        "%H:%M"
+    } else {
+        "%H:%M:%S"
+    };
+    time.with_timezone(&Local).format(format).to_string()
+}
 
-    let max_y = app
-        .memory_history
+/// Where the Chart widget plots its data inside `area`: right of the y-axis
+/// labels (or the overhang of the first x label) and the axis line, above
+/// the x axis and its labels.
+fn plot_area(area: Rect, y_labels: &[String], first_x_label: &str) -> Rect {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let labels_width = y_labels
         .iter()
-        .fold(100.0f64, |max, &val| max.max(val));
+        .map(|label| label.chars().count() as u16)
+        .max()
+        .unwrap_or(0)
+        .max((first_x_label.chars().count() as u16).saturating_sub(1))
+        .min(inner.width / 3);
+    let left = labels_width + 1;
 
-    let chart = Chart::new(vec![dataset])
-        .block(
-            Block::default()
-                .title(" Memory Usage History ")
-                .borders(Borders::ALL),
-        )
-        .x_axis(
-            Axis::default()
-                .style(Style::default().fg(Color::Gray))
-                .bounds([0.0, app.history_size as f64]),
-        )
-        .y_axis(
-            Axis::default()
-                .style(Style::default().fg(Color::Gray))
-                .labels(vec![Line::from("0"), Line::from("50"), Line::from("100")])
-                .bounds([0.0, max_y]),
-        );
+    Rect {
+        x: inner.x + left,
+        y: inner.y,
+        width: inner.width.saturating_sub(left),
+        height: inner.height.saturating_sub(2),
+    }
+}
+
+/// The sample closest in time to the mouse, if the cursor is over the plot.
+fn hovered_sample(
+    app: &App,
+    plot: Rect,
+    (from, to): (f64, f64),
+    data: &[(f64, f64)],
+) -> Option<(f64, f64)> {
+    let (column, row) = app.mouse_position?;
+    if !plot.contains(Position::new(column, row)) {
+        return None;
+    }
 
-    frame.render_widget(chart, area);
+    let ratio = (column - plot.x) as f64 / plot.width.saturating_sub(1).max(1) as f64;
+    let time = from + ratio * (to - from);
+
+    let index = data.partition_point(|(sample_time, _)| *sample_time < time);
+    [index.checked_sub(1), Some(index)]
+        .into_iter()
+        .flatten()
+        .filter_map(|index| data.get(index).copied())
+        .min_by(|a, b| (a.0 - time).abs().total_cmp(&(b.0 - time).abs()))
 }
 
 fn render_temperatures(frame: &mut Frame, system_info: &SystemInfo, area: Rect) {
+    let theme = theme::current();
     let temps = system_info.get_temperatures();
 
     let items: Vec<ListItem> = temps
         .iter()
         .map(|(label, temp)| {
-            let color = if *temp > 80.0 {
-                Color::Red
-            } else if *temp > 60.0 {
-                Color::Yellow
-            } else {
-                Color::Green
-            };
+            let color = usage_color(*temp as f64, 60.0, 80.0);
 
             ListItem::new(Line::from(vec![
-                Span::styled(format!("{:30}", label), Style::default().fg(Color::White)),
+                Span::styled(format!("{:30}", label), Style::default().fg(theme.text)),
                 Span::styled(format!("{:>6.1}°C", temp), Style::default().fg(color)),
             ]))
         })
@@ -190,3 +506,242 @@ fn render_temperatures(frame: &mut Frame, system_info: &SystemInfo, area: Rect)
 
     frame.render_widget(list, area);
 }
+
+fn render_top_processes(frame: &mut Frame, system_info: &SystemInfo, area: Rect) {
+    let theme = theme::current();
+    let by_cpu = &system_info.overview_usage.by_cpu;
+    let by_memory = &system_info.overview_usage.by_memory;
+
+    // The memory list sits next to the CPU one when there's room for both
+    let show_memory = area.width >= 2 * MIN_PANEL_WIDTH;
+    let count = area.height.saturating_sub(3) as usize;
+
+    let name = |process: &ProcessUsage| {
+        Cell::from(process.name.clone()).style(Style::default().fg(process.category.color()))
+    };
+    let rows = (0..count).map(|index| {
+        let mut cells = Vec::new();
+        if let Some(process) = by_cpu.get(index) {
+            cells.push(name(process));
+            cells.push(
+                Cell::from(format!("{:>6.1}%", process.cpu_usage))
+                    .style(Style::default().fg(theme.highlight)),
+            );
+        }
+        if show_memory && let Some(process) = by_memory.get(index) {
+            cells.push(name(process));
+            cells.push(
+                Cell::from(format!("{:>10}", format_bytes(process.memory)))
+                    .style(Style::default().fg(theme.secondary)),
+            );
+        }
+        Row::new(cells)
+    });
+
+    let mut header = vec!["CPU", ""];
+    let mut widths = vec![Constraint::Fill(1), Constraint::Length(7)];
+    if show_memory {
+        header.extend(["Memory", ""]);
+        widths.extend([Constraint::Fill(1), Constraint::Length(10)]);
+    }
+    let header = Row::new(header).style(
+        Style::default()
+            .fg(theme.muted)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .column_spacing(1)
+        .block(
+            Block::default()
+                .title(" Top Processes ")
+                .borders(Borders::ALL),
+        );
+
+    frame.render_widget(table, area);
+}
+
+/// One line per resource, split into the share each process category
+/// takes, with a legend underneath.
+fn render_categories(frame: &mut Frame, system_info: &SystemInfo, area: Rect) {
+    const LABEL_WIDTH: u16 = 5;
+
+    let theme = theme::current();
+    let usage = &system_info.overview_usage.categories;
+    let (_, total_memory) = system_info.get_memory_usage();
+
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let bar_width = inner.width.saturating_sub(LABEL_WIDTH + 7) as usize;
+
+    let cpu_shares: Vec<f64> = usage.iter().map(|(_, cpu, _)| cpu / 100.0).collect();
+    let memory_shares: Vec<f64> = usage
+        .iter()
+        .map(|(_, _, memory)| {
+            if total_memory > 0 {
+                *memory as f64 / total_memory as f64
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    let bar = |label: &str, shares: &[f64]| {
+        let mut spans = vec![Span::styled(
+            format!("{:<width$}", label, width = LABEL_WIDTH as usize),
+            Style::default().fg(theme.muted),
+        )];
+        // Segment edges come from the running total so rounding doesn't
+        // drift along the bar.
+        let mut covered = 0.0;
+        let mut drawn = 0;
+        for ((category, _, _), share) in usage.iter().zip(shares) {
+            covered += share;
+            let edge = ((covered.min(1.0) * bar_width as f64).round() as usize).min(bar_width);
+            if edge > drawn {
+                spans.push(Span::styled(
+                    "█".repeat(edge - drawn),
+                    Style::default().fg(category.color()),
+                ));
+                drawn = edge;
+            }
+        }
+        spans.push(Span::styled(
+            "░".repeat(bar_width - drawn),
+            Style::default().fg(theme.dim),
+        ));
+        spans.push(Span::styled(
+            format!(" {:>5.1}%", covered.min(1.0) * 100.0),
+            Style::default().fg(theme.text),
+        ));
+        Line::from(spans)
+    };
+
+    let mut legend = Vec::new();
+    for (category, _, _) in usage {
+        legend.push(Span::styled("■ ", Style::default().fg(category.color())));
+        legend.push(Span::styled(
+            format!("{} ", category.name()),
+            Style::default().fg(theme.muted),
+        ));
+    }
+
+    let paragraph = Paragraph::new(vec![
+        bar("CPU", &cpu_shares),
+        bar("Mem", &memory_shares),
+        Line::from(legend),
+    ])
+    .block(Block::default().title(" Categories ").borders(Borders::ALL));
+
+    frame.render_widget(paragraph, area);
+}
+
+fn render_network(frame: &mut Frame, system_info: &SystemInfo, area: Rect) {
+    let theme = theme::current();
+    let items: Vec<ListItem> = system_info
+        .network_rates()
+        .into_iter()
+        .map(|(name, received, transmitted)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:12.12}", name), Style::default().fg(theme.text)),
+                Span::styled(
+                    format!("↓ {:>10}", format_rate(received)),
+                    Style::default().fg(theme.good),
+                ),
+                Span::styled(
+                    format!("  ↑ {:>10}", format_rate(transmitted)),
+                    Style::default().fg(theme.accent),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(Block::default().title(" Network ").borders(Borders::ALL));
+
+    frame.render_widget(list, area);
+}
+
+fn render_vpn_badge(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect) {
+    let theme = theme::current();
+    let mut spans = if vpn_status.connected {
+        vec![Span::styled(
+            "● CONNECTED",
+            Style::default().fg(theme.good).add_modifier(Modifier::BOLD),
+        )]
+    } else {
+        vec![Span::styled(
+            "○ DISCONNECTED",
+            Style::default()
+                .fg(theme.critical)
+                .add_modifier(Modifier::BOLD),
+        )]
+    };
+
+    let details = [
+        (vpn_status.provider != VpnProvider::Unknown)
+            .then(|| vpn_status.provider.name().to_string()),
+        vpn_status.server.clone(),
+        vpn_status.country.clone(),
+        vpn_status.ip.clone(),
+    ];
+    for detail in details.into_iter().flatten() {
+        spans.push(Span::styled(" | ", Style::default().fg(theme.dim)));
+        spans.push(Span::styled(detail, Style::default().fg(theme.text)));
+    }
+
+    let paragraph = Paragraph::new(Line::from(spans))
+        .block(Block::default().borders(Borders::ALL).title(" VPN "));
+    frame.render_widget(paragraph, area);
+}
+
+fn render_disks(frame: &mut Frame, app: &App, system_info: &SystemInfo, area: Rect) {
+    const BAR_WIDTH: usize = 20;
+
+    let theme = theme::current();
+    let settings = &app.config.disks;
+    let items: Vec<ListItem> = system_info
+        .get_disk_info()
+        .iter()
+        .filter(|disk| settings.show_pseudo || !disk.is_pseudo(&settings.hidden_filesystems))
+        .map(|disk| {
+            let used = disk.total_space.saturating_sub(disk.available_space);
+            let percent = if disk.total_space > 0 {
+                used as f64 / disk.total_space as f64 * 100.0
+            } else {
+                0.0
+            };
+            let filled = ((percent / 100.0 * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:20.20} ", disk.mount_point),
+                    Style::default().fg(theme.text),
+                ),
+                Span::styled(
+                    "█".repeat(filled),
+                    Style::default().fg(usage_color(percent, 75.0, 90.0)),
+                ),
+                Span::styled(
+                    "░".repeat(BAR_WIDTH - filled),
+                    Style::default().fg(theme.dim),
+                ),
+                Span::styled(
+                    format!(" {:>5.1}%", percent),
+                    Style::default().fg(usage_color(percent, 75.0, 90.0)),
+                ),
+                Span::styled(
+                    format!(
+                        " {} / {}",
+                        format_bytes(used),
+                        format_bytes(disk.total_space)
+                    ),
+                    Style::default().fg(theme.muted),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(Block::default().title(" Disks ").borders(Borders::ALL));
+
+    frame.render_widget(list, area);
+}
@@ -0,0 +1,176 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+};
+
+use crate::{
+    power::{Battery, BatteryState, PowerMonitor},
+    system::format_uptime,
+    theme,
+};
+
+pub fn render_battery(frame: &mut Frame, power: &PowerMonitor, area: Rect) {
+    let ac = match power.adapters.iter().find(|adapter| adapter.online) {
+        Some(adapter) => format!("| on AC ({}) ", adapter.name),
+        None if power.adapters.is_empty() => String::new(),
+        None => "| on battery ".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Battery {}", ac));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let info_height = (power.batteries.len() * 3) as u16;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(info_height), Constraint::Min(0)])
+        .split(inner);
+
+    let lines: Vec<Line> = power.batteries.iter().flat_map(battery_lines).collect();
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    // The chart follows the first battery, laptops with two are rare enough
+    if let Some(battery) = power.batteries.first() {
+        render_history(frame, power, battery, chunks[1]);
+    }
+}
+
+fn battery_lines(battery: &Battery) -> Vec<Line<'_>> {
+    let theme = theme::current();
+    let color = if battery.percent < 15.0 {
+        theme.critical
+    } else if battery.percent < 35.0 {
+        theme.warning
+    } else {
+        theme.good
+    };
+
+    let bar_width = 20;
+    let filled = ((battery.percent / 100.0) * bar_width as f64).round() as usize;
+    let bar = format!("[{}{}]", "=".repeat(filled), " ".repeat(bar_width - filled));
+
+    let state_color = match battery.state {
+        BatteryState::Charging | BatteryState::Full => theme.good,
+        BatteryState::Discharging => theme.highlight,
+        BatteryState::NotCharging | BatteryState::Unknown => theme.muted,
+    };
+
+    let rate = match (battery.power, battery.state) {
+        (Some(power), BatteryState::Charging) => format!("  +{:.1} W", power),
+        (Some(power), BatteryState::Discharging) => format!("  -{:.1} W", power),
+        _ => String::new(),
+    };
+    let remaining = match (battery.time_remaining(), battery.state) {
+        (Some(time), BatteryState::Charging) => {
+            format!("  {} until full", format_uptime(time.as_secs()))
+        }
+        (Some(time), _) => format!("  {} left", format_uptime(time.as_secs())),
+        _ => String::new(),
+    };
+
+    let mut details = Vec::new();
+    if let Some(health) = battery.health_percent() {
+        details.push(format!("health {:.0}%", health));
+    }
+    if let (Some(full), Some(design)) = (battery.energy_full, battery.energy_full_design) {
+        details.push(format!("{:.1} / {:.1} Wh", full, design));
+    }
+    if let Some(cycles) = battery.cycle_count {
+        details.push(format!("{} cycles", cycles));
+    }
+    if let Some(model) = battery.model.as_ref().or(battery.technology.as_ref()) {
+        details.push(model.clone());
+    }
+
+    vec![
+        Line::from(vec![
+            Span::styled(
+                format!("{:6}", battery.name),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(bar, Style::default().fg(color)),
+            Span::styled(
+                format!(" {:5.1}%", battery.percent),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("      "),
+            Span::styled(battery.state.name(), Style::default().fg(state_color)),
+            Span::styled(rate, Style::default().fg(theme.text)),
+            Span::styled(remaining, Style::default().fg(theme.muted)),
+        ]),
+        Line::from(Span::styled(
+            format!("      {}", details.join(" | ")),
+            Style::default().fg(theme.muted),
+        )),
+    ]
+}
+
+fn render_history(frame: &mut Frame, power: &PowerMonitor, battery: &Battery, area: Rect) {
+    let theme = theme::current();
+    let Some(history) = power.history.get(&battery.name) else {
+        return;
+    };
+
+    // Power shares the 0-100 axis with the charge, scaled to the highest
+    // rate seen
+    let max_power = history
+        .iter()
+        .fold(0.0f64, |max, (_, power)| max.max(*power));
+    let charge: Vec<(f64, f64)> = history
+        .iter()
+        .enumerate()
+        .map(|(i, (percent, _))| (i as f64, *percent))
+        .collect();
+    let rate: Vec<(f64, f64)> = history
+        .iter()
+        .enumerate()
+        .map(|(i, (_, power))| {
+            let scaled = if max_power > 0.0 {
+                power / max_power * 100.0
+            } else {
+                0.0
+            };
+            (i as f64, scaled)
+        })
+        .collect();
+
+    let power_name = format!("W (max {:.1})", max_power);
+    let datasets = vec![
+        Dataset::default()
+            .name("Charge %")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.good))
+            .data(&charge),
+        Dataset::default()
+            .name(power_name)
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.highlight))
+            .data(&rate),
+    ];
+
+    let chart = Chart::new(datasets)
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .bounds([0.0, power.history_size as f64]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .labels(vec![Line::from("0"), Line::from("50"), Line::from("100")])
+                .bounds([0.0, 100.0]),
+        );
+
+    frame.render_widget(chart, area);
+}
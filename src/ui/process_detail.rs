@@ -0,0 +1,271 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Sparkline, Wrap},
+};
+
+use crate::{
+    app::App,
+    system::{
+        ProcessDetail, SystemInfo, format_bytes, format_rate, format_timestamp, format_uptime,
+    },
+    theme,
+};
+
+pub fn render_process_detail(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    pid: u32,
+    area: Rect,
+) {
+    let theme = theme::current();
+    let Some(detail) = system_info
+        .process_detail
+        .as_ref()
+        .filter(|detail| detail.info.pid == pid)
+    else {
+        let paragraph = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("  Process {} has exited", pid),
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::ITALIC),
+            )),
+        ])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Process {} | Press ESC to go back ", pid)),
+        );
+        frame.render_widget(paragraph, area);
+        return;
+    };
+
+    let color = detail.info.category.color();
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+        .title(format!(
+            " Process {} - {} ({}) | Press ESC to go back ",
+            pid,
+            detail.info.name,
+            detail.info.category.name()
+        ))
+        .title_style(Style::default().fg(color).add_modifier(Modifier::BOLD));
+    let inner = outer.inner(area);
+    frame.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(inner);
+
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(rows[0]);
+
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(rows[1]);
+
+    render_overview(frame, detail, top[0]);
+    render_sparklines(frame, app, detail, top[1]);
+    render_environment(frame, app, detail, bottom[0]);
+    render_threads(frame, app, detail, bottom[1]);
+}
+
+fn field<'a>(label: &str, value: String) -> Line<'a> {
+    let theme = theme::current();
+    Line::from(vec![
+        Span::styled(
+            format!("{:13}", label),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(value, Style::default().fg(theme.text)),
+    ])
+}
+
+fn unavailable(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "n/a (permission denied)".to_string())
+}
+
+fn render_overview(frame: &mut Frame, detail: &ProcessDetail, area: Rect) {
+    let info = &detail.info;
+
+    let parents = if detail.parent_chain.is_empty() {
+        "-".to_string()
+    } else {
+        detail
+            .parent_chain
+            .iter()
+            .map(|(pid, name)| format!("{}({})", name, pid))
+            .collect::<Vec<_>>()
+            .join(" ← ")
+    };
+
+    let maps = detail.memory_maps.map(|maps| {
+        format!(
+            "{} mappings ({} file, {} anon) | RSS {} | PSS {} | Swap {}",
+            maps.mappings,
+            maps.file_backed,
+            maps.anonymous,
+            format_bytes(maps.rss),
+            format_bytes(maps.pss),
+            format_bytes(maps.swap)
+        )
+    });
+
+    let lines = vec![
+        field("Command:", info.command.clone()),
+        field("Executable:", unavailable(detail.exe.clone())),
+        field("Working dir:", unavailable(detail.cwd.clone())),
+        field("User/Group:", format!("{} / {}", info.user, detail.group)),
+        field(
+            "State:",
+            format!(
+                "{} | nice {} | prio {}",
+                info.state, info.nice, info.priority
+            ),
+        ),
+        field(
+            "Started:",
+            format!(
+                "{} (running {})",
+                format_timestamp(info.start_time),
+                format_uptime(info.run_time)
+            ),
+        ),
+        field("Parents:", parents),
+        field("Threads:", info.threads.to_string()),
+        field(
+            "Open FDs:",
+            unavailable(detail.open_fds.map(|fds| fds.to_string())),
+        ),
+        field(
+            "Memory:",
+            format!(
+                "{} resident | {} virtual",
+                format_bytes(info.memory),
+                format_bytes(info.virtual_memory)
+            ),
+        ),
+        field("Memory maps:", unavailable(maps)),
+        field(
+            "Disk I/O:",
+            format!(
+                "read {} ({}) | written {} ({})",
+                format_bytes(info.disk_read),
+                format_rate(info.disk_read_rate),
+                format_bytes(info.disk_written),
+                format_rate(info.disk_write_rate)
+            ),
+        ),
+        field(
+            "Network:",
+            unavailable(info.network.map(|net| {
+                format!(
+                    "{} sockets | in {} ({}) | out {} ({})",
+                    net.usage.sockets,
+                    format_bytes(net.usage.bytes_received),
+                    format_rate(net.received_rate),
+                    format_bytes(net.usage.bytes_sent),
+                    format_rate(net.sent_rate)
+                )
+            })),
+        ),
+        field("Cgroup:", unavailable(detail.cgroup.clone())),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().title(" Details ").borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, area);
+}
+
+fn render_sparklines(frame: &mut Frame, app: &App, detail: &ProcessDetail, area: Rect) {
+    let theme = theme::current();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let cpu_data: Vec<u64> = app.detail_cpu_history.iter().copied().collect();
+    let cpu = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!(" CPU {:.1}% ", detail.info.cpu_usage))
+                .borders(Borders::ALL),
+        )
+        .data(&cpu_data)
+        .style(Style::default().fg(theme.accent));
+
+    let memory_data: Vec<u64> = app.detail_memory_history.iter().copied().collect();
+    let memory = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!(" Memory {} ", format_bytes(detail.info.memory)))
+                .borders(Borders::ALL),
+        )
+        .data(&memory_data)
+        .style(Style::default().fg(theme.secondary));
+
+    frame.render_widget(cpu, chunks[0]);
+    frame.render_widget(memory, chunks[1]);
+}
+
+fn render_environment(frame: &mut Frame, app: &App, detail: &ProcessDetail, area: Rect) {
+    let theme = theme::current();
+    let items: Vec<ListItem> = detail
+        .environ
+        .iter()
+        .skip(app.detail_scroll)
+        .map(|var| {
+            let (key, value) = var.split_once('=').unwrap_or((var, ""));
+            ListItem::new(Line::from(vec![
+                Span::styled(key.to_string(), Style::default().fg(theme.highlight)),
+                Span::styled("=", Style::default().fg(theme.dim)),
+                Span::raw(value.to_string()),
+            ]))
+        })
+        .collect();
+
+    let title = if detail.environ.is_empty() {
+        " Environment (not readable) ".to_string()
+    } else {
+        format!(" Environment ({}) ", detail.environ.len())
+    };
+
+    let list = List::new(items).block(Block::default().title(title).borders(Borders::ALL));
+    frame.render_widget(list, area);
+}
+
+fn render_threads(frame: &mut Frame, app: &App, detail: &ProcessDetail, area: Rect) {
+    let theme = theme::current();
+    let items: Vec<ListItem> = detail
+        .threads
+        .iter()
+        .skip(app.detail_scroll)
+        .map(|(tid, name)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:8} ", tid), Style::default().fg(theme.accent)),
+                Span::styled(name.clone(), Style::default().fg(theme.text)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .title(format!(" Threads ({}) ", detail.threads.len()))
+            .borders(Borders::ALL),
+    );
+    frame.render_widget(list, area);
+}
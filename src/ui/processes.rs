@@ -1,95 +1,58 @@
+use crate::{
+    app::{App, HitAreas, ProcessColumn},
+    config::ColumnConfig,
+    process_view::ProcessView,
+    system::{
+        ProcessCategory, ProcessInfo, SystemInfo, format_bytes, format_rate, format_timestamp,
+        format_uptime,
+    },
+    theme,
+};
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem},
-};
-use std::collections::HashMap;
-
-use crate::{
-    app::{App, ProcessSort},
-    system::{ProcessCategory, ProcessInfo, SystemInfo, format_bytes},
+    widgets::{Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table, TableState},
 };
 
-pub fn render_processes(frame: &mut Frame, app: &App, system_info: &SystemInfo, area: Rect) {
-    let processes = system_info.get_processes();
-
-    if app.show_all_processes {
-        render_all_processes(frame, app, system_info, area);
-        if app.command_mode {
-            render_command_prompt(frame, app, area);
-        }
-        return;
+pub fn render_processes(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    if let Some(pid) = app.process_detail {
+        super::process_detail::render_process_detail(frame, app, system_info, pid, area);
+    } else if app.show_all_processes {
+        render_all_processes(frame, app, system_info, area, hits);
+    } else if app.category_expanded {
+        render_expanded_category(frame, app, system_info, area, hits);
+    } else {
+        render_category_grid(frame, app, &system_info.process_view, area, hits);
     }
 
-    if app.category_expanded {
-        render_expanded_category(frame, app, system_info, area);
-        if app.command_mode {
-            render_command_prompt(frame, app, area);
-        }
-        return;
+    if app.column_picker {
+        render_column_picker(frame, app, area);
     }
-
-    let mut categorized: HashMap<ProcessCategory, Vec<_>> = HashMap::new();
-    for process in processes.iter() {
-        categorized
-            .entry(process.category)
-            .or_insert_with(Vec::new)
-            .push(process.clone());
+    if app.command_mode {
+        render_command_prompt(frame, app, area);
     }
+}
 
-    for processes_in_category in categorized.values_mut() {
-        match app.process_sort {
-            ProcessSort::Cpu => processes_in_category.sort_by(|a, b| {
-                let cmp = b
-                    .cpu_usage
-                    .partial_cmp(&a.cpu_usage)
-                    .unwrap_or(std::cmp::Ordering::Equal);
-                if app.sort_ascending {
-                    cmp.reverse()
-                } else {
-                    cmp
-                }
-            }),
-            ProcessSort::Memory => processes_in_category.sort_by(|a, b| {
-                let cmp = b.memory.cmp(&a.memory);
-                if app.sort_ascending {
-                    cmp.reverse()
-                } else {
-                    cmp
-                }
-            }),
-            ProcessSort::Name => processes_in_category.sort_by(|a, b| {
-                let cmp = a.name.cmp(&b.name);
-                if app.sort_ascending {
-                    cmp
-                } else {
-                    cmp.reverse()
-                }
-            }),
-            ProcessSort::Pid => processes_in_category.sort_by(|a, b| {
-                let cmp = a.pid.cmp(&b.pid);
-                if app.sort_ascending {
-                    cmp
-                } else {
-                    cmp.reverse()
-                }
-            }),
-        }
+fn render_category_grid(
+    frame: &mut Frame,
+    app: &App,
+    view: &ProcessView,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    if super::is_narrow(area) || super::is_short(area) {
+        render_category_strip(frame, app, view, area, hits);
+        return;
     }
 
-    let category_order = vec![
-        ProcessCategory::System,
-        ProcessCategory::Browser,
-        ProcessCategory::Development,
-        ProcessCategory::Terminal,
-        ProcessCategory::Editor,
-        ProcessCategory::Media,
-        ProcessCategory::Background,
-        ProcessCategory::User,
-    ];
-
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -109,74 +72,104 @@ pub fn render_processes(frame: &mut Frame, app: &App, system_info: &SystemInfo,
         category_areas.push(row[0]);
         category_areas.push(row[1]);
     }
+    hits.categories = category_areas.clone();
+    hits.category_columns = 2;
 
-    let sort_field = match app.process_sort {
-        ProcessSort::Cpu => "CPU",
-        ProcessSort::Memory => "Memory",
-        ProcessSort::Name => "Name",
-        ProcessSort::Pid => "PID",
-    };
-    let sort_order = if app.sort_ascending {
-        "Asc ↑"
-    } else {
-        "Desc ↓"
-    };
-
-    for (idx, category) in category_order.iter().enumerate() {
+    for (idx, category) in ProcessCategory::ALL.iter().enumerate() {
         if idx < category_areas.len() {
             let is_selected = idx == app.selected_category;
             render_category_box(
                 frame,
+                app,
                 category_areas[idx],
                 *category,
-                categorized.get(category).unwrap_or(&Vec::new()),
+                view.category(*category),
                 is_selected,
-                sort_field,
-                sort_order,
             );
         }
     }
+}
 
-    if app.command_mode {
-        render_command_prompt(frame, app, area);
+/// Compact stand-in for the grid: a one-line category picker above the
+/// selected category's box.
+fn render_category_strip(
+    frame: &mut Frame,
+    app: &App,
+    view: &ProcessView,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let theme = theme::current();
+    let chunks = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).split(area);
+
+    let labels: Vec<String> = ProcessCategory::ALL
+        .iter()
+        .map(|category| {
+            let count = view.category(*category).len();
+            format!(" {} {} ", category.name(), count)
+        })
+        .collect();
+
+    // Start late enough that the selected category is on screen
+    let width = |label: &String| label.chars().count() as u16 + 1;
+    let mut first = 0;
+    while first < app.selected_category
+        && labels[first..=app.selected_category]
+            .iter()
+            .map(width)
+            .sum::<u16>()
+            > chunks[0].width
+    {
+        first += 1;
+    }
+
+    let mut spans = Vec::new();
+    let mut x = chunks[0].x;
+    for (idx, (category, label)) in ProcessCategory::ALL.iter().zip(&labels).enumerate() {
+        if idx < first || x >= chunks[0].right() {
+            hits.categories.push(Rect::default());
+            continue;
+        }
+        let label_width = width(label).min(chunks[0].right() - x);
+        hits.categories
+            .push(Rect::new(x, chunks[0].y, label_width, 1));
+        x += label_width;
+
+        let style = if idx == app.selected_category {
+            theme.selection().fg(category.color())
+        } else {
+            Style::default().fg(category.color())
+        };
+        spans.push(Span::styled(label.clone(), style));
+        spans.push(Span::raw(" "));
     }
+    hits.category_columns = ProcessCategory::ALL.len();
+    frame.render_widget(Paragraph::new(Line::from(spans)), chunks[0]);
+
+    let category = ProcessCategory::ALL[app.selected_category];
+    render_category_box(
+        frame,
+        app,
+        chunks[1],
+        category,
+        view.category(category),
+        true,
+    );
 }
 
-fn render_category_box(
+fn render_category_box<'a>(
     frame: &mut Frame,
+    app: &App,
     area: Rect,
     category: ProcessCategory,
-    processes: &[ProcessInfo],
+    processes: impl ExactSizeIterator<Item = &'a ProcessInfo>,
     is_selected: bool,
-    sort_field: &str,
-    sort_order: &str,
 ) {
-    let items: Vec<ListItem> = processes
-        .iter()
+    let columns = &app.config.processes.columns;
+    let count = processes.len();
+    let rows = processes
         .take(10) // Limit to 10 processes per category in the overview
-        .map(|proc| {
-            ListItem::new(Line::from(vec![
-                Span::styled(
-                    format!("{:6} ", proc.pid),
-                    Style::default().fg(Color::DarkGray),
-                ),
-                Span::styled(
-                    format!("{:20} ", proc.name),
-                    Style::default().fg(Color::White),
-                ),
-                Span::styled(
-                    format!("{:5.1}%", proc.cpu_usage),
-                    Style::default().fg(if proc.cpu_usage > 50.0 {
-                        Color::Red
-                    } else if proc.cpu_usage > 20.0 {
-                        Color::Yellow
-                    } else {
-                        Color::Green
-                    }),
-                ),
-            ]))
-        })
-        .collect();
+        .map(|proc| process_row(proc, columns));
 
     let border_style = if is_selected {
         Style::default()
@@ -188,285 +181,337 @@ fn render_category_box(
 
     let title = if is_selected {
         format!(
-            " {} ({}) | Sort: {} {} ",
+            " {} ({}) | Sort: {} ",
             category.name(),
-            processes.len(),
-            sort_field,
-            sort_order
+            count,
+            sort_label(app)
         )
     } else {
-        format!(" {} ({}) ", category.name(), processes.len())
+        format!(" {} ({}) ", category.name(), count)
     };
 
-    let list = List::new(items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(border_style)
-            .title(title)
-            .title_style(
-                Style::default()
-                    .fg(category.color())
-                    .add_modifier(Modifier::BOLD),
-            ),
-    );
+    let table = Table::new(rows, column_widths(columns))
+        .flex(Flex::Start)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .title(title)
+                .title_style(
+                    Style::default()
+                        .fg(category.color())
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
 
-    frame.render_widget(list, area);
+    frame.render_widget(table, area);
 }
 
-fn render_expanded_category(frame: &mut Frame, app: &App, system_info: &SystemInfo, area: Rect) {
-    let processes = system_info.get_processes();
-
-    let category_order = vec![
-        ProcessCategory::System,
-        ProcessCategory::Browser,
-        ProcessCategory::Development,
-        ProcessCategory::Terminal,
-        ProcessCategory::Editor,
-        ProcessCategory::Media,
-        ProcessCategory::Background,
-        ProcessCategory::User,
-    ];
-
-    let selected_category = category_order[app.selected_category];
-
-    let mut filtered: Vec<ProcessInfo> = processes
-        .into_iter()
-        .filter(|p| p.category == selected_category)
-        .collect();
+fn render_expanded_category(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let selected_category = ProcessCategory::ALL[app.selected_category];
+    let processes = system_info.process_view.category(selected_category);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(selected_category.color())
+                .add_modifier(Modifier::BOLD),
+        )
+        .title(format!(
+            " {} - {} Processes | Sort: {} | Press ESC to go back ",
+            selected_category.name(),
+            processes.len(),
+            sort_label(app)
+        ))
+        .title_style(
+            Style::default()
+                .fg(selected_category.color())
+                .add_modifier(Modifier::BOLD),
+        );
+
+    render_process_table(frame, app, block, processes, area, hits);
+}
 
-    match app.process_sort {
-        ProcessSort::Cpu => filtered.sort_by(|a, b| {
-            let cmp = b
-                .cpu_usage
-                .partial_cmp(&a.cpu_usage)
-                .unwrap_or(std::cmp::Ordering::Equal);
-            if app.sort_ascending {
-                cmp.reverse()
-            } else {
-                cmp
-            }
-        }),
-        ProcessSort::Memory => filtered.sort_by(|a, b| {
-            let cmp = b.memory.cmp(&a.memory);
-            if app.sort_ascending {
-                cmp.reverse()
-            } else {
-                cmp
-            }
-        }),
-        ProcessSort::Name => filtered.sort_by(|a, b| {
-            let cmp = a.name.cmp(&b.name);
-            if app.sort_ascending {
-                cmp
-            } else {
-                cmp.reverse()
-            }
-        }),
-        ProcessSort::Pid => filtered.sort_by(|a, b| {
-            let cmp = a.pid.cmp(&b.pid);
-            if app.sort_ascending {
-                cmp
-            } else {
-                cmp.reverse()
-            }
-        }),
+fn render_all_processes(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let theme = theme::current();
+    let processes = system_info.process_view.all();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .title(format!(
+            " All Processes - {} Total | Sort: {} | Press ESC to go back ",
+            processes.len(),
+            sort_label(app)
+        ))
+        .title_style(
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    render_process_table(frame, app, block, processes.iter(), area, hits);
+}
+
+/// Renders a scrollable process table with a clickable header row. The
+/// highlighted row follows `app.process_scroll`. Only the visible rows are
+/// built, which is what keeps frames cheap with thousands of processes.
+fn render_process_table<'a>(
+    frame: &mut Frame,
+    app: &App,
+    block: Block,
+    processes: impl ExactSizeIterator<Item = &'a ProcessInfo> + Clone,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let theme = theme::current();
+    let columns = &app.config.processes.columns;
+    let inner = block.inner(area);
+
+    let header = Row::new(columns.iter().map(|c| {
+        let is_active = c.column == app.process_sort;
+        let label = if is_active {
+            format!(
+                "{}{}",
+                c.column.title(),
+                if app.sort_ascending { "↑" } else { "↓" }
+            )
+        } else {
+            c.column.title().to_string()
+        };
+        let style = if is_active {
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            Style::default()
+                .fg(theme.muted)
+                .add_modifier(Modifier::BOLD)
+        };
+        Cell::from(label).style(style)
+    }));
+
+    hits.process_rows = processes.clone().map(|proc| proc.pid).collect();
+
+    let widths = column_widths(columns);
+
+    // Same layout the Table uses internally, so header clicks line up
+    let header_area = Rect::new(inner.x, inner.y, inner.width, inner.height.min(1));
+    let header_cells = Layout::horizontal(widths.clone())
+        .flex(Flex::Start)
+        .spacing(1)
+        .split(header_area);
+    for (cell, column) in header_cells.iter().zip(columns) {
+        hits.sort_headers.push((*cell, column.column));
     }
+    hits.process_list = Some(Rect::new(
+        inner.x,
+        inner.y.saturating_add(1),
+        inner.width,
+        inner.height.saturating_sub(1),
+    ));
+
+    // Start from last frame's offset so the view only scrolls when the
+    // selection would leave it, the same way the Table would on its own
+    let count = processes.len();
+    let visible = inner.height.saturating_sub(1) as usize;
+    let selected = app.process_scroll.min(count.saturating_sub(1));
+    let mut offset = app
+        .hit_areas
+        .process_offset
+        .min(count.saturating_sub(1))
+        .min(selected);
+    if visible > 0 && selected >= offset + visible {
+        offset = selected + 1 - visible;
+    }
+    hits.process_offset = offset;
 
-    let sort_field = match app.process_sort {
-        ProcessSort::Cpu => "CPU",
-        ProcessSort::Memory => "Memory",
-        ProcessSort::Name => "Name",
-        ProcessSort::Pid => "PID",
-    };
-    let sort_order = if app.sort_ascending {
-        "Asc ↑"
-    } else {
-        "Desc ↓"
-    };
+    let rows = processes
+        .skip(offset)
+        .take(visible)
+        .map(|proc| process_row(proc, columns));
 
-    let items: Vec<ListItem> = filtered
-        .iter()
-        .skip(app.process_scroll)
-        .map(|proc| {
-            ListItem::new(Line::from(vec![
-                Span::styled(format!("{:8} ", proc.pid), Style::default().fg(Color::Cyan)),
-                Span::styled(
-                    format!("{:30} ", proc.name),
-                    Style::default().fg(Color::White),
-                ),
-                Span::styled(
-                    format!("{:8.1}% ", proc.cpu_usage),
-                    Style::default().fg(if proc.cpu_usage > 50.0 {
-                        Color::Red
-                    } else if proc.cpu_usage > 20.0 {
-                        Color::Yellow
-                    } else {
-                        Color::Green
-                    }),
-                ),
-                Span::styled(
-                    format_bytes(proc.memory),
-                    Style::default().fg(Color::Magenta),
-                ),
-            ]))
-        })
-        .collect();
+    let table = Table::new(rows, widths)
+        .header(header)
+        .flex(Flex::Start)
+        .row_highlight_style(theme.selection())
+        .block(block);
 
-    let list = List::new(items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(
-                Style::default()
-                    .fg(selected_category.color())
-                    .add_modifier(Modifier::BOLD),
-            )
-            .title(format!(
-                " {} - {} Processes | Sort: {} {} | Press ESC to go back ",
-                selected_category.name(),
-                filtered.len(),
-                sort_field,
-                sort_order
-            ))
-            .title_style(
-                Style::default()
-                    .fg(selected_category.color())
-                    .add_modifier(Modifier::BOLD),
-            ),
-    );
+    let mut state = TableState::default().with_selected((count > 0).then_some(selected - offset));
+    frame.render_stateful_widget(table, area, &mut state);
+}
 
-    frame.render_widget(list, area);
+fn column_widths(columns: &[ColumnConfig]) -> Vec<Constraint> {
+    columns
+        .iter()
+        .map(|c| Constraint::Length(c.width))
+        .collect()
 }
 
-fn render_all_processes(frame: &mut Frame, app: &App, system_info: &SystemInfo, area: Rect) {
-    let mut processes = system_info.get_processes();
-
-    // Sort all processes
-    match app.process_sort {
-        ProcessSort::Cpu => processes.sort_by(|a, b| {
-            let cmp = b
-                .cpu_usage
-                .partial_cmp(&a.cpu_usage)
-                .unwrap_or(std::cmp::Ordering::Equal);
-            if app.sort_ascending {
-                cmp.reverse()
-            } else {
-                cmp
-            }
+fn process_row<'a>(proc: &ProcessInfo, columns: &[ColumnConfig]) -> Row<'a> {
+    Row::new(
+        columns
+            .iter()
+            .map(|c| Cell::from(cell_text(c.column, proc)).style(cell_style(c.column, proc))),
+    )
+}
+
+fn cell_text(column: ProcessColumn, proc: &ProcessInfo) -> String {
+    match column {
+        ProcessColumn::Pid => proc.pid.to_string(),
+        ProcessColumn::Name => proc.name.clone(),
+        ProcessColumn::Cpu => format!("{:5.1}%", proc.cpu_usage),
+        ProcessColumn::Memory => format_bytes(proc.memory),
+        ProcessColumn::User => proc.user.clone(),
+        ProcessColumn::State => proc.state.clone(),
+        ProcessColumn::Threads => proc.threads.to_string(),
+        ProcessColumn::Command => proc.command.clone(),
+        ProcessColumn::StartTime => format_timestamp(proc.start_time),
+        ProcessColumn::RunTime => format_uptime(proc.run_time),
+        ProcessColumn::VirtualMemory => format_bytes(proc.virtual_memory),
+        ProcessColumn::DiskRead => format_bytes(proc.disk_read),
+        ProcessColumn::DiskWrite => format_bytes(proc.disk_written),
+        ProcessColumn::DiskReadRate => format_rate(proc.disk_read_rate),
+        ProcessColumn::DiskWriteRate => format_rate(proc.disk_write_rate),
+        ProcessColumn::Sockets => proc
+            .network
+            .map_or("-".to_string(), |net| net.usage.sockets.to_string()),
+        ProcessColumn::NetReceived => proc.network.map_or("-".to_string(), |net| {
+            format_bytes(net.usage.bytes_received)
         }),
-        ProcessSort::Memory => processes.sort_by(|a, b| {
-            let cmp = b.memory.cmp(&a.memory);
-            if app.sort_ascending {
-                cmp.reverse()
-            } else {
-                cmp
-            }
+        ProcessColumn::NetSent => proc
+            .network
+            .map_or("-".to_string(), |net| format_bytes(net.usage.bytes_sent)),
+        ProcessColumn::NetRate => proc
+            .network
+            .map_or("-".to_string(), |net| format_rate(net.total_rate())),
+        ProcessColumn::Nice => proc.nice.to_string(),
+        ProcessColumn::Priority => proc.priority.to_string(),
+    }
+}
+
+fn cell_style(column: ProcessColumn, proc: &ProcessInfo) -> Style {
+    let theme = theme::current();
+    match column {
+        ProcessColumn::Pid => Style::default().fg(theme.accent),
+        ProcessColumn::Name => Style::default().fg(theme.text),
+        ProcessColumn::Cpu => Style::default().fg(if proc.cpu_usage > 50.0 {
+            theme.critical
+        } else if proc.cpu_usage > 20.0 {
+            theme.warning
+        } else {
+            theme.good
         }),
-        ProcessSort::Name => processes.sort_by(|a, b| {
-            let cmp = a.name.cmp(&b.name);
-            if app.sort_ascending {
-                cmp
+        ProcessColumn::Memory | ProcessColumn::VirtualMemory => {
+            Style::default().fg(theme.secondary)
+        }
+        ProcessColumn::DiskRead | ProcessColumn::DiskWrite => Style::default().fg(theme.tertiary),
+        ProcessColumn::DiskReadRate | ProcessColumn::DiskWriteRate => {
+            Style::default().fg(if proc.disk_read_rate + proc.disk_write_rate > 0 {
+                theme.highlight
             } else {
-                cmp.reverse()
-            }
-        }),
-        ProcessSort::Pid => processes.sort_by(|a, b| {
-            let cmp = a.pid.cmp(&b.pid);
-            if app.sort_ascending {
-                cmp
+                theme.tertiary
+            })
+        }
+        ProcessColumn::Sockets
+        | ProcessColumn::NetReceived
+        | ProcessColumn::NetSent
+        | ProcessColumn::NetRate => {
+            Style::default().fg(if proc.network.is_some_and(|net| net.total_rate() > 0) {
+                theme.highlight
             } else {
-                cmp.reverse()
-            }
-        }),
+                theme.good
+            })
+        }
+        _ => Style::default().fg(theme.muted),
     }
+}
 
-    let sort_field = match app.process_sort {
-        ProcessSort::Cpu => "CPU",
-        ProcessSort::Memory => "Memory",
-        ProcessSort::Name => "Name",
-        ProcessSort::Pid => "PID",
-    };
-    let sort_order = if app.sort_ascending {
-        "Asc ↑"
-    } else {
-        "Desc ↓"
-    };
+fn sort_label(app: &App) -> String {
+    format!(
+        "{} {}",
+        app.process_sort.title(),
+        if app.sort_ascending {
+            "Asc ↑"
+        } else {
+            "Desc ↓"
+        }
+    )
+}
+
+fn render_column_picker(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = theme::current();
+    let columns = &app.config.processes.columns;
 
-    let items: Vec<ListItem> = processes
+    let items: Vec<ListItem> = ProcessColumn::ALL
         .iter()
-        .skip(app.process_scroll)
-        .map(|proc| {
-            ListItem::new(Line::from(vec![
-                Span::styled(format!("{:8} ", proc.pid), Style::default().fg(Color::Cyan)),
-                Span::styled(
-                    format!("{:30} ", proc.name),
-                    Style::default().fg(Color::White),
-                ),
-                Span::styled(
-                    format!("{:8.1}% ", proc.cpu_usage),
-                    Style::default().fg(if proc.cpu_usage > 50.0 {
-                        Color::Red
-                    } else if proc.cpu_usage > 20.0 {
-                        Color::Yellow
-                    } else {
-                        Color::Green
-                    }),
-                ),
-                Span::styled(
-                    format_bytes(proc.memory),
-                    Style::default().fg(Color::Magenta),
-                ),
-            ]))
+        .enumerate()
+        .map(|(idx, column)| {
+            let visible = columns.iter().find(|c| c.column == *column);
+            let marker = if visible.is_some() { "[x]" } else { "[ ]" };
+            let width = visible
+                .map(|c| format!("{:>4}", c.width))
+                .unwrap_or_default();
+
+            let style = if idx == app.column_picker_index {
+                theme.picked()
+            } else {
+                Style::default().fg(theme.text)
+            };
+
+            ListItem::new(Line::from(vec![Span::styled(
+                format!(" {} {:14}{} ", marker, column.title(), width),
+                style,
+            )]))
         })
         .collect();
 
+    let popup_area = super::centered(area, 30, ProcessColumn::ALL.len() as u16 + 2);
+
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .title(format!(
-                " All Processes - {} Total | Sort: {} {} | Press ESC to go back ",
-                processes.len(),
-                sort_field,
-                sort_order
-            ))
-            .title_style(
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            .border_style(Style::default().fg(theme.highlight))
+            .title(" Columns | Space: toggle ")
+            .title_alignment(Alignment::Center),
     );
 
-    frame.render_widget(list, area);
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(list, popup_area);
 }
 
 fn render_command_prompt(frame: &mut Frame, app: &App, area: Rect) {
-    use ratatui::layout::Alignment;
-    use ratatui::widgets::Paragraph;
-
-    // Create a popup area at the bottom
-    let popup_height = 3;
-    let popup_area = Rect {
-        x: area.x,
-        y: area.y + area.height - popup_height,
-        width: area.width,
-        height: popup_height,
-    };
+    let theme = theme::current();
+    let popup_area = super::bottom(area, 3);
 
     let prompt_text = vec![Line::from(vec![
-        Span::styled("Command: ", Style::default().fg(Color::Yellow)),
-        Span::styled(&app.command_buffer, Style::default().fg(Color::White)),
-        Span::styled("_", Style::default().fg(Color::Green)),
+        Span::styled("Command: ", Style::default().fg(theme.highlight)),
+        Span::styled(&app.command_buffer, Style::default().fg(theme.text)),
+        Span::styled("_", Style::default().fg(theme.good)),
     ])];
 
     let prompt = Paragraph::new(prompt_text)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow))
+                .border_style(Style::default().fg(theme.highlight))
                 .title(" Enter Command ")
                 .title_alignment(Alignment::Center),
         )
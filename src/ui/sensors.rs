@@ -0,0 +1,292 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::Line,
+    widgets::{
+        Axis, Block, Borders, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table, TableState,
+    },
+};
+use std::collections::VecDeque;
+
+use crate::{
+    app::{App, HitAreas},
+    sensors::{Chip, Sensor, SensorLevel},
+    system::SystemInfo,
+    theme,
+};
+
+/// Samples drawn in each row's sparkline.
+const SPARKLINE_WIDTH: usize = 24;
+
+pub fn render_sensors(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let monitor = &system_info.sensors;
+    let sensors: Vec<(&Chip, &Sensor)> = monitor.sensors().collect();
+
+    if sensors.is_empty() {
+        let paragraph = Paragraph::new("No hwmon sensors found")
+            .block(Block::default().borders(Borders::ALL).title(" Sensors "));
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+        .split(area);
+
+    render_sensor_table(frame, app, system_info, &sensors, chunks[0], hits);
+
+    if let Some((chip, sensor)) = sensors.get(app.sensor_selected) {
+        render_sensor_history(frame, system_info, chip, sensor, chunks[1]);
+    }
+}
+
+fn level_color(level: SensorLevel) -> Color {
+    let theme = theme::current();
+    match level {
+        SensorLevel::Normal => theme.good,
+        SensorLevel::Warning => theme.warning,
+        SensorLevel::Critical => theme.critical,
+    }
+}
+
+fn render_sensor_table(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    sensors: &[(&Chip, &Sensor)],
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let theme = theme::current();
+    let header = Row::new(
+        ["Chip", "Sensor", "Value", "Min", "Max", "Crit", "History"].map(|title| {
+            Cell::from(title).style(
+                Style::default()
+                    .fg(theme.muted)
+                    .add_modifier(Modifier::BOLD),
+            )
+        }),
+    );
+
+    let limit = |sensor: &Sensor, limit: Option<f64>| {
+        limit.map_or("-".to_string(), |limit| sensor.kind.format(limit))
+    };
+
+    let mut previous_chip = None;
+    let rows = sensors.iter().map(|(chip, sensor)| {
+        // Only the first sensor of each chip names it, so the chips read
+        // as groups
+        let chip_name = if previous_chip == Some(&chip.hwmon) {
+            String::new()
+        } else {
+            format!("{} ({})", chip.name, chip.hwmon)
+        };
+        previous_chip = Some(&chip.hwmon);
+
+        let color = level_color(sensor.level());
+        let history = system_info
+            .sensors
+            .history
+            .get(&sensor.id)
+            .map(sparkline)
+            .unwrap_or_default();
+
+        Row::new(vec![
+            Cell::from(chip_name).style(
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Cell::from(sensor.label.clone()).style(Style::default().fg(theme.text)),
+            Cell::from(sensor.kind.format(sensor.value))
+                .style(Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Cell::from(limit(sensor, sensor.min)).style(Style::default().fg(theme.muted)),
+            Cell::from(limit(sensor, sensor.max)).style(Style::default().fg(theme.muted)),
+            Cell::from(limit(sensor, sensor.crit)).style(Style::default().fg(theme.muted)),
+            Cell::from(history).style(Style::default().fg(color)),
+        ])
+    });
+
+    let warnings = sensors
+        .iter()
+        .filter(|(_, sensor)| sensor.level() != SensorLevel::Normal)
+        .count();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(
+            " Sensors ({}) | Chips: {} | Over limit: {} ",
+            sensors.len(),
+            system_info.sensors.chips.len(),
+            warnings
+        ));
+    let inner = block.inner(area);
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(20),
+            Constraint::Min(16),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(SPARKLINE_WIDTH as u16),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(theme.selection())
+    .block(block);
+
+    let mut state = TableState::default()
+        .with_offset(app.hit_areas.list_offset)
+        .with_selected(Some(app.sensor_selected));
+    frame.render_stateful_widget(table, area, &mut state);
+
+    hits.list_area = Some(Rect::new(
+        inner.x,
+        inner.y.saturating_add(1),
+        inner.width,
+        inner.height.saturating_sub(1),
+    ));
+    hits.list_offset = state.offset();
+    hits.list_rows = sensors.len();
+}
+
+/// The most recent readings as block characters, scaled between their own
+/// minimum and maximum.
+fn sparkline(history: &VecDeque<f64>) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let recent: Vec<f64> = history
+        .iter()
+        .skip(history.len().saturating_sub(SPARKLINE_WIDTH))
+        .copied()
+        .collect();
+    let min = recent.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = recent.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    recent
+        .iter()
+        .map(|value| {
+            let level = if range > 0.0 {
+                ((value - min) / range * (BARS.len() - 1) as f64).round() as usize
+            } else {
+                BARS.len() / 2
+            };
+            BARS[level.min(BARS.len() - 1)]
+        })
+        .collect()
+}
+
+fn render_sensor_history(
+    frame: &mut Frame,
+    system_info: &SystemInfo,
+    chip: &Chip,
+    sensor: &Sensor,
+    area: Rect,
+) {
+    let theme = theme::current();
+    let history = system_info.sensors.history.get(&sensor.id);
+    let data: Vec<(f64, f64)> = history
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, value)| (i as f64, *value))
+        .collect();
+
+    let size = system_info.sensors.history_size as f64;
+    let max_line: Vec<(f64, f64)> = sensor
+        .max
+        .map(|max| vec![(0.0, max), (size, max)])
+        .unwrap_or_default();
+    let crit_line: Vec<(f64, f64)> = sensor
+        .crit
+        .map(|crit| vec![(0.0, crit), (size, crit)])
+        .unwrap_or_default();
+
+    // Keep the limits in view so it's clear how close the reading is
+    let values = data
+        .iter()
+        .map(|(_, value)| *value)
+        .chain(sensor.max)
+        .chain(sensor.crit);
+    let (low, high) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), value| {
+        (low.min(value), high.max(value))
+    });
+    let padding = match high - low {
+        range if range > 0.0 => range * 0.1,
+        _ => (high.abs() * 0.1).max(0.1),
+    };
+    let low = if low >= 0.0 {
+        (low - padding).max(0.0)
+    } else {
+        low - padding
+    };
+    let high = high + padding;
+
+    let mut datasets = vec![
+        Dataset::default()
+            .name(sensor.label.clone())
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(level_color(sensor.level())))
+            .data(&data),
+    ];
+    if !max_line.is_empty() {
+        datasets.push(
+            Dataset::default()
+                .name("max")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.warning))
+                .data(&max_line),
+        );
+    }
+    if !crit_line.is_empty() {
+        datasets.push(
+            Dataset::default()
+                .name("crit")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.critical))
+                .data(&crit_line),
+        );
+    }
+
+    let chart = Chart::new(datasets)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            " {} / {} | {} ",
+            chip.name,
+            sensor.label,
+            sensor.kind.format(sensor.value)
+        )))
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .bounds([0.0, size]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .labels(vec![
+                    Line::from(format!("{:.1}", low)),
+                    Line::from(format!("{:.1}", (low + high) / 2.0)),
+                    Line::from(format!("{:.1}", high)),
+                ])
+                .bounds([low, high]),
+        );
+
+    frame.render_widget(chart, area);
+}
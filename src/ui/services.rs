@@ -0,0 +1,235 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    app::{App, HitAreas},
+    services::Service,
+    system::{SystemInfo, format_bytes},
+    theme,
+};
+
+pub fn render_services(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let theme = theme::current();
+    if let Some(error) = &system_info.services.error {
+        let paragraph = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("  {}", error),
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::ITALIC),
+            )),
+        ])
+        .block(Block::default().borders(Borders::ALL).title(" Services "));
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let services: Vec<&Service> = system_info
+        .services
+        .services
+        .iter()
+        .filter(|service| !app.services_failed_only || service.is_failed())
+        .collect();
+
+    let chunks = if app.service_journal.is_some() {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(area)
+    } else {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0)])
+            .split(area)
+    };
+
+    render_service_table(frame, app, &services, chunks[0], hits);
+
+    if let Some((unit, lines)) = &app.service_journal {
+        render_journal(frame, unit, lines, chunks[1]);
+    }
+
+    if let Some(action) = app.service_confirm
+        && let Some(unit) = hits.service_rows.get(app.service_selected)
+    {
+        render_confirm(frame, &format!("{} {}?", action.name(), unit), area);
+    }
+}
+
+fn render_service_table(
+    frame: &mut Frame,
+    app: &App,
+    services: &[&Service],
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let theme = theme::current();
+    let failed = services.iter().filter(|s| s.is_failed()).count();
+
+    let header = Row::new(
+        [
+            "Unit",
+            "Load",
+            "Active",
+            "Sub",
+            "PID",
+            "CPU",
+            "Memory",
+            "Description",
+        ]
+        .map(|title| {
+            Cell::from(title).style(
+                Style::default()
+                    .fg(theme.muted)
+                    .add_modifier(Modifier::BOLD),
+            )
+        }),
+    );
+
+    let rows = services.iter().map(|service| {
+        let state_color = match service.active.as_str() {
+            "failed" => theme.critical,
+            "active" => theme.good,
+            "activating" | "deactivating" | "reloading" => theme.warning,
+            _ => theme.dim,
+        };
+        let unit_style = if service.is_failed() {
+            Style::default()
+                .fg(theme.critical)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+
+        Row::new(vec![
+            Cell::from(service.unit.clone()).style(unit_style),
+            Cell::from(service.load.clone()).style(Style::default().fg(theme.muted)),
+            Cell::from(service.active.clone()).style(Style::default().fg(state_color)),
+            Cell::from(service.sub.clone()).style(Style::default().fg(state_color)),
+            Cell::from(
+                service
+                    .main_pid
+                    .map_or("-".to_string(), |pid| pid.to_string()),
+            ),
+            Cell::from(if service.control_group.is_some() {
+                format!("{:5.1}%", service.cpu_percent)
+            } else {
+                "-".to_string()
+            })
+            .style(Style::default().fg(theme.accent)),
+            Cell::from(service.memory.map_or("-".to_string(), format_bytes))
+                .style(Style::default().fg(theme.secondary)),
+            Cell::from(service.description.clone()).style(Style::default().fg(theme.muted)),
+        ])
+    });
+
+    let filter = if app.services_failed_only {
+        "failed only"
+    } else {
+        "all"
+    };
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if failed > 0 {
+            theme.critical
+        } else {
+            theme.accent
+        }))
+        .title(format!(
+            " Services ({}) | Failed: {} | Showing {} (f to toggle) ",
+            services.len(),
+            failed,
+            filter
+        ))
+        .title_bottom(" S: start | X: stop | R: restart | Enter: journal ");
+    if let Some(message) = &app.service_message {
+        block = block.title_bottom(Line::from(format!(" {} ", message)).right_aligned());
+    }
+    let inner = block.inner(area);
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(36),
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(10),
+            Constraint::Min(20),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(theme.selection())
+    .block(block);
+
+    let mut state = TableState::default()
+        .with_offset(app.hit_areas.list_offset)
+        .with_selected(Some(app.service_selected));
+    frame.render_stateful_widget(table, area, &mut state);
+
+    hits.list_area = Some(Rect::new(
+        inner.x,
+        inner.y.saturating_add(1),
+        inner.width,
+        inner.height.saturating_sub(1),
+    ));
+    hits.list_offset = state.offset();
+    hits.list_rows = services.len();
+    hits.service_rows = services.iter().map(|s| s.unit.clone()).collect();
+}
+
+fn render_journal(frame: &mut Frame, unit: &str, lines: &[String], area: Rect) {
+    // Newest lines are at the bottom, so show the tail that fits
+    let visible = area.height.saturating_sub(2) as usize;
+    let items: Vec<ListItem> = lines
+        .iter()
+        .skip(lines.len().saturating_sub(visible))
+        .map(|line| ListItem::new(Line::from(line.as_str())))
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Journal: {} | Enter/Esc to close ", unit)),
+    );
+    frame.render_widget(list, area);
+}
+
+fn render_confirm(frame: &mut Frame, question: &str, area: Rect) {
+    let theme = theme::current();
+    let popup_area = super::centered(area, question.chars().count() as u16 + 6, 5);
+
+    let paragraph = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            question,
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        )),
+    ])
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.highlight))
+            .title(" Confirm | y/n ")
+            .title_alignment(Alignment::Center),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(paragraph, popup_area);
+}
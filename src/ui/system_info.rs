@@ -1,24 +1,74 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{
+        Axis, Block, Borders, Cell, Chart, Dataset, GraphType, List, ListItem, ListState,
+        Paragraph, Row, Table, TableState, Wrap,
+    },
 };
 
-use crate::system::{SystemInfo, format_bytes};
+use std::time::Duration;
+
+use crate::{
+    app::{App, DiskPane, HitAreas},
+    system::{DiskInfo, SystemInfo, format_bytes, format_rate, format_uptime},
+    theme,
+};
+
+pub fn render_system_info(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    if let Some(explorer) = &app.explorer {
+        super::explorer::render_explorer(frame, app, explorer, area, hits);
+        return;
+    }
+
+    let disks = system_info.get_disk_info();
+
+    // Narrow terminals get the panes stacked, without the I/O history
+    if super::is_narrow(area) {
+        let chunks = Layout::vertical([
+            Constraint::Percentage(30),
+            Constraint::Percentage(35),
+            Constraint::Percentage(35),
+        ])
+        .split(area);
+        render_general_info(frame, system_info, chunks[0]);
+        render_disk_info(frame, app, system_info, disks, chunks[1], hits);
+        render_device_table(frame, app, system_info, disks, chunks[2], hits);
+        return;
+    }
 
-pub fn render_system_info(frame: &mut Frame, system_info: &SystemInfo, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
         .split(area);
 
-    render_general_info(frame, system_info, chunks[0]);
-    render_disk_info(frame, system_info, chunks[1]);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(chunks[0]);
+
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[1]);
+
+    render_general_info(frame, system_info, top[0]);
+    render_disk_info(frame, app, system_info, disks, top[1], hits);
+    render_device_table(frame, app, system_info, disks, bottom[0], hits);
+    render_device_history(frame, app, system_info, bottom[1]);
 }
 
 pub fn render_general_info(frame: &mut Frame, system_info: &SystemInfo, area: Rect) {
+    let theme = theme::current();
     let info = system_info.get_system_info();
 
     let mut lines = Vec::new();
@@ -39,10 +89,10 @@ pub fn render_general_info(frame: &mut Frame, system_info: &SystemInfo, area: Re
                 Span::styled(
                     format!("{:15}: ", key),
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(value.clone(), Style::default().fg(Color::White)),
+                Span::styled(value.clone(), Style::default().fg(theme.text)),
             ]));
         }
     }
@@ -58,10 +108,40 @@ pub fn render_general_info(frame: &mut Frame, system_info: &SystemInfo, area: Re
     frame.render_widget(paragraph, area);
 }
 
-pub fn render_disk_info(frame: &mut Frame, system_info: &SystemInfo, area: Rect) {
-    let disks = system_info.get_disk_info();
+pub fn render_disk_info(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    disks: &[DiskInfo],
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let theme = theme::current();
+    let settings = &app.config.disks;
+    let visible: Vec<&DiskInfo> = disks
+        .iter()
+        .filter(|disk| settings.show_pseudo || !disk.is_pseudo(&settings.hidden_filesystems))
+        .collect();
+
+    let low_on_inodes = visible
+        .iter()
+        .filter(|disk| {
+            disk.inode_percent()
+                .is_some_and(|percent| percent >= settings.inode_warning_percent)
+        })
+        .count();
+
+    let warning_horizon = Duration::from_secs(settings.fill_warning_minutes * 60);
+    let filling_soon = |disk: &DiskInfo| {
+        settings.fill_warning_minutes > 0
+            && system_info
+                .disk_trend
+                .trend(&disk.mount_point)
+                .is_some_and(|trend| trend.time_to_full <= warning_horizon)
+    };
+    let filling_up = visible.iter().filter(|disk| filling_soon(disk)).count();
 
-    let items: Vec<ListItem> = disks
+    let items: Vec<ListItem> = visible
         .iter()
         .map(|disk| {
             let used = disk.total_space - disk.available_space;
@@ -75,27 +155,334 @@ pub fn render_disk_info(frame: &mut Frame, system_info: &SystemInfo, area: Rect)
             let filled = ((percent / 100.0) * bar_width as f64) as usize;
             let bar = format!("[{}{}]", "=".repeat(filled), " ".repeat(bar_width - filled));
 
-            ListItem::new(vec![
-                Line::from(vec![Span::styled(
-                    format!("{}", disk.mount_point),
+            let mut title = vec![
+                Span::styled(
+                    disk.mount_point.to_string(),
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
-                )]),
-                Line::from(vec![
-                    Span::raw(format!("  {} ", bar)),
-                    Span::raw(format!(
-                        "{:.1}% ({} / {})",
+                ),
+                Span::styled(
+                    format!(" on {} ({})", disk.source, disk.file_system),
+                    Style::default().fg(theme.muted),
+                ),
+            ];
+            if disk.is_read_only {
+                title.push(Span::styled(" read-only", Style::default().fg(theme.warning)));
+            }
+            if disk.is_removable {
+                title.push(Span::styled(" removable", Style::default().fg(theme.secondary)));
+            }
+
+            let mut usage = vec![
+                Span::raw(format!("  {} ", bar)),
+                Span::raw(format!(
+                    "{:.1}% ({} / {})",
+                    percent,
+                    format_bytes(used),
+                    format_bytes(disk.total_space)
+                )),
+            ];
+            if let Some(stats) = disk
+                .device
+                .as_deref()
+                .and_then(|device| system_info.disk_io.device(device))
+            {
+                usage.push(Span::styled(
+                    format!(
+                        " | R {} W {}",
+                        format_rate(stats.read_rate),
+                        format_rate(stats.write_rate)
+                    ),
+                    Style::default().fg(theme.highlight),
+                ));
+            }
+
+            if let Some(trend) = system_info.disk_trend.trend(&disk.mount_point) {
+                let style = if filling_soon(disk) {
+                    Style::default().fg(theme.critical).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.muted)
+                };
+                usage.push(Span::styled(
+                    format!(
+                        " | +{} full in ~{}",
+                        format_rate(trend.bytes_per_second as u64),
+                        format_uptime(trend.time_to_full.as_secs())
+                    ),
+                    style,
+                ));
+            }
+
+            let inodes = match (disk.inode_percent(), disk.inodes_total, disk.inodes_free) {
+                (Some(percent), Some(total), Some(free))
+                    if percent >= settings.inode_warning_percent =>
+                {
+                    Span::styled(
+                        format!(
+                            "  inodes {:.1}% used ({} of {} free) - new files will fail despite free space",
+                            percent, free, total
+                        ),
+                        Style::default().fg(theme.critical).add_modifier(Modifier::BOLD),
+                    )
+                }
+                (Some(percent), Some(total), Some(free)) => Span::styled(
+                    format!(
+                        "  inodes {:.1}% ({} / {})",
                         percent,
-                        format_bytes(used),
-                        format_bytes(disk.total_space)
-                    )),
+                        total - free,
+                        total
+                    ),
+                    Style::default().fg(theme.muted),
+                ),
+                _ => Span::styled("  inodes n/a", Style::default().fg(theme.dim)),
+            };
+
+            ListItem::new(vec![
+                Line::from(title),
+                Line::from(usage),
+                Line::from(vec![
+                    inodes,
+                    Span::styled(
+                        format!(" | {}", disk.mount_options),
+                        Style::default().fg(theme.dim),
+                    ),
                 ]),
             ])
         })
         .collect();
 
-    let list = List::new(items).block(Block::default().title(" Disk Usage ").borders(Borders::ALL));
+    let focused = app.disk_pane == DiskPane::Mounts;
+    let mut title = format!(
+        " Disk Usage ({} of {} mounts) | e: explore | a: {} pseudo mounts ",
+        visible.len(),
+        disks.len(),
+        if settings.show_pseudo { "hide" } else { "show" }
+    );
+    if low_on_inodes > 0 {
+        title.push_str(&format!("| {} low on inodes ", low_on_inodes));
+    }
+    if filling_up > 0 {
+        title.push_str(&format!("| {} filling up ", filling_up));
+    }
+
+    let border_color = if low_on_inodes > 0 || filling_up > 0 {
+        theme.critical
+    } else if focused {
+        theme.accent
+    } else {
+        theme.text
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color)),
+        )
+        .highlight_style(if focused {
+            theme.selection()
+        } else {
+            Style::default()
+        });
+
+    // Mount entries span several lines, so they aren't mouse targets; the
+    // list scrolls itself to keep the selection in view.
+    let mut state = ListState::default().with_selected(Some(app.mount_selected));
+    frame.render_stateful_widget(list, area, &mut state);
+
+    hits.mount_rows = visible
+        .iter()
+        .map(|disk| disk.mount_point.clone())
+        .collect();
+    if focused {
+        hits.list_rows = visible.len();
+    }
+}
+
+fn render_device_table(
+    frame: &mut Frame,
+    app: &App,
+    system_info: &SystemInfo,
+    disks: &[DiskInfo],
+    area: Rect,
+    hits: &mut HitAreas,
+) {
+    let theme = theme::current();
+    let devices = &system_info.disk_io.devices;
+
+    let header = Row::new(
+        [
+            "Device",
+            "Read/s",
+            "Write/s",
+            "r IOPS",
+            "w IOPS",
+            "Queue",
+            "Await r/w",
+            "Util",
+            "Mounts",
+        ]
+        .map(|title| {
+            Cell::from(title).style(
+                Style::default()
+                    .fg(theme.muted)
+                    .add_modifier(Modifier::BOLD),
+            )
+        }),
+    );
+
+    let rows = devices.iter().map(|device| {
+        let mounts: Vec<&str> = disks
+            .iter()
+            .filter(|disk| disk.device.as_deref() == Some(device.name.as_str()))
+            .map(|disk| disk.mount_point.as_str())
+            .collect();
+
+        let util_color = if device.utilization > 80.0 {
+            theme.critical
+        } else if device.utilization > 40.0 {
+            theme.warning
+        } else {
+            theme.good
+        };
+
+        Row::new(vec![
+            Cell::from(device.name.clone()).style(Style::default().fg(theme.accent)),
+            Cell::from(format_rate(device.read_rate)).style(Style::default().fg(theme.good)),
+            Cell::from(format_rate(device.write_rate)).style(Style::default().fg(theme.critical)),
+            Cell::from(format!("{:.0}", device.read_iops)),
+            Cell::from(format!("{:.0}", device.write_iops)),
+            Cell::from(format!("{:.1} ({})", device.queue_depth, device.in_flight)),
+            Cell::from(format!(
+                "{:.1}/{:.1} ms",
+                device.read_latency_ms, device.write_latency_ms
+            )),
+            Cell::from(format!("{:.0}%", device.utilization))
+                .style(Style::default().fg(util_color)),
+            Cell::from(mounts.join(", ")).style(Style::default().fg(theme.muted)),
+        ])
+    });
+
+    let focused = app.disk_pane == DiskPane::Devices;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if focused { theme.accent } else { theme.text }))
+        .title(" Block Device I/O | d: switch list ");
+    let inner = block.inner(area);
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(9),
+            Constraint::Length(16),
+            Constraint::Length(5),
+            Constraint::Min(10),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(theme.selection())
+    .block(block);
+
+    let mut state = TableState::default()
+        .with_offset(if focused {
+            app.hit_areas.list_offset
+        } else {
+            0
+        })
+        .with_selected(Some(app.disk_selected));
+    frame.render_stateful_widget(table, area, &mut state);
+
+    if focused {
+        hits.list_area = Some(Rect::new(
+            inner.x,
+            inner.y.saturating_add(1),
+            inner.width,
+            inner.height.saturating_sub(1),
+        ));
+        hits.list_offset = state.offset();
+        hits.list_rows = devices.len();
+    }
+}
+
+fn render_device_history(frame: &mut Frame, app: &App, system_info: &SystemInfo, area: Rect) {
+    let theme = theme::current();
+    let disk_io = &system_info.disk_io;
+    let Some(device) = disk_io.devices.get(app.disk_selected) else {
+        let paragraph = Paragraph::new("No block device activity").block(
+            Block::default()
+                .title(" I/O History ")
+                .borders(Borders::ALL),
+        );
+        frame.render_widget(paragraph, area);
+        return;
+    };
+
+    let history = disk_io.history.get(&device.name);
+    let samples = |pick: fn(&(u64, u64)) -> u64| -> Vec<(f64, f64)> {
+        history
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, sample)| (i as f64, pick(sample) as f64))
+            .collect()
+    };
+    let reads = samples(|sample| sample.0);
+    let writes = samples(|sample| sample.1);
+
+    // Keep a small floor so an idle device doesn't get a 0..0 axis
+    let max_y = reads
+        .iter()
+        .chain(&writes)
+        .fold(1024.0f64, |max, &(_, value)| max.max(value));
+
+    let datasets = vec![
+        Dataset::default()
+            .name("read")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.good))
+            .data(&reads),
+        Dataset::default()
+            .name("write")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.critical))
+            .data(&writes),
+    ];
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(format!(
+                    " {} I/O History | read {} | written {} ",
+                    device.name,
+                    format_bytes(device.total_read),
+                    format_bytes(device.total_written)
+                ))
+                .borders(Borders::ALL),
+        )
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .bounds([0.0, disk_io.history_size as f64]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .labels(vec![
+                    Line::from("0"),
+                    Line::from(format_rate((max_y / 2.0) as u64)),
+                    Line::from(format_rate(max_y as u64)),
+                ])
+                .bounds([0.0, max_y]),
+        );
 
-    frame.render_widget(list, area);
+    frame.render_widget(chart, area);
 }
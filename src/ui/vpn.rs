@@ -1,29 +1,51 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
 };
 
+use crate::theme;
 use crate::vpn::{VpnProvider, VpnStatus};
 
+/// Rows the status box and the map take up.
+const STATUS_HEIGHT: u16 = 8;
+const MAP_HEIGHT: u16 = 26;
+/// Below this the details are dropped too and only the status is left.
+const DETAILS_MIN_HEIGHT: u16 = 5;
+
 pub fn render_vpn(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect) {
+    // The map is the first to go, then the details
+    if area.height < STATUS_HEIGHT + DETAILS_MIN_HEIGHT {
+        render_vpn_status(frame, vpn_status, area);
+        return;
+    }
+    let show_map =
+        !super::is_narrow(area) && area.height >= STATUS_HEIGHT + MAP_HEIGHT + DETAILS_MIN_HEIGHT;
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(8),
-            Constraint::Length(26),
-            Constraint::Min(0),
-        ])
+        .constraints(if show_map {
+            vec![
+                Constraint::Length(STATUS_HEIGHT),
+                Constraint::Length(MAP_HEIGHT),
+                Constraint::Min(0),
+            ]
+        } else {
+            vec![Constraint::Length(STATUS_HEIGHT), Constraint::Min(0)]
+        })
         .split(area);
 
     render_vpn_status(frame, vpn_status, chunks[0]);
-    render_vpn_map(frame, vpn_status, chunks[1]);
-    render_vpn_details(frame, vpn_status, chunks[2]);
+    if show_map {
+        render_vpn_map(frame, vpn_status, chunks[1]);
+    }
+    render_vpn_details(frame, vpn_status, chunks[chunks.len() - 1]);
 }
 
 pub fn render_vpn_status(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect) {
+    let theme = theme::current();
     let status_text = if vpn_status.connected {
         "CONNECTED"
     } else {
@@ -31,9 +53,9 @@ pub fn render_vpn_status(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
     };
 
     let status_color = if vpn_status.connected {
-        Color::Green
+        theme.good
     } else {
-        Color::Red
+        theme.critical
     };
 
     let status_indicator = if vpn_status.connected { "●" } else { "○" };
@@ -57,9 +79,9 @@ pub fn render_vpn_status(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
             Span::styled(
                 vpn_status.provider.name(),
                 Style::default().fg(if vpn_status.connected {
-                    Color::Green
+                    theme.good
                 } else {
-                    Color::Yellow
+                    theme.highlight
                 }),
             ),
         ]));
@@ -68,14 +90,14 @@ pub fn render_vpn_status(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
     if let Some(ref server) = vpn_status.server {
         lines.push(Line::from(vec![
             Span::raw("  Server: "),
-            Span::styled(server.clone(), Style::default().fg(Color::Cyan)),
+            Span::styled(server.clone(), Style::default().fg(theme.accent)),
         ]));
     }
 
     if let Some(ref country) = vpn_status.country {
         lines.push(Line::from(vec![
             Span::raw("  Country: "),
-            Span::styled(country.clone(), Style::default().fg(Color::White)),
+            Span::styled(country.clone(), Style::default().fg(theme.text)),
         ]));
     }
 
@@ -91,9 +113,9 @@ pub fn render_vpn_status(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
                 .title(title)
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(if vpn_status.connected {
-                    Color::Green
+                    theme.good
                 } else {
-                    Color::Red
+                    theme.critical
                 })),
         )
         .alignment(Alignment::Left);
@@ -102,6 +124,7 @@ pub fn render_vpn_status(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
 }
 
 pub fn render_vpn_details(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect) {
+    let theme = theme::current();
     let mut items = Vec::new();
 
     if vpn_status.connected {
@@ -109,13 +132,10 @@ pub fn render_vpn_details(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
             Span::styled(
                 "Provider:       ",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(
-                vpn_status.provider.name(),
-                Style::default().fg(Color::Green),
-            ),
+            Span::styled(vpn_status.provider.name(), Style::default().fg(theme.good)),
         ])));
 
         if let Some(ref server) = vpn_status.server {
@@ -123,7 +143,7 @@ pub fn render_vpn_details(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
                 Span::styled(
                     "Server:         ",
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(server.clone()),
@@ -135,7 +155,7 @@ pub fn render_vpn_details(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
                 Span::styled(
                     "Country:        ",
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(country.clone()),
@@ -147,7 +167,7 @@ pub fn render_vpn_details(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
                 Span::styled(
                     "City:           ",
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(city.clone()),
@@ -159,7 +179,7 @@ pub fn render_vpn_details(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
                 Span::styled(
                     "IP Address:     ",
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(ip.clone()),
@@ -171,7 +191,7 @@ pub fn render_vpn_details(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
                 Span::styled(
                     "Protocol:       ",
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(protocol.clone()),
@@ -183,7 +203,7 @@ pub fn render_vpn_details(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
                 Span::styled(
                     "Interface:      ",
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(interface.clone()),
@@ -195,7 +215,7 @@ pub fn render_vpn_details(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
                 Span::styled(
                     "Connected Time: ",
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(time.clone()),
@@ -206,7 +226,7 @@ pub fn render_vpn_details(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
         items.push(ListItem::new(Line::from(vec![Span::styled(
             "Raw Status:",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         )])));
 
@@ -218,7 +238,7 @@ pub fn render_vpn_details(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
         items.push(ListItem::new(Line::from(vec![Span::styled(
             "Not connected to ProtonVPN",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::ITALIC),
         )])));
         items.push(ListItem::new(Line::from("")));
@@ -228,18 +248,18 @@ pub fn render_vpn_details(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect)
         items.push(ListItem::new(Line::from("")));
         items.push(ListItem::new(Line::from(vec![Span::styled(
             "  protonvpn-cli connect",
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(theme.accent),
         )])));
         items.push(ListItem::new(Line::from(vec![Span::styled(
             "  protonvpn connect",
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(theme.accent),
         )])));
 
         if !vpn_status.raw_output.is_empty() {
             items.push(ListItem::new(Line::from("")));
             items.push(ListItem::new(Line::from(vec![Span::styled(
                 "Info:",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(theme.highlight),
             )])));
             items.push(ListItem::new(Line::from(format!(
                 "  {}",
@@ -272,6 +292,7 @@ pub fn render_vpn_map(frame: &mut Frame, vpn_status: &VpnStatus, area: Rect) {
 }
 
 pub fn get_ascii_map<'a>(vpn_status: &VpnStatus, _user_location: &str) -> Vec<Line<'a>> {
+    let theme = theme::current();
     let mut lines = Vec::new();
 
     let map_content = std::fs::read_to_string("map/map.txt").unwrap_or_else(|_| {
@@ -281,9 +302,9 @@ pub fn get_ascii_map<'a>(vpn_status: &VpnStatus, _user_location: &str) -> Vec<Li
     });
 
     let color = if vpn_status.connected {
-        Color::Green
+        theme.good
     } else {
-        Color::Red
+        theme.critical
     };
 
     for map_line in map_content.lines() {
@@ -308,14 +329,14 @@ pub fn get_ascii_map<'a>(vpn_status: &VpnStatus, _user_location: &str) -> Vec<Li
 
         lines.push(Line::from(vec![Span::styled(
             info,
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.good).add_modifier(Modifier::BOLD),
         )]));
     } else {
         lines.push(Line::from(vec![Span::styled(
             "○ Not Connected",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.critical)
+                .add_modifier(Modifier::BOLD),
         )]));
     }
 
@@ -22,6 +22,19 @@ impl VpnProvider {
     }
 }
 
+/// Interfaces VPN clients create, and whose client they belong to.
+pub const VPN_INTERFACES: [(&str, VpnProvider); 9] = [
+    ("proton0", VpnProvider::ProtonVPN),
+    ("pvpn0", VpnProvider::ProtonVPN),
+    ("nordlynx", VpnProvider::NordVPN),
+    ("nordtun", VpnProvider::NordVPN),
+    ("wg-mullvad", VpnProvider::Mullvad),
+    ("wg0", VpnProvider::Generic),  // Generic WireGuard
+    ("tun0", VpnProvider::Generic), // Generic tunnel
+    ("tap0", VpnProvider::Generic), // Generic TAP
+    ("utun", VpnProvider::Generic), // macOS VPN tunnel
+];
+
 #[derive(Debug, Clone)]
 pub struct VpnStatus {
     pub connected: bool,
@@ -99,7 +112,7 @@ impl VpnStatus {
 
     fn check_protonvpn_process() -> Self {
         // Check if ProtonVPN process is running
-        let ps_output = Command::new("ps").args(&["aux"]).output();
+        let ps_output = Command::new("ps").args(["aux"]).output();
 
         if let Ok(output) = ps_output {
             let stdout = String::from_utf8_lossy(&output.stdout);
@@ -110,7 +123,7 @@ impl VpnStatus {
 
                 // Try to get connection info from interfaces
                 for iface in ["proton0", "pvpn0", "tun0", "wg0"] {
-                    let if_output = Command::new("ip").args(&["addr", "show", iface]).output();
+                    let if_output = Command::new("ip").args(["addr", "show", iface]).output();
 
                     if let Ok(if_output) = if_output {
                         let if_stdout = String::from_utf8_lossy(&if_output.stdout);
@@ -124,7 +137,7 @@ impl VpnStatus {
                             // Extract IP
                             for line in if_stdout.lines() {
                                 if line.contains("inet ") {
-                                    let parts: Vec<&str> = line.trim().split_whitespace().collect();
+                                    let parts: Vec<&str> = line.split_whitespace().collect();
                                     if parts.len() >= 2 {
                                         status.ip = Some(parts[1].to_string());
                                     }
@@ -271,20 +284,8 @@ impl VpnStatus {
     fn check_vpn_interface() -> Self {
         let mut status = Self::new();
 
-        let interfaces = [
-            ("proton0", VpnProvider::ProtonVPN),
-            ("pvpn0", VpnProvider::ProtonVPN),
-            ("nordlynx", VpnProvider::NordVPN),
-            ("nordtun", VpnProvider::NordVPN),
-            ("wg-mullvad", VpnProvider::Mullvad),
-            ("wg0", VpnProvider::Generic),  // Generic WireGuard
-            ("tun0", VpnProvider::Generic), // Generic tunnel
-            ("tap0", VpnProvider::Generic), // Generic TAP
-            ("utun", VpnProvider::Generic), // macOS VPN tunnel
-        ];
-
-        for (iface, provider) in interfaces {
-            let output = Command::new("ip").args(&["addr", "show", iface]).output();
+        for (iface, provider) in VPN_INTERFACES {
+            let output = Command::new("ip").args(["addr", "show", iface]).output();
 
             if let Ok(output) = output {
                 let stdout = String::from_utf8_lossy(&output.stdout);
@@ -298,7 +299,7 @@ impl VpnStatus {
 
                     for line in stdout.lines() {
                         if line.contains("inet ") {
-                            let parts: Vec<&str> = line.trim().split_whitespace().collect();
+                            let parts: Vec<&str> = line.split_whitespace().collect();
                             if parts.len() >= 2 {
                                 status.ip = Some(parts[1].to_string());
                             }
@@ -318,23 +319,22 @@ impl VpnStatus {
         status
     }
 
-
-//     // Function to get public IP address, maybe i will reanable it later.
-//     // but i had to curl it, so i couldnt be bothered. to have it enabled.
-//     pub fn get_public_ip() -> Option<String> {
-//         let output = Command::new("curl")
-//             .args(&["-s", "https://api.ipify.org"])
-//             .output();
-//
-//         if let Ok(output) = output {
-//             let ip = String::from_utf8_lossy(&output.stdout).trim().to_string();
-//             if !ip.is_empty() && ip.len() < 50 {
-//                 return Some(ip);
-//             }
-//         }
-//
-//         None
-//     }
+    //     // Function to get public IP address, maybe i will reanable it later.
+    //     // but i had to curl it, so i couldnt be bothered. to have it enabled.
+    //     pub fn get_public_ip() -> Option<String> {
+    //         let output = Command::new("curl")
+    //             .args(&["-s", "https://api.ipify.org"])
+    //             .output();
+    //
+    //         if let Ok(output) = output {
+    //             let ip = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    //             if !ip.is_empty() && ip.len() < 50 {
+    //                 return Some(ip);
+    //             }
+    //         }
+    //
+    //         None
+    //     }
 }
 
 pub fn get_vpn_status() -> VpnStatus {